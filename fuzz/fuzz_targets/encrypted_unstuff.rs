@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ssp::encrypted::unstuff;
+
+// The first byte is the end index, the rest is the stuffed buffer.
+fuzz_target!(|data: &[u8]| {
+    if let Some((&end, buf)) = data.split_first() {
+        let mut buf = buf.to_vec();
+        if let Ok(new_end) = unstuff(buf.as_mut(), end.into()) {
+            assert!(new_end <= end.into());
+        }
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ssp::FirmwareHeader;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(header) = FirmwareHeader::try_from(data) {
+        // a parsed header serializes back to the same bytes
+        let bytes: [u8; ssp::FIRMWARE_HEADER_LEN] = header.try_into().unwrap();
+        assert_eq!(&data[..bytes.len()], bytes.as_ref());
+    }
+});
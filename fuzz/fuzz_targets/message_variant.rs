@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ssp::{MessageType, MessageVariant};
+
+// The first byte selects the expected response type, the rest is the message buffer.
+fuzz_target!(|data: &[u8]| {
+    if let Some((&msg_type, buf)) = data.split_first() {
+        let _ = MessageVariant::from_buf(buf, MessageType::from(msg_type));
+    }
+});
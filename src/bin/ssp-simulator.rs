@@ -0,0 +1,34 @@
+//! Serves a simulated NV200 validator on a pseudo-terminal, and prints the tty path.
+//!
+//! Usage: `ssp-simulator [LINK]`
+//!
+//! When `LINK` is provided, it is created as a symbolic link to the tty path, giving host
+//! applications a stable path to open.
+
+#[cfg(unix)]
+fn main() -> ssp::Result<()> {
+    use std::io::Write;
+
+    use ssp::simulator::{PtyHarness, Simulator};
+
+    let harness = PtyHarness::start(Simulator::new())?;
+
+    if let Some(link) = std::env::args().nth(1) {
+        if std::fs::symlink_metadata(link.as_str()).is_ok() {
+            std::fs::remove_file(link.as_str())?;
+        }
+        std::os::unix::fs::symlink(harness.path(), link.as_str())?;
+    }
+
+    let mut stdout = std::io::stdout();
+    writeln!(stdout, "{}", harness.path())?;
+    stdout.flush()?;
+
+    harness.wait()
+}
+
+#[cfg(not(unix))]
+fn main() {
+    eprintln!("ssp-simulator requires pseudo-terminal support");
+    std::process::exit(1);
+}
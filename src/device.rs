@@ -0,0 +1,916 @@
+//! High-level host driver for SSP devices.
+//!
+//! Performs the command sequence needed to open an encrypted (eSSP) session with a device:
+//!
+//! ```no_build,no_run
+//! Sync -> SetGenerator -> SetModulus -> RequestKeyExchange -> SetupRequest -> SetInhibits -> Enable
+//! ```
+//!
+//! After the key exchange, all commands are sent using the encryption layer.
+
+use alloc::format;
+
+#[cfg(feature = "async")]
+mod async_device;
+#[cfg(feature = "std")]
+mod poller;
+
+#[cfg(feature = "async")]
+pub use async_device::*;
+#[cfg(feature = "std")]
+pub use poller::*;
+
+use crate::{
+    configure_channels, len, message::index, primes::Generator, seed, ChannelValue, CommandOps,
+    CountryCode, DenominationRoute, DeviceStatus, DisableCommand, DisableEvent, DisableResponse,
+    EnableBitfield, EnableBitfieldList, EnableCommand, EnableEvent, EnableResponse,
+    EncryptedCommand, EncryptedResponse, Error, EsspSession, Event, EventAckCommand,
+    EventAckResponse, EventAckTracker, EventPayload, FloatAmountCommand, FloatAmountResponse,
+    FloatByDenominationCommand, FloatByDenominationResponse, GeneratorKey, GetAllLevelsCommand,
+    GetAllLevelsResponse, GetDenominationLevelCommand, GetDenominationLevelResponse,
+    GetDenominationRouteCommand, GetDenominationRouteResponse, GetMinimumPayoutCommand,
+    GetMinimumPayoutResponse, HaltPayoutCommand, HaltPayoutResponse, InhibitChannels,
+    KeyNegotiator, MessageType, MessageVariant, ModulusKey, PayoutAmountCommand,
+    PayoutAmountResponse, PayoutByDenominationCommand, PayoutByDenominationResponse,
+    PayoutDenominationList, PayoutOption, PollCommand, PollEventIter, PollResponse,
+    PollWithAckCommand, PollWithAckResponse, ProtocolVersion, RandomKey, RejectCommand,
+    RejectEvent, RejectResponse, RequestEngine, ResponseStatus, Result, ResyncPolicy, SequenceFlag,
+    SequenceId, SetDenominationLevelCommand, SetDenominationLevelResponse,
+    SetDenominationRouteCommand, SetDenominationRouteResponse, SetInhibitsCommand,
+    SetInhibitsResponse, SetupRequestCommand, SetupRequestResponse, StatusEvent, SyncCommand,
+    SyncResponse, Transport, WrappedEncryptedMessage, STX,
+};
+
+/// Host-side driver for an SSP device.
+///
+/// Manages the [SequenceId] of sent commands, and the [EsspSession] of encrypted commands.
+///
+/// Commands are sent through a [RequestEngine], so lost or corrupted replies are re-transmitted
+/// with the same [SequenceFlag].
+pub struct Device<T: Transport> {
+    engine: RequestEngine<T>,
+    session: Option<EsspSession>,
+    resync: ResyncPolicy,
+    resync_negotiator: Option<KeyNegotiator>,
+    setup: Option<SetupRequestResponse>,
+}
+
+impl<T: Transport> Device<T> {
+    /// Creates a new [Device] communicating over the provided [Transport].
+    pub fn new(transport: T) -> Self {
+        Self {
+            engine: RequestEngine::new(transport),
+            session: None,
+            resync: ResyncPolicy::default(),
+            resync_negotiator: None,
+            setup: None,
+        }
+    }
+
+    /// Builder function that sets the device address.
+    pub fn with_address(mut self, address: u8) -> Self {
+        let mut sequence_id = self.engine.sequence_id();
+        sequence_id.set_id(address);
+
+        self.engine.set_sequence_id(sequence_id);
+        self
+    }
+
+    /// Builder function that sets the number of retransmissions for each command.
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.engine.set_retries(retries);
+        self
+    }
+
+    /// Builder function that sets the [ResyncPolicy] for encrypted replies that fail
+    /// verification.
+    ///
+    /// Defaults to [ResyncPolicy::Reject].
+    ///
+    /// Without the `std` feature, [ResyncPolicy::Renegotiate] also needs a [KeyNegotiator], see
+    /// [with_resync_negotiator](Self::with_resync_negotiator).
+    pub fn with_resync_policy(mut self, policy: ResyncPolicy) -> Self {
+        self.resync = policy;
+        self
+    }
+
+    /// Gets the [ResyncPolicy] for encrypted replies that fail verification.
+    pub fn resync_policy(&self) -> ResyncPolicy {
+        self.resync
+    }
+
+    /// Builder function that sets the [KeyNegotiator] used to start a new [EsspSession] under
+    /// [ResyncPolicy::Renegotiate].
+    ///
+    /// With the `std` feature, new sessions default to random primes generated from system
+    /// entropy. Without `std` there is no entropy source, so the caller supplies the
+    /// negotiator, e.g. seeded from a hardware RNG. Otherwise, the resync fails with
+    /// [Error::Encryption], and the current session is kept.
+    pub fn with_resync_negotiator(mut self, negotiator: KeyNegotiator) -> Self {
+        self.resync_negotiator = Some(negotiator);
+        self
+    }
+
+    /// Gets a reference to the [Transport].
+    pub fn transport(&self) -> &T {
+        self.engine.transport()
+    }
+
+    /// Gets a mutable reference to the [Transport].
+    pub fn transport_mut(&mut self) -> &mut T {
+        self.engine.transport_mut()
+    }
+
+    /// Consumes the [Device], returning the [Transport].
+    pub fn into_transport(self) -> T {
+        self.engine.into_transport()
+    }
+
+    /// Gets a reference to the [RequestEngine].
+    pub fn engine(&self) -> &RequestEngine<T> {
+        &self.engine
+    }
+
+    /// Gets a mutable reference to the [RequestEngine].
+    pub fn engine_mut(&mut self) -> &mut RequestEngine<T> {
+        &mut self.engine
+    }
+
+    /// Gets the [SequenceId] used for the next command.
+    pub fn sequence_id(&self) -> SequenceId {
+        self.engine.sequence_id()
+    }
+
+    /// Gets whether an encryption key has been negotiated with the device.
+    pub fn is_encrypted(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Gets the [EsspSession] negotiated with the device.
+    pub fn session(&self) -> Option<&EsspSession> {
+        self.session.as_ref()
+    }
+
+    /// Gets the [SetupRequestResponse] received while connecting to the device.
+    pub fn setup(&self) -> Option<&SetupRequestResponse> {
+        self.setup.as_ref()
+    }
+
+    /// Gets the [ProtocolVersion] reported by the device while connecting.
+    ///
+    /// Defaults to [ProtocolVersion::new] before the [SetupRequestResponse] is received.
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.setup
+            .as_ref()
+            .and_then(|setup| setup.protocol_version().ok())
+            .unwrap_or_default()
+    }
+
+    /// Opens an encrypted session with the device, and enables it to accept notes.
+    ///
+    /// Key negotiation uses random primes generated from system entropy.
+    #[cfg(feature = "std")]
+    pub fn connect(&mut self) -> Result<SetupRequestResponse> {
+        self.connect_with_negotiator(&mut KeyNegotiator::from_entropy())
+    }
+
+    /// Opens an encrypted session with the device using the provided keys, and enables it to
+    /// accept notes.
+    ///
+    /// Configures the global channel values, and enables all channels reported by the device.
+    ///
+    /// See [negotiate_key](Self::negotiate_key) for the requirements on the keys.
+    pub fn connect_with_keys(
+        &mut self,
+        generator: &GeneratorKey,
+        modulus: &ModulusKey,
+        random: &RandomKey,
+    ) -> Result<SetupRequestResponse> {
+        self.connect_with_negotiator(&mut key_negotiator(generator, modulus, random)?)
+    }
+
+    /// Opens an encrypted session with the device using the [KeyNegotiator], and enables it to
+    /// accept notes.
+    ///
+    /// Configures the global channel values, and enables all channels reported by the device.
+    pub fn connect_with_negotiator(
+        &mut self,
+        negotiator: &mut KeyNegotiator,
+    ) -> Result<SetupRequestResponse> {
+        let setup = self.initialize_with_negotiator(negotiator)?;
+
+        self.enable()?;
+
+        Ok(setup)
+    }
+
+    /// Opens an encrypted session with the device, without enabling it to accept notes.
+    ///
+    /// Key negotiation uses random primes generated from system entropy.
+    #[cfg(feature = "std")]
+    pub fn initialize(&mut self) -> Result<SetupRequestResponse> {
+        self.initialize_with_negotiator(&mut KeyNegotiator::from_entropy())
+    }
+
+    /// Opens an encrypted session with the device using the [KeyNegotiator], without enabling it
+    /// to accept notes.
+    ///
+    /// Configures the global channel values, and enables all channels reported by the device.
+    /// Call [enable](Self::enable) to start accepting notes.
+    pub fn initialize_with_negotiator(
+        &mut self,
+        negotiator: &mut KeyNegotiator,
+    ) -> Result<SetupRequestResponse> {
+        self.sync()?;
+        self.negotiate_session(negotiator)?;
+
+        let setup = self.setup_request()?;
+
+        self.set_inhibits(configure_setup(&setup)?)?;
+
+        Ok(setup)
+    }
+
+    /// Negotiates the eSSP encryption key with the device.
+    ///
+    /// Derives the [EncryptionKey](crate::EncryptionKey) from the device
+    /// [IntermediateKey](crate::IntermediateKey), and starts a new
+    /// [EsspSession] using the [DEFAULT_FIXED_KEY](crate::DEFAULT_FIXED_KEY).
+    ///
+    /// The generator must be larger than the modulus, and both must be prime, see
+    /// [KeyNegotiator::from_keys]. New primes are generated if the device rejects the keys.
+    pub fn negotiate_key(
+        &mut self,
+        generator: &GeneratorKey,
+        modulus: &ModulusKey,
+        random: &RandomKey,
+    ) -> Result<()> {
+        self.negotiate_session(&mut key_negotiator(generator, modulus, random)?)
+    }
+
+    /// Negotiates the eSSP encryption key with the device using the [KeyNegotiator], and starts
+    /// a new [EsspSession].
+    ///
+    /// If the negotiation fails, the current [EsspSession] is kept, so later commands are never
+    /// silently sent unencrypted.
+    pub fn negotiate_session(&mut self, negotiator: &mut KeyNegotiator) -> Result<()> {
+        // key negotiation is always unencrypted
+        let current = self.session.take();
+
+        match negotiator.negotiate(|command| self.command(command)) {
+            Ok(session) => {
+                self.session = Some(session);
+                Ok(())
+            }
+            Err(err) => {
+                self.session = current;
+                Err(err)
+            }
+        }
+    }
+
+    /// Negotiates a new eSSP encryption key with the device, starting a new [EsspSession].
+    ///
+    /// Key negotiation uses random primes generated from system entropy.
+    #[cfg(feature = "std")]
+    pub fn renegotiate_key(&mut self) -> Result<()> {
+        self.negotiate_session(&mut KeyNegotiator::from_entropy())
+    }
+
+    // Starts a new session for the [ResyncPolicy], with the caller's negotiator if provided.
+    fn resync_session(&mut self) -> Result<()> {
+        match self.resync_negotiator.take() {
+            Some(mut negotiator) => {
+                let res = self.negotiate_session(&mut negotiator);
+                self.resync_negotiator = Some(negotiator);
+                res
+            }
+            #[cfg(feature = "std")]
+            None => self.renegotiate_key(),
+            #[cfg(not(feature = "std"))]
+            None => Err(Error::Encryption(ResponseStatus::KeyNotSet)),
+        }
+    }
+
+    /// Sends a [SyncCommand] to the device.
+    ///
+    /// Resets the [SequenceFlag], so the next command is sent with the flag unset.
+    pub fn sync(&mut self) -> Result<SyncResponse> {
+        self.engine.set_sequence_flag(SequenceFlag::Set);
+        self.command(&mut SyncCommand::new())?.into_sync_response()
+    }
+
+    /// Sends a [SetupRequestCommand] to the device.
+    pub fn setup_request(&mut self) -> Result<SetupRequestResponse> {
+        let res = self
+            .command(&mut SetupRequestCommand::new())?
+            .into_setup_request_response()?;
+
+        self.setup = Some(res);
+
+        Ok(res)
+    }
+
+    /// Sends a [SetInhibitsCommand] to the device.
+    pub fn set_inhibits(&mut self, inhibits: EnableBitfieldList) -> Result<SetInhibitsResponse> {
+        let mut cmd = SetInhibitsCommand::new();
+        cmd.set_inhibits(inhibits)?;
+
+        self.command(&mut cmd)?.into_set_inhibits_response()
+    }
+
+    /// Sends an [EnableCommand] to the device.
+    pub fn enable(&mut self) -> Result<EnableResponse> {
+        self.command(&mut EnableCommand::new())?
+            .into_enable_response()
+    }
+
+    /// Sends a [DisableCommand] to the device.
+    pub fn disable(&mut self) -> Result<DisableResponse> {
+        self.command(&mut DisableCommand::new())?
+            .into_disable_response()
+    }
+
+    /// Sends a [PollCommand] to the device.
+    pub fn poll(&mut self) -> Result<PollResponse> {
+        self.command(&mut PollCommand::new())?.into_poll_response()
+    }
+
+    /// Sends a [PollWithAckCommand] to the device.
+    pub fn poll_with_ack(&mut self) -> Result<PollWithAckResponse> {
+        self.command(&mut PollWithAckCommand::new())?
+            .into_poll_with_ack_response()
+    }
+
+    /// Sends an [EventAckCommand] to the device.
+    pub fn event_ack(&mut self) -> Result<EventAckResponse> {
+        self.command(&mut EventAckCommand::new())?
+            .into_event_ack_response()
+    }
+
+    /// Polls the device with [PollWithAckCommand], and acknowledges the events once processed.
+    ///
+    /// Events not yet processed are passed to `handler`. If `handler` returns an error, the events
+    /// are not acknowledged, and the device will repeat them on the next poll.
+    ///
+    /// After `handler` succeeds, the events are marked as processed in the `tracker`, and an
+    /// [EventAckCommand] is sent. If the ACK fails, repeated events are filtered by the `tracker`
+    /// on the next poll, and the ACK is re-sent.
+    pub fn poll_with_ack_events<F>(
+        &mut self,
+        tracker: &mut EventAckTracker,
+        mut handler: F,
+    ) -> Result<PollWithAckResponse>
+    where
+        F: FnMut(PollEventIter<'_>) -> Result<()>,
+    {
+        let res = self.poll_with_ack()?;
+        let events = tracker.new_events(&res, self.protocol_version());
+
+        if !events.remaining().is_empty() {
+            handler(events)?;
+            tracker.processed(&res);
+        }
+
+        if tracker.is_pending() {
+            self.event_ack()?;
+            tracker.acknowledged();
+        }
+
+        Ok(res)
+    }
+
+    /// Sends a [RejectCommand] to the device.
+    pub fn reject(&mut self) -> Result<RejectResponse> {
+        self.command(&mut RejectCommand::new())?
+            .into_reject_response()
+    }
+
+    /// Sends a [PayoutByDenominationCommand] to the device.
+    pub fn payout(
+        &mut self,
+        payouts: &PayoutDenominationList,
+        option: PayoutOption,
+    ) -> Result<PayoutByDenominationResponse> {
+        let mut cmd = PayoutByDenominationCommand::new()
+            .with_payout_denominations(payouts)
+            .with_payout_option(option);
+
+        self.command(&mut cmd)?
+            .into_payout_by_denomination_response()
+    }
+
+    /// Sends a [PayoutAmountCommand] to the device.
+    pub fn payout_amount(
+        &mut self,
+        amount: u32,
+        currency: CountryCode,
+        option: PayoutOption,
+    ) -> Result<PayoutAmountResponse> {
+        let mut cmd = PayoutAmountCommand::new()
+            .with_amount(amount)
+            .with_currency(currency)
+            .with_payout_option(option);
+
+        self.command(&mut cmd)?.into_payout_amount_response()
+    }
+
+    /// Sends a [HaltPayoutCommand] to the device.
+    pub fn halt_payout(&mut self) -> Result<HaltPayoutResponse> {
+        self.command(&mut HaltPayoutCommand::new())?
+            .into_halt_payout_response()
+    }
+
+    /// Sends a [FloatAmountCommand] to the device.
+    pub fn float_amount(
+        &mut self,
+        min_payout: u32,
+        amount: u32,
+        currency: CountryCode,
+        option: PayoutOption,
+    ) -> Result<FloatAmountResponse> {
+        let mut cmd = FloatAmountCommand::new()
+            .with_min_payout(min_payout)
+            .with_amount(amount)
+            .with_currency(currency)
+            .with_payout_option(option);
+
+        self.command(&mut cmd)?.into_float_amount_response()
+    }
+
+    /// Sends a [GetMinimumPayoutCommand] to the device.
+    pub fn get_minimum_payout(
+        &mut self,
+        currency: CountryCode,
+    ) -> Result<GetMinimumPayoutResponse> {
+        let mut cmd = GetMinimumPayoutCommand::new().with_currency(currency);
+
+        self.command(&mut cmd)?.into_get_minimum_payout_response()
+    }
+
+    /// Sends a [FloatByDenominationCommand] to the device.
+    pub fn float_by_denomination(
+        &mut self,
+        floats: &PayoutDenominationList,
+        option: PayoutOption,
+    ) -> Result<FloatByDenominationResponse> {
+        let mut cmd = FloatByDenominationCommand::new()
+            .with_payout_denominations(floats)
+            .with_payout_option(option);
+
+        self.command(&mut cmd)?
+            .into_float_by_denomination_response()
+    }
+
+    /// Sends a [SetDenominationLevelCommand] to the device.
+    pub fn set_denomination_level(
+        &mut self,
+        count: u16,
+        value: ChannelValue,
+        currency: CountryCode,
+    ) -> Result<SetDenominationLevelResponse> {
+        let mut cmd = SetDenominationLevelCommand::new()
+            .with_count(count)
+            .with_value(value)
+            .with_currency(currency);
+
+        self.command(&mut cmd)?
+            .into_set_denomination_level_response()
+    }
+
+    /// Sends a [GetDenominationLevelCommand] to the device.
+    pub fn get_denomination_level(
+        &mut self,
+        value: ChannelValue,
+        currency: CountryCode,
+    ) -> Result<GetDenominationLevelResponse> {
+        let mut cmd = GetDenominationLevelCommand::new()
+            .with_value(value)
+            .with_currency(currency);
+
+        self.command(&mut cmd)?
+            .into_get_denomination_level_response()
+    }
+
+    /// Sends a [GetAllLevelsCommand] to the device.
+    pub fn get_all_levels(&mut self) -> Result<GetAllLevelsResponse> {
+        self.command(&mut GetAllLevelsCommand::new())?
+            .into_get_all_levels_response()
+    }
+
+    /// Sends a [SetDenominationRouteCommand] to the device.
+    pub fn set_denomination_route(
+        &mut self,
+        route: DenominationRoute,
+        value: ChannelValue,
+        currency: CountryCode,
+    ) -> Result<SetDenominationRouteResponse> {
+        let mut cmd = SetDenominationRouteCommand::new()
+            .with_route(route)
+            .with_value(value)
+            .with_currency(currency);
+
+        self.command(&mut cmd)?
+            .into_set_denomination_route_response()
+    }
+
+    /// Sends a [GetDenominationRouteCommand] to the device.
+    pub fn get_denomination_route(
+        &mut self,
+        value: ChannelValue,
+        currency: CountryCode,
+    ) -> Result<GetDenominationRouteResponse> {
+        let mut cmd = GetDenominationRouteCommand::new()
+            .with_value(value)
+            .with_currency(currency);
+
+        self.command(&mut cmd)?
+            .into_get_denomination_route_response()
+    }
+
+    /// Sends the command requested by an [Event], e.g. a decoded JSON-RPC request.
+    ///
+    /// Returns the [Event] reporting the result of the command.
+    ///
+    /// [StackEvent] requests send no command, since a note held in escrow is stacked by the next
+    /// poll.
+    pub fn handle_event(&mut self, event: &Event) -> Result<Event> {
+        match event.payload() {
+            EventPayload::EnableEvent(_) => {
+                self.enable()?;
+                Ok(EnableEvent::new(self.protocol_version()).into())
+            }
+            EventPayload::DisableEvent(_) => {
+                self.disable()?;
+                Ok(DisableEvent::new().into())
+            }
+            EventPayload::RejectEvent(_) => {
+                self.reject()?;
+                Ok(RejectEvent::new().into())
+            }
+            EventPayload::StackEvent(evt) => Ok((*evt).into()),
+            EventPayload::StatusEvent(_) => {
+                Ok(status_event(self.setup.as_ref(), self.protocol_version()))
+            }
+            EventPayload::DispenseEvent(evt) => {
+                self.payout(evt.as_inner(), PayoutOption::PayoutAmount)?;
+                Ok(evt.clone().into())
+            }
+            EventPayload::SetInhibitsEvent(evt) => {
+                self.set_inhibits(evt.inhibits()?)?;
+                Ok(evt.clone().into())
+            }
+            EventPayload::Error(err) => Err(err.clone()),
+            payload => Err(Error::Event(format!(
+                "unsupported request: {}",
+                payload.method()
+            ))),
+        }
+    }
+
+    /// Sends a command to the device, and parses the response.
+    ///
+    /// If an encryption key has been negotiated, the command is sent using the encryption layer.
+    /// Encrypted replies that fail verification are handled by the [ResyncPolicy].
+    ///
+    /// Returns [Error::Status] if the device responds with a non-OK [ResponseStatus].
+    pub fn command(&mut self, command: &mut dyn CommandOps) -> Result<MessageVariant> {
+        let msg_type = command.command();
+        let mut buf = [0u8; len::MAX_MESSAGE];
+
+        let frame_len = match self.session.as_mut() {
+            Some(session) => {
+                let mut wrapped = encrypt_command(session, command)?;
+                self.engine.request_frame(&mut wrapped, &mut buf)?
+            }
+            None => self.engine.request_frame(command, &mut buf)?,
+        };
+
+        let res = parse_reply(self.session.as_mut(), buf[..frame_len].as_ref(), msg_type);
+
+        if let Err(err) = res.as_ref() {
+            if self.resync.should_resync(err) {
+                log::warn!("Negotiating a new eSSP session after: {err}");
+
+                if let Err(resync_err) = self.resync_session() {
+                    log::error!("Error negotiating a new eSSP session: {resync_err}");
+                }
+            }
+        }
+
+        res
+    }
+}
+
+/// Creates a [KeyNegotiator] from the provided keys.
+///
+/// The prime [Generator] is seeded from the secret [RandomKey], so no system entropy is needed.
+pub(crate) fn key_negotiator(
+    generator: &GeneratorKey,
+    modulus: &ModulusKey,
+    random: &RandomKey,
+) -> Result<KeyNegotiator> {
+    let primes = Generator::from_seed(seed(
+        random.as_inner().to_le_bytes().as_ref(),
+        modulus.as_inner().to_le_bytes().as_ref(),
+    ));
+
+    KeyNegotiator::from_keys(primes, generator.clone(), modulus.clone(), random.clone())
+}
+
+/// Configures the global channel values reported in the [SetupRequestResponse].
+///
+/// Returns the inhibit bitfields enabling all channels reported by the device.
+pub(crate) fn configure_setup(setup: &SetupRequestResponse) -> Result<EnableBitfieldList> {
+    let channels = if setup.protocol_version()?.to_u8() >= ProtocolVersion::Six.to_u8() {
+        setup.channel_values_long()?
+    } else {
+        setup.channel_values()?
+    };
+
+    configure_channels(channels.as_ref())?;
+
+    let num_bitfields = setup.num_channels().div_ceil(8);
+    let inhibit_len = usize::from(InhibitChannels::from(num_bitfields.max(2)));
+
+    Ok((0..inhibit_len)
+        .map(|_| EnableBitfield::from(0xff))
+        .collect::<crate::Vec<EnableBitfield>>()
+        .into())
+}
+
+/// Creates the [StatusEvent] reported for a [Method::Status](crate::Method::Status) request.
+pub(crate) fn status_event(
+    setup: Option<&SetupRequestResponse>,
+    protocol_version: ProtocolVersion,
+) -> Event {
+    let status = setup
+        .map(DeviceStatus::from)
+        .unwrap_or_default()
+        .with_protocol_version(protocol_version);
+
+    StatusEvent::new(status).into()
+}
+
+/// Wraps a command in an encrypted eSSP message.
+pub(crate) fn encrypt_command(
+    session: &mut EsspSession,
+    command: &mut dyn CommandOps,
+) -> Result<WrappedEncryptedMessage> {
+    seal_command(session, EncryptedCommand::new().with_message_data(command)?)
+}
+
+/// Encrypts an [EncryptedCommand] with the [EsspSession], incrementing the session count.
+pub(crate) fn seal_command(
+    session: &mut EsspSession,
+    command: EncryptedCommand,
+) -> Result<WrappedEncryptedMessage> {
+    let mut wrapped = command.encrypt(session)?;
+
+    // the transport handles byte stuffing for the entire frame
+    wrapped.unstuff_encrypted_data()?;
+
+    Ok(wrapped)
+}
+
+/// Parses a reply frame, decrypting it if an [EsspSession] has been negotiated.
+///
+/// Returns [Error::Status] if the device responds with a non-OK [ResponseStatus].
+///
+/// With an [EsspSession], the device only replies unencrypted with an error status, e.g. after
+/// losing the key in a reset. Any other unencrypted reply is rejected with
+/// [Error::UnencryptedResponse], since it skipped the sequence count and CRC checks.
+pub(crate) fn parse_reply(
+    session: Option<&mut EsspSession>,
+    frame: &[u8],
+    msg_type: MessageType,
+) -> Result<MessageVariant> {
+    match session {
+        Some(_) if frame.get(index::DATA) != Some(&MessageType::Encrypted.into()) => {
+            match response_status(frame) {
+                status if status.is_ok() => Err(Error::UnencryptedResponse(msg_type)),
+                status => Err(Error::Status(status)),
+            }
+        }
+        Some(session) => {
+            let mut wrapped = WrappedEncryptedMessage::try_from(frame)?;
+
+            // decryption expects the encrypted data to be stuffed
+            wrapped.stuff_encrypted_data()?;
+
+            let res = EncryptedResponse::decrypt_verified(session, wrapped)?;
+
+            let data = res.message_data();
+            let data_len = data.len();
+            let res_len = len::METADATA + data_len;
+
+            let mut res_buf = [0u8; len::MAX_MESSAGE];
+            res_buf[index::STX] = STX;
+            res_buf[index::SEQ_ID] = frame[index::SEQ_ID];
+            res_buf[index::LEN] = data_len as u8;
+            res_buf[index::DATA..index::DATA + data_len].copy_from_slice(data);
+
+            let crc = crate::crc::crc16(res_buf[index::SEQ_ID..res_len - 2].as_ref());
+            res_buf[res_len - 2..res_len].copy_from_slice(crc.to_le_bytes().as_ref());
+
+            parse_response(res_buf[..res_len].as_ref(), msg_type)
+        }
+        _ => parse_response(frame, msg_type),
+    }
+}
+
+fn parse_response(frame: &[u8], msg_type: MessageType) -> Result<MessageVariant> {
+    let status = response_status(frame);
+
+    if status.is_ok() {
+        MessageVariant::from_buf(frame, msg_type)
+    } else {
+        Err(Error::Status(status))
+    }
+}
+
+fn response_status(frame: &[u8]) -> ResponseStatus {
+    frame
+        .get(index::RESPONSE_STATUS)
+        .map(|&s| ResponseStatus::from(s))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::mock::MockTransport;
+    use crate::{InhibitChannelVec, MessageOps, Method, SetInhibitsEvent, StackEvent};
+
+    #[test]
+    fn test_device_sequence_flag() -> Result<()> {
+        let mut transport = MockTransport::new();
+        transport.push_reply(&mut SyncResponse::new(), ResponseStatus::Ok);
+        transport.push_reply(&mut EnableResponse::new(), ResponseStatus::Ok);
+
+        let mut device = Device::new(transport).with_address(0x10).with_retries(1);
+
+        device.sync()?;
+        device.enable()?;
+
+        // no reply, so the flag is not toggled
+        assert!(device.disable().is_err());
+
+        let sent = device.transport().sent();
+
+        assert_eq!(sent.len(), 4);
+        assert_eq!(sent[0][index::SEQ_ID], 0x90);
+        assert_eq!(sent[1][index::SEQ_ID], 0x10);
+        assert_eq!(sent[2][index::SEQ_ID], 0x90);
+        assert_eq!(sent[3][index::SEQ_ID], 0x90);
+        assert_eq!(device.sequence_id(), SequenceId::from(0x90));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_device_response_status() {
+        let mut transport = MockTransport::new();
+        transport.push_reply(
+            &mut RejectResponse::new(),
+            ResponseStatus::CommandCannotBeProcessed,
+        );
+
+        let mut device = Device::new(transport);
+
+        assert_eq!(
+            device.reject(),
+            Err(Error::Status(ResponseStatus::CommandCannotBeProcessed))
+        );
+    }
+
+    #[test]
+    fn test_device_unencrypted_reply() {
+        let mut transport = MockTransport::new();
+        // forged, or replayed, plaintext replies skip the eSSP checks
+        transport.push_reply(&mut PollResponse::new(), ResponseStatus::Ok);
+        transport.push_reply(&mut PollResponse::new(), ResponseStatus::KeyNotSet);
+
+        let mut device = Device::new(transport).with_retries(0);
+        device.session = Some(EsspSession::new(0x1aed_a1fb.into()));
+
+        assert_eq!(
+            device.poll(),
+            Err(Error::UnencryptedResponse(MessageType::Poll))
+        );
+        assert_eq!(device.poll(), Err(Error::Status(ResponseStatus::KeyNotSet)));
+    }
+
+    #[test]
+    fn test_device_failed_negotiation_keeps_session() {
+        let mut device = Device::new(MockTransport::new()).with_retries(0);
+
+        let session = EsspSession::new(0x1aed_a1fb.into());
+        device.session = Some(session.clone());
+
+        // the device never answers the key exchange
+        let mut negotiator = KeyNegotiator::new(
+            Generator::from_seed([0x5a; 32]),
+            RandomKey::from_seed([0xa5; 32]),
+        );
+        assert!(device.negotiate_session(&mut negotiator).is_err());
+
+        assert_eq!(device.session().map(EsspSession::key), Some(session.key()));
+
+        // later commands are still encrypted
+        assert!(device.poll().is_err());
+
+        let sent = device.transport().sent();
+        assert_eq!(
+            sent.last().map(|frame| frame[index::DATA]),
+            Some(MessageType::Encrypted.into())
+        );
+    }
+
+    #[test]
+    fn test_device_poll_with_ack_events() -> Result<()> {
+        let credit = [ResponseStatus::NoteCredit.to_u8(), 0x01];
+
+        let mut poll_res = PollWithAckResponse::new();
+        poll_res.set_data_len(3);
+        poll_res.buf_mut()[index::DATA + 1..index::DATA + 3].copy_from_slice(credit.as_ref());
+
+        let mut transport = MockTransport::new();
+        transport.push_reply(&mut poll_res.clone(), ResponseStatus::Ok);
+        // ACK reply is lost
+        transport.push_error(Error::Timeout("dropped reply".into()));
+        // device repeats the un-ACKed credit
+        transport.push_reply(&mut poll_res, ResponseStatus::Ok);
+        transport.push_reply(&mut EventAckResponse::new(), ResponseStatus::Ok);
+
+        let mut device = Device::new(transport).with_retries(0);
+        let mut tracker = EventAckTracker::new();
+        let mut credits = 0;
+
+        let mut handler = |events: PollEventIter<'_>| -> Result<()> {
+            for event in events {
+                if event?.is_note_credit_event() {
+                    credits += 1;
+                }
+            }
+            Ok(())
+        };
+
+        assert!(device
+            .poll_with_ack_events(&mut tracker, &mut handler)
+            .is_err());
+        assert!(tracker.is_pending());
+
+        device.poll_with_ack_events(&mut tracker, &mut handler)?;
+        assert!(!tracker.is_pending());
+
+        assert_eq!(credits, 1);
+
+        let sent = device.transport().sent();
+        assert_eq!(sent.len(), 4);
+        assert_eq!(sent[1][index::DATA], u8::from(MessageType::EventAck));
+        assert_eq!(sent[3][index::DATA], u8::from(MessageType::EventAck));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_device_handle_event() -> Result<()> {
+        let mut transport = MockTransport::new();
+        transport.push_reply(&mut SetInhibitsResponse::new(), ResponseStatus::Ok);
+        transport.push_reply(&mut RejectResponse::new(), ResponseStatus::Ok);
+
+        let mut device = Device::new(transport);
+
+        let inhibits = SetInhibitsEvent::create(InhibitChannelVec::from_slice(&[1, 2])?);
+        assert_eq!(
+            device.handle_event(&Event::from(inhibits.clone()))?,
+            Event::from(inhibits)
+        );
+
+        assert_eq!(
+            device.handle_event(&Event::from(Method::Reject))?,
+            Event::from(RejectEvent::new())
+        );
+
+        // stacking needs no command
+        assert_eq!(
+            device.handle_event(&Event::from(Method::Stack))?,
+            Event::from(StackEvent::default())
+        );
+
+        let err = Error::Event("invalid request".into());
+        assert_eq!(
+            device.handle_event(&Event::new(Method::Fail, EventPayload::Error(err.clone()))),
+            Err(err)
+        );
+
+        let sent = device.transport().sent();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0][index::DATA], u8::from(MessageType::SetInhibits));
+        assert_eq!(sent[0][index::DATA + 1..index::DATA + 3], [0b0000_0011, 0]);
+        assert_eq!(sent[1][index::DATA], u8::from(MessageType::Reject));
+
+        Ok(())
+    }
+}
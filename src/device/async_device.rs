@@ -0,0 +1,625 @@
+//! Asynchronous host driver for SSP devices.
+
+use alloc::{boxed::Box, collections::VecDeque};
+use core::future::Future;
+use std::time::Duration;
+
+use futures_core::Stream;
+
+use crate::{
+    len, CommandOps, CountryCode, DisableCommand, DisableEvent, DisableResponse,
+    EnableBitfieldList, EnableCommand, EnableEvent, EnableResponse, EncryptedCommand, Error,
+    EsspSession, Event, EventAckCommand, EventAckResponse, EventPayload, GeneratorKey,
+    HaltPayoutCommand, HaltPayoutResponse, KeyNegotiator, MessageType, MessageVariant, ModulusKey,
+    PayoutAmountCommand, PayoutAmountResponse, PayoutByDenominationCommand,
+    PayoutByDenominationResponse, PayoutDenominationList, PayoutOption, PollCommand, PollResponse,
+    PollWithAckCommand, PollWithAckResponse, ProtocolVersion, RandomKey, RejectCommand,
+    RejectEvent, RejectResponse, ResponseStatus, Result, ResyncPolicy, SequenceFlag, SequenceId,
+    SetInhibitsCommand, SetInhibitsResponse, SetupRequestCommand, SetupRequestResponse,
+    SyncCommand, SyncResponse,
+};
+
+use super::{configure_setup, key_negotiator, parse_reply, seal_command, status_event};
+use crate::transport::{AsyncRequestEngine, AsyncTransport, EncodedMessage};
+
+/// Asynchronous host-side driver for an SSP device.
+///
+/// The asynchronous counterpart of [Device](crate::Device), built on the same message types.
+/// Commands are sent through an [AsyncRequestEngine], so lost or corrupted replies are
+/// re-transmitted with the same [SequenceFlag].
+///
+/// # Cancellation
+///
+/// Commands are only encrypted and sent once their future is first polled, so dropping a
+/// command future before then has no effect on the device, or the [EsspSession].
+///
+/// Dropping a command future after it was first polled leaves the device in an unknown state:
+/// the command may have been executed, and the [SequenceFlag] and eSSP sequence count of the
+/// device and the host may differ. The next encrypted command then fails verification. Call
+/// [sync](Self::sync) and [renegotiate_key](Self::renegotiate_key) to recover, or set
+/// [ResyncPolicy::Renegotiate] to start a new session automatically.
+pub struct AsyncDevice<T: AsyncTransport> {
+    engine: AsyncRequestEngine<T>,
+    session: Option<EsspSession>,
+    resync: ResyncPolicy,
+    setup: Option<SetupRequestResponse>,
+}
+
+impl<T: AsyncTransport> AsyncDevice<T> {
+    /// Creates a new [AsyncDevice] communicating over the provided [AsyncTransport].
+    pub fn new(transport: T) -> Self {
+        Self {
+            engine: AsyncRequestEngine::new(transport),
+            session: None,
+            resync: ResyncPolicy::default(),
+            setup: None,
+        }
+    }
+
+    /// Builder function that sets the device address.
+    pub fn with_address(mut self, address: u8) -> Self {
+        let mut sequence_id = self.engine.sequence_id();
+        sequence_id.set_id(address);
+
+        self.engine.set_sequence_id(sequence_id);
+        self
+    }
+
+    /// Builder function that sets the number of retransmissions for each command.
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.engine.set_retries(retries);
+        self
+    }
+
+    /// Builder function that sets the [ResyncPolicy] for encrypted replies that fail
+    /// verification.
+    ///
+    /// Defaults to [ResyncPolicy::Reject].
+    pub fn with_resync_policy(mut self, policy: ResyncPolicy) -> Self {
+        self.resync = policy;
+        self
+    }
+
+    /// Gets the [ResyncPolicy] for encrypted replies that fail verification.
+    pub fn resync_policy(&self) -> ResyncPolicy {
+        self.resync
+    }
+
+    /// Gets a reference to the [AsyncTransport].
+    pub fn transport(&self) -> &T {
+        self.engine.transport()
+    }
+
+    /// Gets a mutable reference to the [AsyncTransport].
+    pub fn transport_mut(&mut self) -> &mut T {
+        self.engine.transport_mut()
+    }
+
+    /// Consumes the [AsyncDevice], returning the [AsyncTransport].
+    pub fn into_transport(self) -> T {
+        self.engine.into_transport()
+    }
+
+    /// Gets a reference to the [AsyncRequestEngine].
+    pub fn engine(&self) -> &AsyncRequestEngine<T> {
+        &self.engine
+    }
+
+    /// Gets a mutable reference to the [AsyncRequestEngine].
+    pub fn engine_mut(&mut self) -> &mut AsyncRequestEngine<T> {
+        &mut self.engine
+    }
+
+    /// Gets the [SequenceId] used for the next command.
+    pub fn sequence_id(&self) -> SequenceId {
+        self.engine.sequence_id()
+    }
+
+    /// Gets whether an encryption key has been negotiated with the device.
+    pub fn is_encrypted(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Gets the [EsspSession] negotiated with the device.
+    pub fn session(&self) -> Option<&EsspSession> {
+        self.session.as_ref()
+    }
+
+    /// Gets the [SetupRequestResponse] received while connecting to the device.
+    pub fn setup(&self) -> Option<&SetupRequestResponse> {
+        self.setup.as_ref()
+    }
+
+    /// Gets the [ProtocolVersion] reported by the device while connecting.
+    ///
+    /// Defaults to [ProtocolVersion::new] before the [SetupRequestResponse] is received.
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.setup
+            .as_ref()
+            .and_then(|setup| setup.protocol_version().ok())
+            .unwrap_or_default()
+    }
+
+    /// Opens an encrypted session with the device, and enables it to accept notes.
+    ///
+    /// Key negotiation uses random primes generated from system entropy.
+    pub async fn connect(&mut self) -> Result<SetupRequestResponse> {
+        self.connect_with_negotiator(&mut KeyNegotiator::from_entropy())
+            .await
+    }
+
+    /// Opens an encrypted session with the device using the provided keys, and enables it to
+    /// accept notes.
+    ///
+    /// See [Device::connect_with_keys](crate::Device::connect_with_keys) for details.
+    pub async fn connect_with_keys(
+        &mut self,
+        generator: &GeneratorKey,
+        modulus: &ModulusKey,
+        random: &RandomKey,
+    ) -> Result<SetupRequestResponse> {
+        self.connect_with_negotiator(&mut key_negotiator(generator, modulus, random)?)
+            .await
+    }
+
+    /// Opens an encrypted session with the device using the [KeyNegotiator], and enables it to
+    /// accept notes.
+    ///
+    /// Configures the global channel values, and enables all channels reported by the device.
+    pub async fn connect_with_negotiator(
+        &mut self,
+        negotiator: &mut KeyNegotiator,
+    ) -> Result<SetupRequestResponse> {
+        self.sync().await?;
+        self.negotiate_session(negotiator).await?;
+
+        let setup = self.setup_request().await?;
+
+        self.set_inhibits(configure_setup(&setup)?).await?;
+        self.enable().await?;
+
+        Ok(setup)
+    }
+
+    /// Negotiates the eSSP encryption key with the device.
+    ///
+    /// See [Device::negotiate_key](crate::Device::negotiate_key) for details.
+    pub async fn negotiate_key(
+        &mut self,
+        generator: &GeneratorKey,
+        modulus: &ModulusKey,
+        random: &RandomKey,
+    ) -> Result<()> {
+        self.negotiate_session(&mut key_negotiator(generator, modulus, random)?)
+            .await
+    }
+
+    /// Negotiates the eSSP encryption key with the device using the [KeyNegotiator], and starts
+    /// a new [EsspSession].
+    ///
+    /// If the negotiation fails, the current [EsspSession] is kept, so later commands are never
+    /// silently sent unencrypted.
+    pub async fn negotiate_session(&mut self, negotiator: &mut KeyNegotiator) -> Result<()> {
+        // key negotiation is always unencrypted
+        let current = self.session.take();
+
+        match self.run_negotiator(negotiator).await {
+            Ok(session) => {
+                self.session = Some(session);
+                Ok(())
+            }
+            Err(err) => {
+                self.session = current;
+                Err(err)
+            }
+        }
+    }
+
+    async fn run_negotiator(&mut self, negotiator: &mut KeyNegotiator) -> Result<EsspSession> {
+        negotiator.reset();
+
+        while let Some(mut command) = negotiator.command() {
+            let reply = self.request(command.as_command_mut()).await;
+
+            if let Some(session) = negotiator.handle_reply(reply)? {
+                return Ok(session);
+            }
+        }
+
+        Err(Error::Status(ResponseStatus::KeyNotSet))
+    }
+
+    /// Negotiates a new eSSP encryption key with the device, starting a new [EsspSession].
+    ///
+    /// Key negotiation uses random primes generated from system entropy.
+    pub async fn renegotiate_key(&mut self) -> Result<()> {
+        self.negotiate_session(&mut KeyNegotiator::from_entropy())
+            .await
+    }
+
+    /// Sends a [SyncCommand] to the device.
+    ///
+    /// Resets the [SequenceFlag], so the next command is sent with the flag unset.
+    pub async fn sync(&mut self) -> Result<SyncResponse> {
+        self.engine.set_sequence_flag(SequenceFlag::Set);
+        self.command(&mut SyncCommand::new())
+            .await?
+            .into_sync_response()
+    }
+
+    /// Sends a [SetupRequestCommand] to the device.
+    pub async fn setup_request(&mut self) -> Result<SetupRequestResponse> {
+        let res = self
+            .command(&mut SetupRequestCommand::new())
+            .await?
+            .into_setup_request_response()?;
+
+        self.setup = Some(res);
+
+        Ok(res)
+    }
+
+    /// Sends a [SetInhibitsCommand] to the device.
+    pub async fn set_inhibits(
+        &mut self,
+        inhibits: EnableBitfieldList,
+    ) -> Result<SetInhibitsResponse> {
+        let mut cmd = SetInhibitsCommand::new();
+        cmd.set_inhibits(inhibits)?;
+
+        self.command(&mut cmd).await?.into_set_inhibits_response()
+    }
+
+    /// Sends an [EnableCommand] to the device.
+    pub async fn enable(&mut self) -> Result<EnableResponse> {
+        self.command(&mut EnableCommand::new())
+            .await?
+            .into_enable_response()
+    }
+
+    /// Sends a [DisableCommand] to the device.
+    pub async fn disable(&mut self) -> Result<DisableResponse> {
+        self.command(&mut DisableCommand::new())
+            .await?
+            .into_disable_response()
+    }
+
+    /// Sends a [PollCommand] to the device.
+    pub async fn poll(&mut self) -> Result<PollResponse> {
+        self.command(&mut PollCommand::new())
+            .await?
+            .into_poll_response()
+    }
+
+    /// Sends a [PollWithAckCommand] to the device.
+    pub async fn poll_with_ack(&mut self) -> Result<PollWithAckResponse> {
+        self.command(&mut PollWithAckCommand::new())
+            .await?
+            .into_poll_with_ack_response()
+    }
+
+    /// Sends an [EventAckCommand] to the device.
+    pub async fn event_ack(&mut self) -> Result<EventAckResponse> {
+        self.command(&mut EventAckCommand::new())
+            .await?
+            .into_event_ack_response()
+    }
+
+    /// Sends a [RejectCommand] to the device.
+    pub async fn reject(&mut self) -> Result<RejectResponse> {
+        self.command(&mut RejectCommand::new())
+            .await?
+            .into_reject_response()
+    }
+
+    /// Sends a [PayoutByDenominationCommand] to the device.
+    pub async fn payout(
+        &mut self,
+        payouts: &PayoutDenominationList,
+        option: PayoutOption,
+    ) -> Result<PayoutByDenominationResponse> {
+        let mut cmd = PayoutByDenominationCommand::new()
+            .with_payout_denominations(payouts)
+            .with_payout_option(option);
+
+        self.command(&mut cmd)
+            .await?
+            .into_payout_by_denomination_response()
+    }
+
+    /// Sends a [PayoutAmountCommand] to the device.
+    pub async fn payout_amount(
+        &mut self,
+        amount: u32,
+        currency: CountryCode,
+        option: PayoutOption,
+    ) -> Result<PayoutAmountResponse> {
+        let mut cmd = PayoutAmountCommand::new()
+            .with_amount(amount)
+            .with_currency(currency)
+            .with_payout_option(option);
+
+        self.command(&mut cmd).await?.into_payout_amount_response()
+    }
+
+    /// Sends a [HaltPayoutCommand] to the device.
+    pub async fn halt_payout(&mut self) -> Result<HaltPayoutResponse> {
+        self.command(&mut HaltPayoutCommand::new())
+            .await?
+            .into_halt_payout_response()
+    }
+
+    /// Sends the command requested by an [Event], e.g. a decoded JSON-RPC request.
+    ///
+    /// See [Device::handle_event](crate::Device::handle_event) for details.
+    pub async fn handle_event(&mut self, event: &Event) -> Result<Event> {
+        match event.payload() {
+            EventPayload::EnableEvent(_) => {
+                self.enable().await?;
+                Ok(EnableEvent::new(self.protocol_version()).into())
+            }
+            EventPayload::DisableEvent(_) => {
+                self.disable().await?;
+                Ok(DisableEvent::new().into())
+            }
+            EventPayload::RejectEvent(_) => {
+                self.reject().await?;
+                Ok(RejectEvent::new().into())
+            }
+            EventPayload::StackEvent(evt) => Ok((*evt).into()),
+            EventPayload::StatusEvent(_) => {
+                Ok(status_event(self.setup.as_ref(), self.protocol_version()))
+            }
+            EventPayload::DispenseEvent(evt) => {
+                self.payout(evt.as_inner(), PayoutOption::PayoutAmount)
+                    .await?;
+                Ok(evt.clone().into())
+            }
+            EventPayload::SetInhibitsEvent(evt) => {
+                self.set_inhibits(evt.inhibits()?).await?;
+                Ok(evt.clone().into())
+            }
+            EventPayload::Error(err) => Err(err.clone()),
+            payload => Err(Error::Event(format!(
+                "unsupported request: {}",
+                payload.method()
+            ))),
+        }
+    }
+
+    /// Polls the device every `interval`, and streams the reported events.
+    ///
+    /// From the SSP Implementation Guide, the delay between polls should be no less than 200 ms,
+    /// and no greater than 1000 ms.
+    ///
+    /// Poll failures are streamed as errors, and polling continues on the next interval. The
+    /// stream never ends, so drop it to stop polling.
+    pub fn poll_events(
+        &mut self,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<Event>> + Send + '_ {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        let state = (self, ticker, VecDeque::<Result<Event>>::new());
+
+        futures_util::stream::unfold(state, |(device, mut ticker, mut pending)| async move {
+            loop {
+                if let Some(event) = pending.pop_front() {
+                    return Some((event, (device, ticker, pending)));
+                }
+
+                ticker.tick().await;
+
+                match device.poll().await {
+                    Ok(res) => {
+                        pending.extend(
+                            res.events(device.protocol_version())
+                                .map(|p| p.map(|p| Event::new(p.method(), p))),
+                        );
+                    }
+                    Err(err) => pending.push_back(Err(err)),
+                }
+            }
+        })
+    }
+
+    /// Sends a command to the device, and parses the response.
+    ///
+    /// If an encryption key has been negotiated, the command is sent using the encryption layer.
+    /// Encrypted replies that fail verification are handled by the [ResyncPolicy].
+    ///
+    /// The command data is copied before the returned future is first polled, so the future does
+    /// not borrow the `command`. Encryption is deferred until the future runs, see
+    /// [cancellation](Self#cancellation) for details.
+    ///
+    /// Returns [Error::Status] if the device responds with a non-OK
+    /// [ResponseStatus](crate::ResponseStatus).
+    pub fn command<'a>(
+        &'a mut self,
+        command: &mut dyn CommandOps,
+    ) -> impl Future<Output = Result<MessageVariant>> + Send + 'a {
+        let (message, msg_type) = self.prepare_command(command);
+
+        async move {
+            let res = self.send_prepared(message, msg_type).await;
+
+            if let Err(err) = res.as_ref() {
+                if self.resync.should_resync(err) {
+                    log::warn!("Negotiating a new eSSP session after: {err}");
+
+                    if let Err(resync_err) = self.renegotiate_key().await {
+                        log::error!("Error negotiating a new eSSP session: {resync_err}");
+                    }
+                }
+            }
+
+            res
+        }
+    }
+
+    // Sends a command without the [ResyncPolicy], so key negotiation does not recurse.
+    fn request<'a>(
+        &'a mut self,
+        command: &mut dyn CommandOps,
+    ) -> impl Future<Output = Result<MessageVariant>> + Send + 'a {
+        let (message, msg_type) = self.prepare_command(command);
+
+        self.send_prepared(message, msg_type)
+    }
+
+    fn prepare_command(
+        &self,
+        command: &mut dyn CommandOps,
+    ) -> (Result<PreparedCommand>, MessageType) {
+        let msg_type = command.command();
+
+        let message = if self.session.is_some() {
+            EncryptedCommand::new()
+                .with_message_data(command)
+                .map(|command| PreparedCommand::Encrypted(Box::new(command)))
+        } else {
+            Ok(PreparedCommand::Plain(self.engine.encode(command)))
+        };
+
+        (message, msg_type)
+    }
+
+    async fn send_prepared(
+        &mut self,
+        message: Result<PreparedCommand>,
+        msg_type: MessageType,
+    ) -> Result<MessageVariant> {
+        let message = match message? {
+            PreparedCommand::Plain(message) => message,
+            PreparedCommand::Encrypted(command) => {
+                // the future borrows the device, so the session can not end before it runs
+                let session = self
+                    .session
+                    .as_mut()
+                    .ok_or(Error::Encryption(ResponseStatus::KeyNotSet))?;
+
+                // only increments the session count once the command is about to be sent
+                self.engine.encode(&mut seal_command(session, *command)?)
+            }
+        };
+
+        let mut buf = [0u8; len::MAX_MESSAGE];
+
+        let frame_len = self.engine.request_encoded(message, &mut buf).await?;
+
+        parse_reply(self.session.as_mut(), buf[..frame_len].as_ref(), msg_type)
+    }
+}
+
+// Command copied out of the caller's message, so the command future does not borrow it.
+enum PreparedCommand {
+    // unencrypted frame, ready to send
+    Plain(EncodedMessage),
+    // command data, encrypted when the future runs
+    Encrypted(Box<EncryptedCommand>),
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+
+    use super::*;
+    use crate::message::index;
+    use crate::transport::mock::MockTransport;
+    use crate::{MessageOps, MessageType, Method, ResponseStatus};
+
+    #[tokio::test]
+    async fn test_async_device_sequence_flag() -> Result<()> {
+        let mut transport = MockTransport::new();
+        transport.push_reply(&mut SyncResponse::new(), ResponseStatus::Ok);
+        transport.push_reply(&mut EnableResponse::new(), ResponseStatus::Ok);
+
+        let mut device = AsyncDevice::new(transport)
+            .with_address(0x10)
+            .with_retries(0);
+
+        // futures are Send, so commands can run on a multi-threaded runtime
+        let mut device = tokio::spawn(async move {
+            device.sync().await?;
+            device.enable().await?;
+            Ok::<_, Error>(device)
+        })
+        .await
+        .unwrap()?;
+
+        assert!(device.disable().await.is_err());
+
+        let sent = device.transport().sent();
+        assert_eq!(sent.len(), 3);
+        assert_eq!(sent[0][index::SEQ_ID], 0x90);
+        assert_eq!(sent[1][index::SEQ_ID], 0x10);
+        assert_eq!(sent[2][index::SEQ_ID], 0x90);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_async_device_poll_events() -> Result<()> {
+        let credit = [ResponseStatus::NoteCredit.to_u8(), 0x01];
+
+        let mut poll_res = PollResponse::new();
+        poll_res.set_data_len(3);
+        poll_res.buf_mut()[index::DATA + 1..index::DATA + 3].copy_from_slice(credit.as_ref());
+
+        let mut transport = MockTransport::new();
+        transport.push_reply(&mut poll_res, ResponseStatus::Ok);
+
+        let mut device = AsyncDevice::new(transport).with_retries(0);
+
+        {
+            let events = device.poll_events(Duration::from_millis(1));
+            futures_util::pin_mut!(events);
+
+            let event = events.next().await.unwrap()?;
+            assert!(event.payload().is_note_credit_event());
+
+            // poll failures are streamed, instead of ending the stream
+            assert!(matches!(events.next().await, Some(Err(Error::Timeout(_)))));
+        }
+
+        assert!(matches!(
+            device.handle_event(&Event::from(Method::Disable)).await,
+            Err(Error::Timeout(_))
+        ));
+
+        let sent = device.transport().sent();
+        assert_eq!(sent.len(), 3);
+        assert_eq!(sent[0][index::DATA], u8::from(MessageType::Poll));
+        assert_eq!(sent[1][index::DATA], u8::from(MessageType::Poll));
+        assert_eq!(sent[2][index::DATA], u8::from(MessageType::Disable));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_async_device_dropped_command() -> Result<()> {
+        use crate::simulator::{Simulator, SimulatorTransport};
+
+        let transport = SimulatorTransport::new(Simulator::new());
+        let simulator = transport.simulator();
+
+        let mut device = AsyncDevice::new(transport).with_retries(0);
+        device.connect().await?;
+
+        let count = device.session().map(EsspSession::count);
+
+        // dropped before it is polled, e.g. losing a `select!`
+        drop(device.command(&mut PollCommand::new()));
+
+        assert_eq!(device.session().map(EsspSession::count), count);
+
+        // the simulator drops commands with the wrong eSSP sequence count
+        device.poll().await?;
+        device.disable().await?;
+
+        assert!(!simulator.lock().unwrap().is_enabled());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,547 @@
+//! Background polling service for SSP devices.
+
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use core::ops::{Deref, DerefMut};
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::{CommandOps, Error, Event, EventAckTracker, MessageVariant, Result, Transport};
+
+use super::Device;
+
+/// Default time between background polls, in milliseconds.
+///
+/// From the SSP Implementation Guide, the delay between polls should be no less than 200 ms, and
+/// no greater than 1000 ms.
+pub const DEFAULT_POLL_INTERVAL_MS: u64 = 200;
+/// Default maximum number of queued poll events.
+pub const DEFAULT_EVENT_QUEUE_LEN: usize = 64;
+
+/// Command used by the [Poller] to poll the device.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PollMode {
+    /// Poll with [PollCommand](crate::PollCommand).
+    #[default]
+    Poll,
+    /// Poll with [PollWithAckCommand](crate::PollWithAckCommand), and acknowledge events once
+    /// they are queued.
+    PollWithAck,
+}
+
+/// Background service polling an SSP [Device] on its own thread.
+///
+/// Events reported by the device are converted to [Event]s, and put in a bounded queue read
+/// with [Poller::recv_timeout]. While the queue is full, the device is not polled, so unread
+/// events stay buffered on the device instead of being dropped.
+///
+/// Polling is paused while other commands are sent through [Poller::device] or
+/// [Poller::command], so polls never interleave with other commands, and the
+/// [SequenceFlag](crate::SequenceFlag) state stays consistent.
+pub struct Poller<T: Transport + Send + 'static> {
+    shared: Arc<Shared<T>>,
+    interval: Duration,
+    mode: PollMode,
+    handle: Option<JoinHandle<()>>,
+}
+
+struct Shared<T: Transport> {
+    device: Mutex<Device<T>>,
+    state: Mutex<PollState>,
+    state_cond: Condvar,
+    queue: Mutex<VecDeque<Result<Event>>>,
+    queue_cond: Condvar,
+    capacity: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct PollState {
+    running: bool,
+    paused: usize,
+}
+
+impl<T: Transport + Send + 'static> Poller<T> {
+    /// Creates a new [Poller] for the [Device].
+    ///
+    /// Polling does not begin until [Poller::start] is called.
+    pub fn new(device: Device<T>) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                device: Mutex::new(device),
+                state: Mutex::new(PollState::default()),
+                state_cond: Condvar::new(),
+                queue: Mutex::new(VecDeque::new()),
+                queue_cond: Condvar::new(),
+                capacity: DEFAULT_EVENT_QUEUE_LEN,
+            }),
+            interval: Duration::from_millis(DEFAULT_POLL_INTERVAL_MS),
+            mode: PollMode::default(),
+            handle: None,
+        }
+    }
+
+    /// Builder function that sets the time between polls.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Builder function that sets the [PollMode].
+    pub fn with_mode(mut self, mode: PollMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Builder function that sets the maximum number of queued events.
+    ///
+    /// Must be set before polling is started.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        if let Some(shared) = Arc::get_mut(&mut self.shared) {
+            shared.capacity = capacity.max(1);
+        }
+        self
+    }
+
+    /// Gets the time between polls.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Gets the [PollMode].
+    pub fn mode(&self) -> PollMode {
+        self.mode
+    }
+
+    /// Gets the maximum number of queued events.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// Gets whether the polling thread is running.
+    pub fn is_running(&self) -> bool {
+        self.shared.state().running
+    }
+
+    /// Gets whether polling is paused.
+    pub fn is_paused(&self) -> bool {
+        self.shared.state().paused != 0
+    }
+
+    /// Starts polling the device on a background thread.
+    ///
+    /// Returns [Error::PollingReinit] if polling is already running.
+    pub fn start(&mut self) -> Result<()> {
+        {
+            let mut state = self.shared.state();
+            if state.running {
+                return Err(Error::PollingReinit);
+            }
+            state.running = true;
+        }
+
+        let shared = Arc::clone(&self.shared);
+        let (interval, mode) = (self.interval, self.mode);
+
+        let handle = thread::Builder::new()
+            .name("ssp-poller".into())
+            .spawn(move || shared.run(interval, mode));
+
+        match handle {
+            Ok(handle) => {
+                self.handle = Some(handle);
+                Ok(())
+            }
+            Err(err) => {
+                self.shared.state().running = false;
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Stops polling, and waits for the polling thread to exit.
+    ///
+    /// Queued events are kept, and can still be read after stopping.
+    pub fn stop(&mut self) {
+        self.shared.state().running = false;
+        self.shared.state_cond.notify_all();
+
+        if let Some(handle) = self.handle.take() {
+            if handle.join().is_err() {
+                log::error!("Polling thread panicked");
+            }
+        }
+    }
+
+    /// Pauses polling until [Poller::resume] is called.
+    ///
+    /// Waits for a poll in progress to complete, so no poll is sent after this returns.
+    ///
+    /// Pauses nest, polling resumes after each pause has a matching resume.
+    pub fn pause(&self) {
+        self.shared.state().paused += 1;
+
+        // wait for a poll in progress
+        drop(self.shared.device());
+    }
+
+    /// Resumes polling after a call to [Poller::pause].
+    pub fn resume(&self) {
+        {
+            let mut state = self.shared.state();
+            state.paused = state.paused.saturating_sub(1);
+        }
+        self.shared.state_cond.notify_all();
+    }
+
+    /// Pauses polling, and gets exclusive access to the [Device].
+    ///
+    /// Polling resumes when the returned guard is dropped.
+    pub fn device(&self) -> PollerDevice<'_, T> {
+        self.pause();
+
+        PollerDevice {
+            poller: self,
+            device: Some(self.shared.device()),
+        }
+    }
+
+    /// Sends a command to the device between polls.
+    pub fn command(&self, command: &mut dyn CommandOps) -> Result<MessageVariant> {
+        self.device().command(command)
+    }
+
+    /// Waits for the next queued event.
+    ///
+    /// Errors from failed polls are queued with the events, and returned in order.
+    ///
+    /// Returns [Error::QueueTimeout] if no event is queued before the `timeout` expires.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<Event> {
+        let deadline = Instant::now() + timeout;
+        let mut queue = self.shared.queue();
+
+        loop {
+            if let Some(event) = queue.pop_front() {
+                drop(queue);
+                // there is room for another poll
+                self.shared.state_cond.notify_all();
+                return event;
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(Error::QueueTimeout);
+            }
+
+            queue = self
+                .shared
+                .queue_cond
+                .wait_timeout(queue, remaining)
+                .unwrap_or_else(|err| err.into_inner())
+                .0;
+        }
+    }
+
+    /// Gets the next queued event, without waiting.
+    pub fn try_recv(&self) -> Option<Result<Event>> {
+        let event = self.shared.queue().pop_front();
+        self.shared.state_cond.notify_all();
+        event
+    }
+
+    /// Gets the number of queued events.
+    pub fn len(&self) -> usize {
+        self.shared.queue().len()
+    }
+
+    /// Gets whether the event queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.shared.queue().is_empty()
+    }
+
+    /// Stops polling, and returns the [Device].
+    ///
+    /// Queued events are dropped.
+    pub fn into_device(mut self) -> Device<T> {
+        self.stop();
+
+        let shared = Arc::clone(&self.shared);
+        drop(self);
+
+        match Arc::try_unwrap(shared) {
+            Ok(shared) => shared
+                .device
+                .into_inner()
+                .unwrap_or_else(|err| err.into_inner()),
+            // the polling thread has exited, so no other reference remains
+            Err(_) => unreachable!("polling thread still holds the device"),
+        }
+    }
+}
+
+impl<T: Transport + Send + 'static> Drop for Poller<T> {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+impl<T: Transport> Shared<T> {
+    fn state(&self) -> MutexGuard<'_, PollState> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn device(&self) -> MutexGuard<'_, Device<T>> {
+        self.device.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn queue(&self) -> MutexGuard<'_, VecDeque<Result<Event>>> {
+        self.queue.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn run(&self, interval: Duration, mode: PollMode) {
+        let mut tracker = EventAckTracker::new();
+        let mut next_poll = Instant::now() + interval;
+
+        loop {
+            if !self.wait_for_poll(next_poll) {
+                break;
+            }
+            next_poll = Instant::now() + interval;
+
+            let mut device = self.device();
+
+            // a pause may have started while waiting for the device
+            if self.state().paused != 0 {
+                continue;
+            }
+
+            let res = match mode {
+                PollMode::Poll => device.poll().map(|res| {
+                    let protocol = device.protocol_version();
+                    self.push_events(res.events(protocol));
+                }),
+                PollMode::PollWithAck => device
+                    .poll_with_ack_events(&mut tracker, |events| {
+                        self.push_events(events);
+                        Ok(())
+                    })
+                    .map(|_| ()),
+            };
+
+            drop(device);
+
+            if let Err(err) = res {
+                log::warn!("Error polling device: {err}");
+                self.push(Err(err));
+            }
+        }
+
+        log::debug!("Polling thread stopped");
+    }
+
+    /// Waits until the next poll is due, while not paused and the queue has room.
+    ///
+    /// Returns `false` if polling was stopped.
+    fn wait_for_poll(&self, next_poll: Instant) -> bool {
+        let mut state = self.state();
+
+        loop {
+            if !state.running {
+                return false;
+            }
+
+            let now = Instant::now();
+            let ready = state.paused == 0 && self.queue().len() < self.capacity;
+
+            let timeout = if now < next_poll {
+                next_poll - now
+            } else if ready {
+                return true;
+            } else {
+                // woken by resume, or by reading from the queue
+                Duration::from_millis(DEFAULT_POLL_INTERVAL_MS)
+            };
+
+            state = self
+                .state_cond
+                .wait_timeout(state, timeout)
+                .unwrap_or_else(|err| err.into_inner())
+                .0;
+        }
+    }
+
+    fn push_events<I>(&self, events: I)
+    where
+        I: Iterator<Item = Result<crate::EventPayload>>,
+    {
+        for event in events {
+            self.push(event.map(|p| Event::new(p.method(), p)));
+        }
+    }
+
+    fn push(&self, event: Result<Event>) {
+        self.queue().push_back(event);
+        self.queue_cond.notify_one();
+    }
+}
+
+/// Exclusive access to the [Device] of a [Poller], with polling paused.
+///
+/// Polling resumes when the guard is dropped.
+pub struct PollerDevice<'a, T: Transport + Send + 'static> {
+    poller: &'a Poller<T>,
+    device: Option<MutexGuard<'a, Device<T>>>,
+}
+
+impl<T: Transport + Send + 'static> Deref for PollerDevice<'_, T> {
+    type Target = Device<T>;
+
+    fn deref(&self) -> &Self::Target {
+        // only taken on drop
+        self.device.as_ref().unwrap()
+    }
+}
+
+impl<T: Transport + Send + 'static> DerefMut for PollerDevice<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.device.as_mut().unwrap()
+    }
+}
+
+impl<T: Transport + Send + 'static> Drop for PollerDevice<'_, T> {
+    fn drop(&mut self) {
+        // release the device before polling resumes
+        self.device.take();
+        self.poller.resume();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::index;
+    use crate::transport::mock::MockTransport;
+    use crate::{
+        EnableResponse, EventAckResponse, MessageOps, MessageType, Method, PollResponse,
+        PollWithAckResponse, ResponseStatus,
+    };
+
+    fn poll_reply(credits: u8) -> PollResponse {
+        let mut res = PollResponse::new();
+        res.set_data_len(1 + 2 * credits);
+
+        for i in 0..credits as usize {
+            let start = index::DATA + 1 + 2 * i;
+            res.buf_mut()[start..start + 2]
+                .copy_from_slice(&[ResponseStatus::NoteCredit.to_u8(), 0x01]);
+        }
+
+        res
+    }
+
+    #[test]
+    fn test_poller_queue() -> Result<()> {
+        let mut transport = MockTransport::new();
+        transport.push_reply(&mut poll_reply(2), ResponseStatus::Ok);
+        transport.push_reply(&mut poll_reply(1), ResponseStatus::Ok);
+
+        let mut poller = Poller::new(Device::new(transport).with_retries(0))
+            .with_interval(Duration::from_millis(5))
+            .with_capacity(2);
+
+        assert!(matches!(
+            poller.recv_timeout(Duration::from_millis(1)),
+            Err(Error::QueueTimeout)
+        ));
+
+        poller.start()?;
+        assert_eq!(poller.start(), Err(Error::PollingReinit));
+
+        // the queue is full after the first poll, so the second is delayed
+        let event = poller.recv_timeout(Duration::from_secs(1))?;
+        assert_eq!(event.method(), Method::NoteCredit);
+        thread::sleep(Duration::from_millis(20));
+        assert!(poller.len() <= 2);
+
+        for _ in 0..2 {
+            let event = poller.recv_timeout(Duration::from_secs(1))?;
+            assert_eq!(event.method(), Method::NoteCredit);
+        }
+
+        // no more replies, so the failed poll is queued
+        assert!(matches!(
+            poller.recv_timeout(Duration::from_secs(1)),
+            Err(Error::Timeout(_))
+        ));
+
+        poller.stop();
+        assert!(!poller.is_running());
+
+        let device = poller.into_device();
+        let sent = device.transport().sent();
+        assert!(sent.len() >= 3);
+        assert!(sent
+            .iter()
+            .all(|f| f[index::DATA] == u8::from(MessageType::Poll)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_poller_pause_command() -> Result<()> {
+        let mut poll_res = PollWithAckResponse::new();
+        poll_res.set_data_len(3);
+        poll_res.buf_mut()[index::DATA + 1..index::DATA + 3]
+            .copy_from_slice(&[ResponseStatus::NoteCredit.to_u8(), 0x01]);
+
+        let mut transport = MockTransport::new();
+        transport.push_reply(&mut poll_res, ResponseStatus::Ok);
+        transport.push_reply(&mut EventAckResponse::new(), ResponseStatus::Ok);
+
+        let mut poller = Poller::new(Device::new(transport).with_retries(0))
+            .with_interval(Duration::from_millis(5))
+            .with_mode(PollMode::PollWithAck);
+
+        poller.start()?;
+
+        let event = poller.recv_timeout(Duration::from_secs(1))?;
+        assert_eq!(event.method(), Method::NoteCredit);
+
+        poller.pause();
+        assert!(poller.is_paused());
+
+        let paused_len = poller.device().transport().sent().len();
+        thread::sleep(Duration::from_millis(20));
+
+        {
+            let mut device = poller.device();
+            // no polls while paused
+            assert_eq!(device.transport().sent().len(), paused_len);
+
+            device
+                .transport_mut()
+                .push_reply(&mut EnableResponse::new(), ResponseStatus::Ok);
+            device.enable()?;
+        }
+
+        // still paused by the explicit pause
+        assert!(poller.is_paused());
+        poller.resume();
+        assert!(!poller.is_paused());
+
+        poller.stop();
+
+        let device = poller.into_device();
+        let sent = device.transport().sent();
+
+        assert_eq!(sent[0][index::DATA], u8::from(MessageType::PollWithAck));
+        assert_eq!(sent[1][index::DATA], u8::from(MessageType::EventAck));
+        // the command is sent between polls, with the next sequence flag
+        let enable = &sent[paused_len];
+        assert_eq!(enable[index::DATA], u8::from(MessageType::Enable));
+        assert_ne!(enable[index::SEQ_ID], sent[1][index::SEQ_ID]);
+
+        Ok(())
+    }
+}
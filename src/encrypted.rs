@@ -1,19 +1,20 @@
 //! Messages for encrypted SSP (eSSP) communication.
 
-use crate::std::{
-    cmp,
-    sync::atomic::{AtomicU32, Ordering},
-};
-use crate::{Error, Result, SequenceCount, STEX};
-
-static SEQUENCE_COUNT: AtomicU32 = AtomicU32::new(0);
+use crate::std::cmp;
+use crate::{len, Error, IntermediateKey, MessageOps, ModulusKey, Result, SequenceCount, STEX};
 
 mod command;
+mod negotiator;
+mod responder;
 mod response;
+mod session;
 mod wrapped;
 
 pub use command::*;
+pub use negotiator::*;
+pub use responder::*;
 pub use response::*;
+pub use session::*;
 pub use wrapped::*;
 
 pub mod encrypted_index {
@@ -26,36 +27,60 @@ pub mod encrypted_index {
     pub const RESPONSE_STATUS: usize = 6;
 }
 
-/// Gets the current [SequenceCount].
-pub fn sequence_count() -> SequenceCount {
-    SEQUENCE_COUNT.load(Ordering::Relaxed).into()
-}
+/// Verifies the decrypted `data_len` is packed to fill the `wrapped_len` of the
+/// [WrappedEncryptedMessage] data, i.e. `STEX` and the encrypted fields.
+fn verify_packing(data_len: usize, wrapped_len: usize) -> Result<()> {
+    // count all metadata bytes except STEX
+    let raw_len = len::ENCRYPTED_METADATA - 1 + data_len;
+    let packed_len = raw_len + len::aes_packing_len(raw_len);
+    let enc_len = wrapped_len.saturating_sub(1);
 
-/// Sets the current [SequenceCount].
-pub fn set_sequence_count(count: u32) {
-    SEQUENCE_COUNT.store(count, Ordering::SeqCst);
+    if packed_len == enc_len {
+        Ok(())
+    } else {
+        Err(Error::InvalidPackingLength((packed_len, enc_len)))
+    }
 }
 
-/// Increments the [SequenceCount].
-///
-/// Returns the new [SequenceCount].
-pub fn increment_sequence_count() -> SequenceCount {
-    let count = SEQUENCE_COUNT.load(Ordering::Relaxed).saturating_add(1);
-
-    SEQUENCE_COUNT.store(count, Ordering::SeqCst);
+/// Verifies the checksum of a decrypted message.
+fn verify_encrypted_checksum(message: &dyn MessageOps) -> Result<()> {
+    message.verify_checksum().map_err(|err| match err {
+        Error::Crc(crc) => Error::EncryptedCrc(crc),
+        err => err,
+    })
+}
 
-    count.into()
+/// Verifies the [SequenceCount] of a decrypted message matches the `expected` session count.
+fn verify_count(count: SequenceCount, expected: SequenceCount) -> Result<()> {
+    if count == expected {
+        Ok(())
+    } else {
+        Err(Error::InvalidSequenceCount((
+            count.as_inner(),
+            expected.as_inner(),
+        )))
+    }
 }
 
-/// Resets the [SequenceCount] to zero.
+/// Verifies an [IntermediateKey] received during key negotiation.
 ///
-/// Returns the [SequenceCount] value before the reset.
-pub fn reset_sequence_count() -> SequenceCount {
-    let count = SEQUENCE_COUNT.load(Ordering::Relaxed);
+/// Rejects keys that confine the shared secret to a trivial subgroup: 0, 1 and `modulus - 1`.
+fn verify_intermediate_key(key: &IntermediateKey, modulus: &ModulusKey) -> Result<()> {
+    let (key, modulus) = (key.as_inner(), modulus.as_inner());
 
-    SEQUENCE_COUNT.store(0, Ordering::SeqCst);
+    if key > 1 && key < modulus.saturating_sub(1) {
+        Ok(())
+    } else {
+        Err(Error::InvalidIntermediateKey(key))
+    }
+}
 
-    count.into()
+/// Converts a length error from byte stuffing an encrypted buffer into [Error::Stuffing].
+fn stuffing_error(err: Error) -> Error {
+    match err {
+        Error::InvalidLength(len) => Error::Stuffing(len),
+        err => err,
+    }
 }
 
 /// Stuffs encrypted buffers with repeated `STX` bytes if they occur.
@@ -78,6 +103,11 @@ pub fn stuff(buf: &mut [u8], mut end: usize) -> Result<usize> {
         }
 
         if buf[i] == STX {
+            // the stuffed `STX` byte needs room at the end of the buffer
+            if end + 1 >= len {
+                return Err(Error::InvalidLength((end + 2, len)));
+            }
+
             // copy all bytes forward a position
             // e.g. convert
             //   0x7f 0xaa 0xbb
@@ -141,7 +171,7 @@ pub fn unstuff(buf: &mut [u8], mut end: usize) -> Result<usize> {
 
 #[cfg(all(test, feature = "std"))]
 pub mod tests {
-    use crate::{len, std::sync::Mutex, AesKey, MessageOps, Result};
+    use crate::{len, AesKey, MessageOps, Result};
 
     use super::*;
 
@@ -150,29 +180,29 @@ pub mod tests {
         b'p',
     ];
 
-    // Add a mutex to run tests one at a time
-    static T: Mutex<()> = Mutex::new(());
-
     fn test_key() -> AesKey {
         AesKey::clone_from_slice(TEST_KEY.as_ref())
     }
 
+    fn test_session() -> EsspSession {
+        EsspSession::from_aes_key(test_key())
+    }
+
     #[test]
     fn test_command_encryption() -> Result<()> {
         use crate::PollCommand;
 
-        let _lock = T.lock();
-
-        let key = test_key();
+        let mut host = test_session();
+        let mut device = test_session();
 
         let mut poll_msg = PollCommand::new();
 
         let mut enc_cmd = EncryptedCommand::new();
         enc_cmd.set_message_data(&mut poll_msg)?;
 
-        let wrap_msg = enc_cmd.clone().encrypt(&key);
+        let wrap_msg = enc_cmd.clone().encrypt(&mut host)?;
 
-        let dec_cmd = EncryptedCommand::decrypt(&key, wrap_msg);
+        let dec_cmd = EncryptedCommand::decrypt(&mut device, wrap_msg)?;
 
         assert_eq!(dec_cmd.data(), enc_cmd.data());
 
@@ -185,9 +215,8 @@ pub mod tests {
     fn test_response_encryption() -> Result<()> {
         use crate::{PollResponse, ResponseOps, ResponseStatus};
 
-        let _lock = T.lock();
-
-        let key = test_key();
+        let mut host = test_session();
+        let mut device = test_session();
 
         let mut poll_msg = PollResponse::new();
         poll_msg.set_response_status(ResponseStatus::Ok);
@@ -196,9 +225,9 @@ pub mod tests {
         let mut enc_res = EncryptedResponse::new();
         enc_res.set_message_data(&mut poll_msg)?;
 
-        let wrap_msg = enc_res.clone().encrypt(&key);
+        let wrap_msg = enc_res.clone().encrypt(&mut device)?;
 
-        let dec_res = EncryptedResponse::decrypt(&key, wrap_msg);
+        let dec_res = EncryptedResponse::decrypt(&mut host, wrap_msg)?;
 
         assert_eq!(dec_res.data(), enc_res.data());
 
@@ -207,10 +236,127 @@ pub mod tests {
         Ok(())
     }
 
+    fn test_response(data_len: usize) -> Result<EncryptedResponse> {
+        use crate::{PollResponse, ResponseOps, ResponseStatus};
+
+        let mut poll_msg = PollResponse::new();
+        poll_msg.set_response_status(ResponseStatus::Ok);
+        poll_msg.set_data_len(data_len as u8);
+
+        let mut enc_res = EncryptedResponse::new();
+        enc_res.set_message_data(&poll_msg)?;
+
+        Ok(enc_res)
+    }
+
     #[test]
-    fn test_byte_stuffing() -> Result<()> {
-        let _lock = T.lock();
+    fn test_decrypt_verified() -> Result<()> {
+        use crate::PollCommand;
+
+        let mut host = test_session();
+        let mut device = test_session();
+
+        let enc_cmd = EncryptedCommand::new().with_message_data(&PollCommand::new())?;
+        let wrap_msg = enc_cmd.clone().encrypt(&mut host)?;
+
+        let dec_cmd = EncryptedCommand::decrypt_verified(&mut device, wrap_msg)?;
+        assert_eq!(dec_cmd.data(), enc_cmd.data());
+        assert_eq!(device.count(), host.count());
 
+        let enc_res = test_response(1)?;
+        let wrap_msg = enc_res.clone().encrypt(&mut device)?;
+
+        let dec_res = EncryptedResponse::decrypt_verified(&mut host, wrap_msg)?;
+        assert_eq!(dec_res.data(), enc_res.data());
+        assert_eq!(host.count(), device.count());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decrypt_verified_replay() -> Result<()> {
+        let mut host = test_session();
+        let mut device = test_session();
+
+        let wrap_msg = test_response(1)?.encrypt(&mut device)?;
+
+        EncryptedResponse::decrypt_verified(&mut host, wrap_msg.clone())?;
+
+        // the replayed packet has a stale count, and leaves the session unchanged
+        assert_eq!(
+            EncryptedResponse::decrypt_verified(&mut host, wrap_msg),
+            Err(Error::InvalidSequenceCount((0, 1)))
+        );
+        assert_eq!(host.count(), SequenceCount::from(1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decrypt_verified_crc() -> Result<()> {
+        let mut host = test_session();
+        let mut device = test_session();
+
+        // long enough for two AES blocks, so the corruption leaves the length intact
+        let mut wrap_msg = test_response(12)?.encrypt(&mut device)?;
+        wrap_msg.unstuff_encrypted_data()?;
+
+        let last = wrap_msg.data_len() - 1;
+        wrap_msg.data_mut()[last] ^= 0x01;
+        wrap_msg.stuff_encrypted_data()?;
+
+        assert!(matches!(
+            EncryptedResponse::decrypt_verified(&mut host, wrap_msg),
+            Err(Error::EncryptedCrc(_))
+        ));
+        assert_eq!(host.count(), SequenceCount::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decrypt_verified_packing() -> Result<()> {
+        let mut host = test_session();
+        let mut device = test_session();
+
+        let mut wrap_msg = test_response(12)?.encrypt(&mut device)?;
+        wrap_msg.unstuff_encrypted_data()?;
+
+        // drop the second AES block
+        wrap_msg.set_data_len((1 + len::AES) as u8);
+        wrap_msg.stuff_encrypted_data()?;
+
+        assert_eq!(
+            EncryptedResponse::decrypt_verified(&mut host, wrap_msg),
+            Err(Error::InvalidPackingLength((2 * len::AES, len::AES)))
+        );
+        assert_eq!(host.count(), SequenceCount::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decrypt_stuffing_error() -> Result<()> {
+        use crate::STX;
+
+        let mut host = test_session();
+
+        // the stuffed `STX` bytes run past the end of the buffer
+        let mut wrap_msg = WrappedEncryptedMessage::new();
+        wrap_msg.set_data_len(u8::MAX);
+        wrap_msg.data_mut()[1..].fill(STX);
+
+        assert!(matches!(
+            EncryptedResponse::decrypt(&mut host, wrap_msg),
+            Err(Error::Stuffing(_))
+        ));
+        assert_eq!(host.count(), SequenceCount::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_byte_stuffing() -> Result<()> {
         let mut buf = [0x7f, 0xaa, 0xbb, 0x00];
         let exp = [0x7f, 0x7f, 0xaa, 0xbb];
         let end = 2;
@@ -248,8 +394,6 @@ pub mod tests {
 
     #[test]
     fn test_byte_unstuffing() -> Result<()> {
-        let _lock = T.lock();
-
         let mut buf = [0x7f, 0x7f, 0xaa, 0xbb];
         let exp = [0x7f, 0xaa, 0xbb, 0x00];
         let end = buf.len() - 1;
@@ -289,15 +433,13 @@ pub mod tests {
     fn test_encrypt_decrypt_stuffing() -> Result<()> {
         use crate::PollCommand;
 
-        let _lock = T.lock();
-
         let mut msg = PollCommand::new();
         let _clear_csum = msg.calculate_checksum();
 
-        let key = AesKey::from([
+        let mut session = EsspSession::from_aes_key(AesKey::from([
             0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x10, 0x11, 0x12, 0x13, 0x14,
             0x15, 0x16,
-        ]);
+        ]));
 
         #[cfg(feature = "std")]
         println!("Clear-text command buffer: {:x?}", msg.buf());
@@ -305,14 +447,14 @@ pub mod tests {
         let mut enc_msg = EncryptedCommand::new();
 
         enc_msg.set_message_data(&mut msg)?;
-        let wrap_msg = enc_msg.encrypt(&key);
+        let wrap_msg = enc_msg.encrypt(&mut session.clone())?;
 
         let _wrap_csum = wrap_msg.checksum();
 
         #[cfg(feature = "std")]
         println!("Wrapped encrypted command buffer: {:x?}", wrap_msg.buf());
 
-        let dec_msg = EncryptedResponse::decrypt(&key, wrap_msg);
+        let dec_msg = EncryptedResponse::decrypt(&mut session, wrap_msg)?;
 
         dec_msg.verify_checksum()?;
 
@@ -323,12 +465,10 @@ pub mod tests {
     fn test_encrypt_known_keys() -> Result<()> {
         use crate::{HostProtocolVersionCommand, ProtocolVersion};
 
-        let _lock = T.lock();
-
-        let key = AesKey::from([
+        let mut session = EsspSession::from_aes_key(AesKey::from([
             0x67, 0x45, 0x23, 0x01, 0x67, 0x45, 0x23, 0x01, 0x5e, 0xfa, 0xe5, 0x0d, 0x00, 0x00,
             0x00, 0x00,
-        ]);
+        ]));
 
         let exp_enc_bytes = [
             0x7f, 0x80, 0x11, 0x7e, 0x86, 0x01, 0xf3, 0xa7, 0x85, 0xec, 0x6f, 0x4b, 0x3d, 0x0f,
@@ -337,12 +477,9 @@ pub mod tests {
 
         let msg = HostProtocolVersionCommand::new().with_version(ProtocolVersion::Six);
 
-        set_sequence_count(0);
-
         let mut wrap_msg = EncryptedCommand::new()
-            .with_count(0u32.into())
             .with_message_data(&msg)?
-            .encrypt(&key);
+            .encrypt(&mut session)?;
 
         wrap_msg.set_sequence_id(128u8.into());
         wrap_msg.calculate_checksum();
@@ -11,7 +11,7 @@ use crate::{
     impl_message_from_buf, len, AesKey, CommandOps, Error, MessageOps, Result, SequenceCount,
 };
 
-use super::{encrypted_index as index, WrappedEncryptedMessage};
+use super::{encrypted_index as index, EsspSession, WrappedEncryptedMessage};
 
 /// Encrypted - Command (0x7E)
 ///
@@ -212,16 +212,20 @@ impl EncryptedCommand {
     /// Encrypts and consumes the [EncryptedCommand] message.
     ///
     /// Converts the [EncryptedCommand] message into a standard [WrappedEncryptedMessage].
-    pub fn encrypt(mut self, key: &AesKey) -> WrappedEncryptedMessage {
-        //use crate::aes;
-        use aes::cipher::{BlockEncrypt, KeyInit};
+    ///
+    /// The message [SequenceCount] is set from the [EsspSession], and the session count is
+    /// incremented.
+    ///
+    /// Returns an error, leaving the [EsspSession] unchanged, if:
+    ///
+    /// - the encrypted data is not a multiple of the [AES block length](crate::len::AES):
+    ///   [Error::Aes]
+    /// - the encrypted data can not be byte stuffed: [Error::Stuffing]
+    pub fn encrypt(mut self, session: &mut EsspSession) -> Result<WrappedEncryptedMessage> {
+        use crate::aes;
 
         self.set_packing();
-
-        if super::sequence_count().as_inner() != 0 {
-            self.set_count(super::sequence_count());
-        }
-
+        self.set_count(session.count());
         self.calculate_checksum();
 
         let mut enc_msg = WrappedEncryptedMessage::new();
@@ -231,63 +235,99 @@ impl EncryptedCommand {
 
         log::trace!("Encrypted message: {:x?}", self.buf());
 
-        let plain_data = self.encrypt_data();
         let cipher_data = enc_msg.data_mut()[1..].as_mut();
+        let plain_data = self.encrypt_data();
 
-        let ciph = aes::Aes128::new(key);
-
-        for (pchunk, cchunk) in plain_data
-            .chunks_exact(16)
-            .zip(cipher_data.chunks_exact_mut(16))
-        {
-            ciph.encrypt_block_b2b(pchunk.into(), cchunk.into());
-        }
+        aes::aes_encrypt_inplace(session.key().as_ref(), plain_data, cipher_data)?;
 
         enc_msg.calculate_checksum();
-        if let Err(err) = enc_msg.verify_checksum() {
-            log::error!("error validating wrapped encrypted checksum: {err}");
-        }
 
-        if let Err(err) = enc_msg.stuff_encrypted_data() {
-            log::error!("error stuffing encrypted command message: {err}");
-        }
+        enc_msg
+            .stuff_encrypted_data()
+            .map_err(super::stuffing_error)?;
 
-        let count = super::sequence_count();
-        let next_count = super::increment_sequence_count();
+        let count = session.count();
+        let next_count = session.increment_count();
 
         log::trace!("encryption sequence count: {count}");
         log::trace!("next encryption sequence count: {next_count}");
 
-        enc_msg
+        Ok(enc_msg)
     }
 
     /// Decrypts and consumes the [WrappedEncryptedMessage].
     ///
-    /// Converts the [WrappedEncryptedMessage] into an [EncryptedCommand].
+    /// Converts the [WrappedEncryptedMessage] into an [EncryptedCommand], and increments the
+    /// [EsspSession] count.
+    ///
+    /// Returns an error, leaving the [EsspSession] unchanged, if the message can not be
+    /// un-stuffed ([Error::Stuffing]), or decrypted ([Error::Aes]).
+    ///
+    /// The decrypted message is not verified, see [decrypt_verified](Self::decrypt_verified).
     ///
     /// **Note**: only useful if implementing a device-side binary, and/or testing host-side
     /// functionality.
-    pub fn decrypt(key: &AesKey, mut message: WrappedEncryptedMessage) -> Self {
+    pub fn decrypt(session: &mut EsspSession, message: WrappedEncryptedMessage) -> Result<Self> {
+        let (dec_msg, _) = Self::decrypt_message(session.key(), message)?;
+
+        session.increment_count();
+
+        Ok(dec_msg)
+    }
+
+    /// Decrypts and consumes the [WrappedEncryptedMessage], and verifies the decrypted message.
+    ///
+    /// Returns an error, leaving the [EsspSession] unchanged, if:
+    ///
+    /// - the message can not be un-stuffed, or decrypted: [Error::Stuffing], [Error::Aes]
+    /// - the packing does not fill the wrapped message: [Error::InvalidPackingLength]
+    /// - the inner checksum is invalid: [Error::EncryptedCrc]
+    /// - the [SequenceCount] does not match the session: [Error::InvalidSequenceCount]
+    ///
+    /// A count mismatch means the message was replayed, or the session is out of sync.
+    ///
+    /// On success, the session count is incremented.
+    ///
+    /// **Note**: only useful if implementing a device-side binary, and/or testing host-side
+    /// functionality.
+    pub fn decrypt_verified(
+        session: &mut EsspSession,
+        message: WrappedEncryptedMessage,
+    ) -> Result<Self> {
+        let (dec_msg, wrapped_len) = Self::decrypt_message(session.key(), message)?;
+
+        super::verify_packing(dec_msg.data_len(), wrapped_len)?;
+        super::verify_encrypted_checksum(&dec_msg)?;
+        super::verify_count(dec_msg.count(), session.count())?;
+
+        session.increment_count();
+
+        Ok(dec_msg)
+    }
+
+    // Returns the decrypted message, and the un-stuffed wrapped data length.
+    fn decrypt_message(
+        key: &AesKey,
+        mut message: WrappedEncryptedMessage,
+    ) -> Result<(Self, usize)> {
         use crate::aes;
 
-        if let Err(err) = message.unstuff_encrypted_data() {
-            log::error!("error unstuffing encrypted command message: {err}");
-        }
+        message
+            .unstuff_encrypted_data()
+            .map_err(super::stuffing_error)?;
+
+        let wrapped_len = message.data_len();
 
         let mut dec_msg = Self::new();
-        dec_msg.set_data_len(message.data_len().saturating_sub(len::ENCRYPTED_METADATA) as u8);
+        dec_msg.set_data_len(wrapped_len.saturating_sub(len::ENCRYPTED_METADATA) as u8);
 
         // Skip the STEX (0x7E) byte, it's not encrypted/decrypted
         let cipher_data = message.data()[1..].as_ref();
         let plain_data = dec_msg.encrypt_data();
 
-        if let Err(err) = aes::aes_decrypt_inplace(key.as_ref(), cipher_data, plain_data) {
-            log::error!("error decrypting message: {err}");
-        }
-
-        super::increment_sequence_count();
+        aes::aes_decrypt_inplace(key.as_ref(), cipher_data, plain_data)?;
 
-        dec_msg
+        Ok((dec_msg, wrapped_len))
     }
 }
 
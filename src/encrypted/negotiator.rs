@@ -0,0 +1,517 @@
+use crate::{
+    primes::Generator,
+    std::{cmp, fmt},
+    CommandOps, EncryptionKey, Error, EsspSession, GeneratorKey, IntermediateKey, MessageVariant,
+    ModulusKey, RandomKey, RequestKeyExchangeCommand, ResponseStatus, Result, SetGeneratorCommand,
+    SetModulusCommand,
+};
+
+/// Default number of attempts to negotiate a key, see [KeyNegotiator::with_max_attempts].
+pub const DEFAULT_NEGOTIATION_ATTEMPTS: usize = 3;
+
+/// Steps of the `eSSP` key negotiation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NegotiationStep {
+    /// Send the [GeneratorKey] to the device.
+    #[default]
+    SetGenerator,
+    /// Send the [ModulusKey] to the device.
+    SetModulus,
+    /// Exchange [IntermediateKey]s with the device.
+    RequestKeyExchange,
+    /// The [EncryptionKey] is negotiated.
+    Complete,
+}
+
+impl NegotiationStep {
+    /// Creates a new [NegotiationStep].
+    pub const fn new() -> Self {
+        Self::SetGenerator
+    }
+}
+
+/// Command sent to the device for a [NegotiationStep].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NegotiationCommand {
+    SetGenerator(SetGeneratorCommand),
+    SetModulus(SetModulusCommand),
+    RequestKeyExchange(RequestKeyExchangeCommand),
+}
+
+impl NegotiationCommand {
+    /// Gets a mutable reference to the inner command, e.g. to send it over a transport.
+    pub fn as_command_mut(&mut self) -> &mut dyn CommandOps {
+        match self {
+            Self::SetGenerator(cmd) => cmd,
+            Self::SetModulus(cmd) => cmd,
+            Self::RequestKeyExchange(cmd) => cmd,
+        }
+    }
+}
+
+/// Host-side state machine for the `eSSP` Diffie-Hellman key negotiation:
+///
+/// ```no_build,no_run
+/// SetGenerator -> SetModulus -> RequestKeyExchange -> EsspSession
+/// ```
+///
+/// The negotiator does not own a transport. Send each [command](Self::command) to the device,
+/// and pass the reply to [handle_reply](Self::handle_reply), or let
+/// [negotiate](Self::negotiate) run the steps with a request function.
+///
+/// The spec requires the generator to be larger than the modulus, and both to be prime. If the
+/// device answers with [KeyNotSet](ResponseStatus::KeyNotSet) or
+/// [ParameterOutOfRange](ResponseStatus::ParameterOutOfRange), new primes are generated, and
+/// the negotiation starts over.
+pub struct KeyNegotiator {
+    primes: Generator,
+    generator: GeneratorKey,
+    modulus: ModulusKey,
+    random: RandomKey,
+    step: NegotiationStep,
+    attempts: usize,
+    max_attempts: usize,
+}
+
+impl KeyNegotiator {
+    /// Creates a new [KeyNegotiator], generating the [GeneratorKey] and [ModulusKey] from the
+    /// prime [Generator].
+    pub fn new(mut primes: Generator, random: RandomKey) -> Self {
+        let (generator, modulus) = Self::new_primes(&mut primes);
+
+        Self {
+            primes,
+            generator,
+            modulus,
+            random,
+            step: NegotiationStep::new(),
+            attempts: 0,
+            max_attempts: DEFAULT_NEGOTIATION_ATTEMPTS,
+        }
+    }
+
+    /// Creates a new [KeyNegotiator] from the provided keys.
+    ///
+    /// The prime [Generator] is used to test the keys, and to generate new primes if the device
+    /// rejects them.
+    ///
+    /// Returns an error if:
+    ///
+    /// - the generator is not larger than the modulus: [Error::InvalidGenerator]
+    /// - either key is not prime: [Error::InvalidPrime]
+    pub fn from_keys(
+        mut primes: Generator,
+        generator: GeneratorKey,
+        modulus: ModulusKey,
+        random: RandomKey,
+    ) -> Result<Self> {
+        Self::verify_primes(&mut primes, &generator, &modulus)?;
+
+        Ok(Self {
+            primes,
+            generator,
+            modulus,
+            random,
+            step: NegotiationStep::new(),
+            attempts: 0,
+            max_attempts: DEFAULT_NEGOTIATION_ATTEMPTS,
+        })
+    }
+
+    /// Creates a new [KeyNegotiator], generating the primes and [RandomKey] from system entropy.
+    #[cfg(feature = "std")]
+    pub fn from_entropy() -> Self {
+        Self::new(Generator::from_entropy(), RandomKey::from_entropy())
+    }
+
+    /// Builder function that sets the maximum number of attempts to negotiate a key.
+    ///
+    /// Each device response rejecting the primes uses an attempt. At least one attempt is made.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Gets the maximum number of attempts to negotiate a key.
+    pub fn max_attempts(&self) -> usize {
+        self.max_attempts
+    }
+
+    /// Gets the number of attempts rejected by the device.
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// Gets the current [NegotiationStep].
+    pub fn step(&self) -> NegotiationStep {
+        self.step
+    }
+
+    /// Gets the [GeneratorKey].
+    pub fn generator(&self) -> &GeneratorKey {
+        &self.generator
+    }
+
+    /// Gets the [ModulusKey].
+    pub fn modulus(&self) -> &ModulusKey {
+        &self.modulus
+    }
+
+    /// Gets the host [IntermediateKey] sent in the [RequestKeyExchangeCommand].
+    pub fn host_key(&self) -> IntermediateKey {
+        IntermediateKey::from_keys(&self.generator, &self.random, &self.modulus)
+    }
+
+    /// Restarts the negotiation from [NegotiationStep::SetGenerator], keeping the current keys.
+    pub fn reset(&mut self) {
+        self.step = NegotiationStep::SetGenerator;
+        self.attempts = 0;
+    }
+
+    /// Gets the command to send to the device for the current [NegotiationStep].
+    ///
+    /// Returns `None` when the negotiation is [complete](NegotiationStep::Complete).
+    pub fn command(&self) -> Option<NegotiationCommand> {
+        match self.step {
+            NegotiationStep::SetGenerator => {
+                let mut cmd = SetGeneratorCommand::new();
+                cmd.set_generator(&self.generator);
+
+                Some(NegotiationCommand::SetGenerator(cmd))
+            }
+            NegotiationStep::SetModulus => {
+                let mut cmd = SetModulusCommand::new();
+                cmd.set_modulus(&self.modulus);
+
+                Some(NegotiationCommand::SetModulus(cmd))
+            }
+            NegotiationStep::RequestKeyExchange => {
+                let mut cmd = RequestKeyExchangeCommand::new();
+                cmd.set_intermediate_key(&self.host_key());
+
+                Some(NegotiationCommand::RequestKeyExchange(cmd))
+            }
+            NegotiationStep::Complete => None,
+        }
+    }
+
+    /// Handles the device `reply` to the current [command](Self::command), and advances to the
+    /// next [NegotiationStep].
+    ///
+    /// The `reply` may hold a non-OK [ResponseStatus], or an [Error::Status] from a driver that
+    /// already checked the status.
+    ///
+    /// Returns the new [EsspSession] when the negotiation is complete.
+    ///
+    /// Returns an error if the request failed, the device rejected the primes on all
+    /// [max_attempts](Self::max_attempts), or the device [IntermediateKey] is invalid
+    /// ([Error::InvalidIntermediateKey]).
+    pub fn handle_reply(&mut self, reply: Result<MessageVariant>) -> Result<Option<EsspSession>> {
+        let reply = reply.and_then(|res| {
+            let status = res.as_response().response_status();
+
+            if status.is_ok() {
+                Ok(res)
+            } else {
+                Err(Error::Status(status))
+            }
+        });
+
+        match reply {
+            Ok(res) => self.advance(res),
+            Err(Error::Status(
+                status @ (ResponseStatus::KeyNotSet | ResponseStatus::ParameterOutOfRange),
+            )) if self.attempts + 1 < self.max_attempts => {
+                self.attempts += 1;
+
+                log::warn!(
+                    "Device rejected eSSP keys: {status}, generating new primes, attempt: {}",
+                    self.attempts
+                );
+
+                let (generator, modulus) = Self::new_primes(&mut self.primes);
+                self.generator = generator;
+                self.modulus = modulus;
+                self.step = NegotiationStep::SetGenerator;
+
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Runs the negotiation from the start, sending each command with the `request` function.
+    ///
+    /// The `request` function sends a command over any transport, and returns the device reply,
+    /// e.g. [RequestEngine::request](crate::RequestEngine::request).
+    pub fn negotiate<F>(&mut self, mut request: F) -> Result<EsspSession>
+    where
+        F: FnMut(&mut dyn CommandOps) -> Result<MessageVariant>,
+    {
+        self.reset();
+
+        while let Some(mut command) = self.command() {
+            if let Some(session) = self.handle_reply(request(command.as_command_mut()))? {
+                return Ok(session);
+            }
+        }
+
+        Err(Error::Status(ResponseStatus::KeyNotSet))
+    }
+
+    fn advance(&mut self, res: MessageVariant) -> Result<Option<EsspSession>> {
+        match self.step {
+            NegotiationStep::SetGenerator => self.step = NegotiationStep::SetModulus,
+            NegotiationStep::SetModulus => self.step = NegotiationStep::RequestKeyExchange,
+            NegotiationStep::RequestKeyExchange => {
+                let device_key = res.into_request_key_exchange_response()?.intermediate_key();
+                super::verify_intermediate_key(&device_key, &self.modulus)?;
+
+                let key = EncryptionKey::from_keys(&device_key, &self.random, &self.modulus);
+
+                self.step = NegotiationStep::Complete;
+
+                return Ok(Some(EsspSession::new(key)));
+            }
+            NegotiationStep::Complete => (),
+        }
+
+        Ok(None)
+    }
+
+    fn new_primes(primes: &mut Generator) -> (GeneratorKey, ModulusKey) {
+        loop {
+            let (generator, modulus) = (primes.new_prime(), primes.new_prime());
+
+            // the generator is expected to be the larger prime
+            match generator.cmp(&modulus) {
+                cmp::Ordering::Greater => return (generator.into(), modulus.into()),
+                cmp::Ordering::Less => return (modulus.into(), generator.into()),
+                cmp::Ordering::Equal => (),
+            }
+        }
+    }
+
+    fn verify_primes(
+        primes: &mut Generator,
+        generator: &GeneratorKey,
+        modulus: &ModulusKey,
+    ) -> Result<()> {
+        let (gen, modulus) = (generator.as_inner(), modulus.as_inner());
+
+        if gen <= modulus {
+            Err(Error::InvalidGenerator((gen, modulus)))
+        } else if !primes.is_probable_prime(gen) {
+            Err(Error::InvalidPrime(gen))
+        } else if !primes.is_probable_prime(modulus) {
+            Err(Error::InvalidPrime(modulus))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Debug for KeyNegotiator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // leave the key material out of logs
+        f.debug_struct("KeyNegotiator")
+            .field("step", &self.step)
+            .field("attempts", &self.attempts)
+            .field("max_attempts", &self.max_attempts)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        MessageType, RequestKeyExchangeResponse, ResponseOps, SetGeneratorResponse,
+        SetModulusResponse,
+    };
+
+    const SEED: [u8; 32] = [0x5a; 32];
+
+    // Device side of the key exchange, rejecting the first `rejects` key exchanges.
+    #[derive(Default)]
+    struct TestDevice {
+        generator: Option<GeneratorKey>,
+        modulus: Option<ModulusKey>,
+        key: Option<EncryptionKey>,
+        device_key: Option<IntermediateKey>,
+        rejects: usize,
+    }
+
+    impl TestDevice {
+        fn request(&mut self, command: &mut dyn CommandOps) -> Result<MessageVariant> {
+            command.calculate_checksum();
+            let buf = command.buf();
+
+            let mut res = match command.command() {
+                MessageType::SetGenerator => {
+                    self.generator = Some(SetGeneratorCommand::try_from(buf)?.generator());
+                    MessageVariant::SetGeneratorResponse(SetGeneratorResponse::new())
+                }
+                MessageType::SetModulus => {
+                    self.modulus = Some(SetModulusCommand::try_from(buf)?.modulus());
+                    MessageVariant::SetModulusResponse(SetModulusResponse::new())
+                }
+                _ => {
+                    let host_key = RequestKeyExchangeCommand::try_from(buf)?.intermediate_key();
+                    let (generator, modulus) = (self.generator.take(), self.modulus.take());
+
+                    let mut res = RequestKeyExchangeResponse::new();
+
+                    match (generator, modulus) {
+                        (Some(generator), Some(modulus)) if self.rejects == 0 => {
+                            let random = RandomKey::from_inner(0x1234_5678_9abc);
+
+                            res.set_intermediate_key(self.device_key.clone().unwrap_or_else(
+                                || IntermediateKey::from_keys(&generator, &random, &modulus),
+                            ));
+                            self.key = Some(EncryptionKey::from_keys(&host_key, &random, &modulus));
+                        }
+                        _ => {
+                            self.rejects = self.rejects.saturating_sub(1);
+                            res.set_response_status(ResponseStatus::KeyNotSet);
+
+                            return Ok(MessageVariant::RequestKeyExchangeResponse(res));
+                        }
+                    }
+
+                    MessageVariant::RequestKeyExchangeResponse(res)
+                }
+            };
+
+            res.as_response_mut()
+                .set_response_status(ResponseStatus::Ok);
+
+            Ok(res)
+        }
+    }
+
+    #[test]
+    fn test_key_negotiator() -> Result<()> {
+        let mut device = TestDevice::default();
+        let mut negotiator =
+            KeyNegotiator::new(Generator::from_seed(SEED), RandomKey::from_seed(SEED));
+
+        assert!(negotiator.generator().as_inner() > negotiator.modulus().as_inner());
+
+        let session = negotiator.negotiate(|command| device.request(command))?;
+
+        assert_eq!(negotiator.step(), NegotiationStep::Complete);
+        assert_eq!(negotiator.command(), None);
+        assert_eq!(Some(session.encryption_key()), device.key.as_ref());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_negotiator_steps() -> Result<()> {
+        let mut device = TestDevice::default();
+        let mut negotiator =
+            KeyNegotiator::new(Generator::from_seed(SEED), RandomKey::from_seed(SEED));
+
+        let mut session = None;
+
+        for step in [
+            NegotiationStep::SetGenerator,
+            NegotiationStep::SetModulus,
+            NegotiationStep::RequestKeyExchange,
+        ] {
+            assert_eq!(negotiator.step(), step);
+            assert!(session.is_none());
+
+            let mut command = negotiator.command().unwrap();
+            session = negotiator.handle_reply(device.request(command.as_command_mut()))?;
+        }
+
+        assert_eq!(
+            session.as_ref().map(EsspSession::encryption_key),
+            device.key.as_ref()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_negotiator_regenerates_primes() -> Result<()> {
+        let mut device = TestDevice {
+            rejects: 2,
+            ..Default::default()
+        };
+        let mut negotiator =
+            KeyNegotiator::new(Generator::from_seed(SEED), RandomKey::from_seed(SEED));
+
+        let generator = negotiator.generator().clone();
+
+        let session = negotiator.negotiate(|command| device.request(command))?;
+
+        assert_eq!(negotiator.attempts(), 2);
+        assert_ne!(negotiator.generator(), &generator);
+        assert_eq!(Some(session.encryption_key()), device.key.as_ref());
+
+        // the device keeps rejecting the keys
+        let mut device = TestDevice {
+            rejects: 2,
+            ..Default::default()
+        };
+        let mut negotiator = negotiator.with_max_attempts(2);
+
+        assert_eq!(
+            negotiator.negotiate(|command| device.request(command)),
+            Err(Error::Status(ResponseStatus::KeyNotSet))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_negotiator_invalid_keys() {
+        let primes = || Generator::from_seed(SEED);
+        let random = RandomKey::from_seed(SEED);
+
+        // known primes from a device capture
+        let (gen, modulus) = (0x7fcc_9ee3u64, 0x7f1c_7181u64);
+
+        assert_eq!(
+            KeyNegotiator::from_keys(primes(), modulus.into(), gen.into(), random.clone())
+                .map(|_| ()),
+            Err(Error::InvalidGenerator((modulus, gen)))
+        );
+        assert_eq!(
+            KeyNegotiator::from_keys(primes(), (gen + 2).into(), modulus.into(), random.clone())
+                .map(|_| ()),
+            Err(Error::InvalidPrime(gen + 2))
+        );
+        assert_eq!(
+            KeyNegotiator::from_keys(primes(), gen.into(), 0x100.into(), random.clone())
+                .map(|_| ()),
+            Err(Error::InvalidPrime(0x100))
+        );
+        assert!(KeyNegotiator::from_keys(primes(), gen.into(), modulus.into(), random).is_ok());
+    }
+
+    #[test]
+    fn test_key_negotiator_invalid_device_key() {
+        let negotiator = KeyNegotiator::new(Generator::from_seed(SEED), RandomKey::from_seed(SEED));
+        let modulus = negotiator.modulus().as_inner();
+
+        for key in [0, 1, modulus - 1] {
+            let mut device = TestDevice {
+                device_key: Some(key.into()),
+                ..Default::default()
+            };
+            let mut negotiator =
+                KeyNegotiator::new(Generator::from_seed(SEED), RandomKey::from_seed(SEED));
+
+            assert_eq!(
+                negotiator
+                    .negotiate(|command| device.request(command))
+                    .map(|_| ()),
+                Err(Error::InvalidIntermediateKey(key))
+            );
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use crate::{
+    primes::Generator, std::fmt, EncryptionKey, Error, EsspSession, GeneratorKey, IntermediateKey,
+    ModulusKey, RandomKey, RequestKeyExchangeCommand, RequestKeyExchangeResponse, ResponseOps,
+    ResponseStatus, Result, SetGeneratorCommand, SetGeneratorResponse, SetModulusCommand,
+    SetModulusResponse,
+};
+
+/// Device-side responder for the `eSSP` Diffie-Hellman key negotiation.
+///
+/// The counterpart of the host [KeyNegotiator](super::KeyNegotiator), answering the
+/// [SetGeneratorCommand], [SetModulusCommand] and [RequestKeyExchangeCommand].
+///
+/// Each handler returns the reply with an OK [ResponseStatus], or an [Error::Status] with the
+/// status the device should reply with:
+///
+/// - [ParameterOutOfRange](ResponseStatus::ParameterOutOfRange): a key is not prime, the
+///   generator is not larger than the modulus, or the host [IntermediateKey] is invalid
+/// - [KeyNotSet](ResponseStatus::KeyNotSet): the key exchange was requested before the generator
+///   and modulus were set
+///
+/// **Note**: only useful if implementing a device-side binary, and/or testing host-side
+/// functionality.
+pub struct KeyExchangeResponder {
+    primes: Generator,
+    generator: Option<GeneratorKey>,
+    modulus: Option<ModulusKey>,
+    key: Option<EncryptionKey>,
+}
+
+impl KeyExchangeResponder {
+    /// Creates a new [KeyExchangeResponder].
+    ///
+    /// The prime [Generator] is used to test the received primes, and to generate the device
+    /// [RandomKey].
+    pub fn new(primes: Generator) -> Self {
+        Self {
+            primes,
+            generator: None,
+            modulus: None,
+            key: None,
+        }
+    }
+
+    /// Creates a new [KeyExchangeResponder] using system entropy.
+    #[cfg(feature = "std")]
+    pub fn from_entropy() -> Self {
+        Self::new(Generator::from_entropy())
+    }
+
+    /// Gets the [GeneratorKey] set by the host.
+    pub fn generator(&self) -> Option<&GeneratorKey> {
+        self.generator.as_ref()
+    }
+
+    /// Gets the [ModulusKey] set by the host.
+    pub fn modulus(&self) -> Option<&ModulusKey> {
+        self.modulus.as_ref()
+    }
+
+    /// Gets the [EncryptionKey] derived in the last key exchange.
+    pub fn encryption_key(&self) -> Option<&EncryptionKey> {
+        self.key.as_ref()
+    }
+
+    /// Creates a new [EsspSession] from the [EncryptionKey] derived in the last key exchange.
+    pub fn session(&self) -> Option<EsspSession> {
+        self.key.clone().map(EsspSession::new)
+    }
+
+    /// Handles a [SetGeneratorCommand].
+    ///
+    /// Starts a new key exchange, clearing the [ModulusKey] and [EncryptionKey] from any previous
+    /// exchange.
+    ///
+    /// Returns [ParameterOutOfRange](ResponseStatus::ParameterOutOfRange) if the generator is not
+    /// prime.
+    pub fn set_generator(&mut self, command: &SetGeneratorCommand) -> Result<SetGeneratorResponse> {
+        self.generator = None;
+        self.modulus = None;
+        self.key = None;
+
+        let generator = command.generator();
+
+        if !self.primes.is_probable_prime(generator.as_inner()) {
+            return Err(Self::out_of_range(Error::InvalidPrime(
+                generator.as_inner(),
+            )));
+        }
+
+        self.generator = Some(generator);
+
+        let mut res = SetGeneratorResponse::new();
+        res.set_response_status(ResponseStatus::Ok);
+
+        Ok(res)
+    }
+
+    /// Handles a [SetModulusCommand].
+    ///
+    /// Returns [ParameterOutOfRange](ResponseStatus::ParameterOutOfRange) if the modulus is not
+    /// prime.
+    pub fn set_modulus(&mut self, command: &SetModulusCommand) -> Result<SetModulusResponse> {
+        let modulus = command.modulus();
+
+        if !self.primes.is_probable_prime(modulus.as_inner()) {
+            return Err(Self::out_of_range(Error::InvalidPrime(modulus.as_inner())));
+        }
+
+        self.modulus = Some(modulus);
+
+        let mut res = SetModulusResponse::new();
+        res.set_response_status(ResponseStatus::Ok);
+
+        Ok(res)
+    }
+
+    /// Handles a [RequestKeyExchangeCommand].
+    ///
+    /// Generates the device [RandomKey], and replies with the device [IntermediateKey]. The
+    /// [EncryptionKey] is derived from the host [IntermediateKey].
+    ///
+    /// Returns:
+    ///
+    /// - [KeyNotSet](ResponseStatus::KeyNotSet) if the generator or modulus is not set
+    /// - [ParameterOutOfRange](ResponseStatus::ParameterOutOfRange) if the generator is not larger
+    ///   than the modulus, or the host [IntermediateKey] is invalid
+    pub fn request_key_exchange(
+        &mut self,
+        command: &RequestKeyExchangeCommand,
+    ) -> Result<RequestKeyExchangeResponse> {
+        let (Some(generator), Some(modulus)) = (self.generator.as_ref(), self.modulus.as_ref())
+        else {
+            return Err(Error::Status(ResponseStatus::KeyNotSet));
+        };
+
+        if generator.as_inner() <= modulus.as_inner() {
+            return Err(Self::out_of_range(Error::InvalidGenerator((
+                generator.as_inner(),
+                modulus.as_inner(),
+            ))));
+        }
+
+        let host_key = command.intermediate_key();
+        super::verify_intermediate_key(&host_key, modulus).map_err(Self::out_of_range)?;
+
+        let random = RandomKey::from_generator(&mut self.primes);
+        let device_key = IntermediateKey::from_keys(generator, &random, modulus);
+
+        self.key = Some(EncryptionKey::from_keys(&host_key, &random, modulus));
+
+        let mut res = RequestKeyExchangeResponse::new();
+        res.set_intermediate_key(device_key);
+        res.set_response_status(ResponseStatus::Ok);
+
+        Ok(res)
+    }
+
+    fn out_of_range(err: Error) -> Error {
+        log::warn!("Rejecting eSSP key exchange: {err}");
+
+        Error::Status(ResponseStatus::ParameterOutOfRange)
+    }
+}
+
+impl fmt::Debug for KeyExchangeResponder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // leave the key material out of logs
+        f.debug_struct("KeyExchangeResponder")
+            .field("generator", &self.generator.is_some())
+            .field("modulus", &self.modulus.is_some())
+            .field("key", &self.key.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CommandOps, KeyNegotiator, MessageType, MessageVariant};
+
+    const SEED: [u8; 32] = [0xa5; 32];
+
+    // known primes from a device capture
+    const GENERATOR: u64 = 0x7fcc_9ee3;
+    const MODULUS: u64 = 0x7f1c_7181;
+
+    fn respond(
+        responder: &mut KeyExchangeResponder,
+        command: &mut dyn CommandOps,
+    ) -> Result<MessageVariant> {
+        command.calculate_checksum();
+        let buf = command.buf();
+
+        match command.command() {
+            MessageType::SetGenerator => responder
+                .set_generator(&SetGeneratorCommand::try_from(buf)?)
+                .map(MessageVariant::SetGeneratorResponse),
+            MessageType::SetModulus => responder
+                .set_modulus(&SetModulusCommand::try_from(buf)?)
+                .map(MessageVariant::SetModulusResponse),
+            msg_type => {
+                assert_eq!(msg_type, MessageType::RequestKeyExchange);
+
+                responder
+                    .request_key_exchange(&RequestKeyExchangeCommand::try_from(buf)?)
+                    .map(MessageVariant::RequestKeyExchangeResponse)
+            }
+        }
+    }
+
+    fn set_keys(responder: &mut KeyExchangeResponder, generator: u64, modulus: u64) -> Result<()> {
+        let mut gen_cmd = SetGeneratorCommand::new();
+        gen_cmd.set_generator(&generator.into());
+        responder.set_generator(&gen_cmd)?;
+
+        let mut mod_cmd = SetModulusCommand::new();
+        mod_cmd.set_modulus(&modulus.into());
+        responder.set_modulus(&mod_cmd)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_exchange_responder() -> Result<()> {
+        let mut responder = KeyExchangeResponder::new(Generator::from_seed(SEED));
+        let mut negotiator =
+            KeyNegotiator::new(Generator::from_seed([0x5a; 32]), RandomKey::from_seed(SEED));
+
+        let host = negotiator.negotiate(|command| respond(&mut responder, command))?;
+        let device = responder.session().unwrap();
+
+        assert_eq!(host, device);
+        assert_eq!(responder.generator(), Some(negotiator.generator()));
+        assert_eq!(responder.modulus(), Some(negotiator.modulus()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_exchange_responder_not_prime() {
+        let mut responder = KeyExchangeResponder::new(Generator::from_seed(SEED));
+
+        let mut gen_cmd = SetGeneratorCommand::new();
+        gen_cmd.set_generator(&(GENERATOR + 2).into());
+
+        assert_eq!(
+            responder.set_generator(&gen_cmd),
+            Err(Error::Status(ResponseStatus::ParameterOutOfRange))
+        );
+        assert_eq!(responder.generator(), None);
+
+        let mut mod_cmd = SetModulusCommand::new();
+        mod_cmd.set_modulus(&0x100.into());
+
+        assert_eq!(
+            responder.set_modulus(&mod_cmd),
+            Err(Error::Status(ResponseStatus::ParameterOutOfRange))
+        );
+        assert_eq!(responder.modulus(), None);
+    }
+
+    #[test]
+    fn test_key_exchange_responder_rejects() -> Result<()> {
+        let mut responder = KeyExchangeResponder::new(Generator::from_seed(SEED));
+
+        let mut key_cmd = RequestKeyExchangeCommand::new();
+        key_cmd.set_intermediate_key(&0x1234.into());
+
+        assert_eq!(
+            responder.request_key_exchange(&key_cmd),
+            Err(Error::Status(ResponseStatus::KeyNotSet))
+        );
+
+        // the generator must be the larger prime
+        set_keys(&mut responder, MODULUS, GENERATOR)?;
+
+        assert_eq!(
+            responder.request_key_exchange(&key_cmd),
+            Err(Error::Status(ResponseStatus::ParameterOutOfRange))
+        );
+
+        set_keys(&mut responder, GENERATOR, MODULUS)?;
+
+        for host_key in [0, 1, MODULUS - 1] {
+            key_cmd.set_intermediate_key(&host_key.into());
+
+            assert_eq!(
+                responder.request_key_exchange(&key_cmd),
+                Err(Error::Status(ResponseStatus::ParameterOutOfRange))
+            );
+        }
+
+        assert_eq!(responder.encryption_key(), None);
+
+        key_cmd.set_intermediate_key(&0x1234.into());
+        responder.request_key_exchange(&key_cmd)?;
+
+        assert!(responder.encryption_key().is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_exchange_responder_new_exchange() -> Result<()> {
+        let mut responder = KeyExchangeResponder::new(Generator::from_seed(SEED));
+        set_keys(&mut responder, GENERATOR, MODULUS)?;
+
+        let mut key_cmd = RequestKeyExchangeCommand::new();
+        key_cmd.set_intermediate_key(&0x1234.into());
+        responder.request_key_exchange(&key_cmd)?;
+
+        assert!(responder.encryption_key().is_some());
+
+        // a failed renegotiation must not leave the previous key in place
+        let mut gen_cmd = SetGeneratorCommand::new();
+        gen_cmd.set_generator(&(GENERATOR + 2).into());
+
+        assert!(responder.set_generator(&gen_cmd).is_err());
+        assert_eq!(responder.modulus(), None);
+        assert_eq!(responder.encryption_key(), None);
+        assert!(responder.session().is_none());
+
+        assert_eq!(
+            responder.request_key_exchange(&key_cmd),
+            Err(Error::Status(ResponseStatus::KeyNotSet))
+        );
+
+        Ok(())
+    }
+}
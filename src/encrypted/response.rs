@@ -11,7 +11,7 @@ use crate::{
     SequenceCount,
 };
 
-use super::{encrypted_index as index, WrappedEncryptedMessage};
+use super::{encrypted_index as index, EsspSession, WrappedEncryptedMessage};
 
 /// Encrypted - Response (0x7E)
 ///
@@ -48,6 +48,17 @@ impl EncryptedResponse {
         self.buf[index::COUNT..index::COUNT_END].as_ref()
     }
 
+    fn set_count(&mut self, count: SequenceCount) {
+        self.buf[index::COUNT..index::COUNT_END]
+            .copy_from_slice(count.as_inner().to_le_bytes().as_ref());
+    }
+
+    /// Builder function that sets the [SequenceCount].
+    pub fn with_count(mut self, count: SequenceCount) -> Self {
+        self.set_count(count);
+        self
+    }
+
     /// Gets the message data.
     pub fn message_data(&self) -> &[u8] {
         let start = self.data_start();
@@ -76,7 +87,7 @@ impl EncryptedResponse {
     /// encrypted message is wrapped in an outer standard SSP message.
     ///
     /// Matryoshka dolls all the way down...
-    pub fn set_message_data<M: ResponseOps>(&mut self, message: &M) -> Result<()> {
+    pub fn set_message_data<M: ResponseOps + ?Sized>(&mut self, message: &M) -> Result<()> {
         let len = message.data_len();
 
         if message.data().len() != len {
@@ -176,7 +187,7 @@ impl EncryptedResponse {
     }
 
     fn encrypt_data(&mut self) -> &mut [u8] {
-        let len = cmp::min(self.len(), len::MAX_ENCRYPTED_DATA);
+        let len = cmp::min(self.len(), self.buf.len());
         self.buf[index::LEN..len].as_mut()
     }
 
@@ -184,12 +195,19 @@ impl EncryptedResponse {
     ///
     /// Converts the [EncryptedResponse] message into a standard [WrappedEncryptedMessage].
     ///
+    /// The message [SequenceCount] is set from the [EsspSession], and the session count is
+    /// incremented.
+    ///
+    /// Returns an error, leaving the [EsspSession] unchanged, if the message can not be
+    /// encrypted ([Error::Aes]), or byte stuffed ([Error::Stuffing]).
+    ///
     /// **Note**: only useful if implementing a device-side binary, and/or testing host-side
     /// functionality.
-    pub fn encrypt(mut self, key: &AesKey) -> WrappedEncryptedMessage {
+    pub fn encrypt(mut self, session: &mut EsspSession) -> Result<WrappedEncryptedMessage> {
         use crate::aes;
 
         self.set_packing();
+        self.set_count(session.count());
         self.calculate_checksum();
 
         let mut enc_msg = WrappedEncryptedMessage::new();
@@ -200,51 +218,97 @@ impl EncryptedResponse {
         let cipher_data = enc_msg.data_mut()[1..].as_mut();
         let plain_data = self.encrypt_data();
 
-        if let Err(err) = aes::aes_encrypt_inplace(key.as_ref(), plain_data, cipher_data) {
-            log::error!("error encrypting response message: {err}");
-        }
+        aes::aes_encrypt_inplace(session.key().as_ref(), plain_data, cipher_data)?;
 
-        super::increment_sequence_count();
+        enc_msg
+            .stuff_encrypted_data()
+            .map_err(super::stuffing_error)?;
 
-        if let Err(err) = enc_msg.stuff_encrypted_data() {
-            log::error!("error stuffing encrypted response message: {err}");
-        }
+        session.increment_count();
 
-        enc_msg
+        Ok(enc_msg)
     }
 
     /// Decrypts and consumes the [WrappedEncryptedMessage].
     ///
-    /// Converts the [WrappedEncryptedMessage] into an [EncryptedResponse].
-    pub fn decrypt(key: &AesKey, mut message: WrappedEncryptedMessage) -> Self {
-        use crate::aes;
+    /// Converts the [WrappedEncryptedMessage] into an [EncryptedResponse], and increments the
+    /// [EsspSession] count.
+    ///
+    /// Returns an error, leaving the [EsspSession] unchanged, if the message can not be
+    /// un-stuffed ([Error::Stuffing]), or decrypted ([Error::Aes]).
+    ///
+    /// If the decrypted [SequenceCount] does not match the session, the session count is
+    /// re-synchronised to the decrypted count. Use [decrypt_verified](Self::decrypt_verified) to
+    /// reject the message instead.
+    pub fn decrypt(session: &mut EsspSession, message: WrappedEncryptedMessage) -> Result<Self> {
+        let (dec_msg, _) = Self::decrypt_message(session.key(), message)?;
+
+        let seq_count = session.count();
+        let dec_count = dec_msg.count();
 
-        if let Err(err) = message.unstuff_encrypted_data() {
-            log::error!("error unstuffing encrypted response message: {err}");
+        if seq_count != dec_count {
+            log::error!("decryption sequence count is out of sync: have: {dec_count}, expected: {seq_count}");
+            session.set_count(dec_count);
         }
 
+        session.increment_count();
+
+        Ok(dec_msg)
+    }
+
+    /// Decrypts and consumes the [WrappedEncryptedMessage], and verifies the decrypted message.
+    ///
+    /// Returns an error, leaving the [EsspSession] unchanged, if:
+    ///
+    /// - the message can not be un-stuffed, or decrypted: [Error::Stuffing], [Error::Aes]
+    /// - the packing does not fill the wrapped message: [Error::InvalidPackingLength]
+    /// - the inner checksum is invalid: [Error::EncryptedCrc]
+    /// - the [SequenceCount] does not match the session: [Error::InvalidSequenceCount]
+    ///
+    /// A count mismatch means the message was replayed, or the session is out of sync. See
+    /// [ResyncPolicy](super::ResyncPolicy) for recovering the session.
+    ///
+    /// On success, the session count is incremented.
+    pub fn decrypt_verified(
+        session: &mut EsspSession,
+        message: WrappedEncryptedMessage,
+    ) -> Result<Self> {
+        let (dec_msg, wrapped_len) = Self::decrypt_message(session.key(), message)?;
+
+        super::verify_packing(dec_msg.data_len(), wrapped_len)?;
+        super::verify_encrypted_checksum(&dec_msg)?;
+        super::verify_count(dec_msg.count(), session.count())?;
+
+        session.increment_count();
+
+        Ok(dec_msg)
+    }
+
+    // Returns the decrypted message, and the un-stuffed wrapped data length.
+    fn decrypt_message(
+        key: &AesKey,
+        mut message: WrappedEncryptedMessage,
+    ) -> Result<(Self, usize)> {
+        use crate::aes;
+
+        message
+            .unstuff_encrypted_data()
+            .map_err(super::stuffing_error)?;
+
+        let wrapped_len = message.data_len();
+
         let mut dec_msg = Self::new();
-        dec_msg.set_data_len(message.data_len().saturating_sub(len::ENCRYPTED_METADATA) as u8);
+        dec_msg.set_data_len(wrapped_len.saturating_sub(len::ENCRYPTED_METADATA) as u8);
 
         // Skip the STEX (0x7E) byte, it's not encrypted/decrypted
         let cipher_data = message.data()[1..].as_ref();
         let plain_data = dec_msg.encrypt_data();
 
-        if let Err(err) = aes::aes_decrypt_inplace(key.as_ref(), cipher_data, plain_data) {
-            log::error!("error decrypting response message: {err}");
-        }
+        aes::aes_decrypt_inplace(key.as_ref(), cipher_data, plain_data)?;
 
         log::trace!("decrypted data: {:x?}", plain_data);
 
-        let seq_count = super::sequence_count();
-        let dec_count = dec_msg.count();
-
-        if seq_count != dec_count {
-            log::error!("decryption sequence count is out of sync: have: {dec_count}, expected: {seq_count}");
-            super::set_sequence_count(dec_count.as_inner());
-        }
-
-        dec_msg
+        Ok((dec_msg, wrapped_len))
     }
 }
 
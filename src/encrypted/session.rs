@@ -0,0 +1,228 @@
+use crate::{
+    len,
+    std::{fmt, mem},
+    AesKey, EncryptionKey, Error, FixedKey, SequenceCount,
+};
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Encryption context for an `eSSP` session with a single device.
+///
+/// Owns the [AesKey], and the negotiated keys it is built from:
+///
+/// ```no_build,no_run
+/// AES KEY = FIXED KEY (LE) | ENCRYPTION KEY (LE)
+/// ```
+///
+/// Also owns the [SequenceCount] of encrypted packets. The count is incremented each time a
+/// packet is encrypted or decrypted with the session.
+///
+/// Each device needs its own session, so a host can talk to multiple encrypted devices at once,
+/// e.g. a payout unit and a coin hopper in the same cabinet.
+#[derive(Clone, PartialEq)]
+pub struct EsspSession {
+    fixed_key: FixedKey,
+    encryption_key: EncryptionKey,
+    key: AesKey,
+    count: SequenceCount,
+}
+
+impl EsspSession {
+    /// Creates a new [EsspSession] from the negotiated [EncryptionKey], and the
+    /// [DEFAULT_FIXED_KEY](crate::DEFAULT_FIXED_KEY).
+    pub fn new(encryption_key: EncryptionKey) -> Self {
+        Self::from_keys(FixedKey::new(), encryption_key)
+    }
+
+    /// Creates a new [EsspSession] from a [FixedKey] and the negotiated [EncryptionKey].
+    pub fn from_keys(fixed_key: FixedKey, encryption_key: EncryptionKey) -> Self {
+        let mut key = AesKey::from(&fixed_key);
+        key[len::FIXED_KEY..].copy_from_slice(encryption_key.as_inner().to_le_bytes().as_ref());
+
+        Self {
+            fixed_key,
+            encryption_key,
+            key,
+            count: SequenceCount::new(),
+        }
+    }
+
+    /// Creates a new [EsspSession] from a full [AesKey].
+    pub fn from_aes_key(key: AesKey) -> Self {
+        let (fixed, encryption) = key.split_at(len::FIXED_KEY);
+
+        let mut fixed_key = [0u8; len::FIXED_KEY];
+        fixed_key.copy_from_slice(fixed);
+
+        let mut encryption_key = [0u8; mem::size_of::<u64>()];
+        encryption_key.copy_from_slice(encryption);
+
+        let session = Self {
+            fixed_key: u64::from_le_bytes(fixed_key).into(),
+            encryption_key: u64::from_le_bytes(encryption_key).into(),
+            key,
+            count: SequenceCount::new(),
+        };
+
+        fixed_key.zeroize();
+        encryption_key.zeroize();
+
+        session
+    }
+
+    /// Gets the [AesKey] used to encrypt and decrypt packets.
+    pub fn key(&self) -> &AesKey {
+        &self.key
+    }
+
+    /// Gets the [FixedKey] part of the [AesKey].
+    pub fn fixed_key(&self) -> &FixedKey {
+        &self.fixed_key
+    }
+
+    /// Gets the negotiated [EncryptionKey] part of the [AesKey].
+    pub fn encryption_key(&self) -> &EncryptionKey {
+        &self.encryption_key
+    }
+
+    /// Gets the current [SequenceCount].
+    pub fn count(&self) -> SequenceCount {
+        self.count
+    }
+
+    /// Sets the current [SequenceCount].
+    pub fn set_count(&mut self, count: SequenceCount) {
+        self.count = count;
+    }
+
+    /// Builder function that sets the current [SequenceCount].
+    pub fn with_count(mut self, count: SequenceCount) -> Self {
+        self.set_count(count);
+        self
+    }
+
+    /// Increments the [SequenceCount].
+    ///
+    /// Returns the new [SequenceCount].
+    pub fn increment_count(&mut self) -> SequenceCount {
+        self.count = self.count.as_inner().saturating_add(1).into();
+        self.count
+    }
+
+    /// Resets the [SequenceCount] to zero.
+    ///
+    /// Returns the [SequenceCount] value before the reset.
+    pub fn reset_count(&mut self) -> SequenceCount {
+        mem::take(&mut self.count)
+    }
+}
+
+impl fmt::Debug for EsspSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // leave the key material out of logs
+        f.debug_struct("EsspSession")
+            .field("count", &self.count)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Zeroize for EsspSession {
+    fn zeroize(&mut self) {
+        self.fixed_key.zeroize();
+        self.encryption_key.zeroize();
+        self.key.as_mut_slice().zeroize();
+        self.count = SequenceCount::new();
+    }
+}
+
+impl Drop for EsspSession {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for EsspSession {}
+
+/// Policy for recovering when a verified decryption fails, e.g. with
+/// [decrypt_verified](crate::EncryptedResponse::decrypt_verified).
+///
+/// A failed verification means a packet was replayed, or the [EsspSession] is out of sync with
+/// the device. Either way, the session can not be trusted anymore.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResyncPolicy {
+    /// Returns the error, and keeps the [EsspSession].
+    #[default]
+    Reject,
+    /// Returns the error, and negotiates new keys with the device, starting a new [EsspSession].
+    ///
+    /// If the negotiation fails, the old [EsspSession] is kept.
+    ///
+    /// Without the `std` feature, the [Device](crate::Device) needs a
+    /// [KeyNegotiator](crate::KeyNegotiator) to start the new session, see
+    /// [with_resync_negotiator](crate::Device::with_resync_negotiator).
+    Renegotiate,
+}
+
+impl ResyncPolicy {
+    /// Gets whether the policy starts a new [EsspSession] after the `err`.
+    ///
+    /// Only errors from a verified decryption start a new session.
+    pub fn should_resync(&self, err: &Error) -> bool {
+        matches!(self, Self::Renegotiate)
+            && matches!(
+                err,
+                Error::InvalidSequenceCount(_)
+                    | Error::EncryptedCrc(_)
+                    | Error::InvalidPackingLength(_)
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_keys() {
+        let encryption_key = EncryptionKey::from_inner(0x1aed_a1fb);
+
+        let session = EsspSession::new(encryption_key.clone());
+        assert_eq!(session.key(), &AesKey::from(encryption_key.clone()));
+        assert_eq!(session.fixed_key(), &FixedKey::new());
+        assert_eq!(session.encryption_key(), &encryption_key);
+
+        let aes_session = EsspSession::from_aes_key(*session.key());
+        assert_eq!(aes_session, session);
+    }
+
+    #[test]
+    fn test_session_count() {
+        let mut payout = EsspSession::new(EncryptionKey::from_inner(0x1234));
+        let mut hopper = EsspSession::new(EncryptionKey::from_inner(0x5678));
+
+        assert_eq!(payout.increment_count(), SequenceCount::from(1));
+        assert_eq!(payout.increment_count(), SequenceCount::from(2));
+
+        // sessions with different devices keep separate counts
+        assert_eq!(hopper.count(), SequenceCount::new());
+
+        assert_eq!(payout.reset_count(), SequenceCount::from(2));
+        assert_eq!(payout.count(), SequenceCount::new());
+
+        hopper.set_count(u32::MAX.into());
+        assert_eq!(hopper.increment_count(), SequenceCount::from(u32::MAX));
+    }
+
+    #[test]
+    fn test_session_zeroize() {
+        let mut session =
+            EsspSession::new(EncryptionKey::from_inner(0x1aed_a1fb)).with_count(3.into());
+
+        session.zeroize();
+
+        assert_eq!(session.key(), &AesKey::default());
+        assert_eq!(session.fixed_key(), &FixedKey::from_inner(0));
+        assert_eq!(session.encryption_key(), &EncryptionKey::from_inner(0));
+        assert_eq!(session.count(), SequenceCount::new());
+    }
+}
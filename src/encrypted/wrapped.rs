@@ -28,8 +28,7 @@ impl WrappedEncryptedMessage {
     }
 
     pub fn is_encrypted(&self) -> bool {
-        use crate::message::STEX;
-        self.data()[0] == STEX
+        self.data()[0] == u8::from(MessageType::Encrypted)
     }
 
     /// Gets the wrapped encrypted data.
@@ -15,18 +15,27 @@ pub enum Error {
     Generic(i64),
     Aes(String),
     Crc((u16, u16)),
+    EncryptedCrc((u16, u16)),
     Encryption(ResponseStatus),
     InvalidBarcodeCharacters(u8),
     InvalidDataLength((usize, usize)),
     InvalidInhibitChannels,
     InvalidLength((usize, usize)),
     InvalidEvent((ResponseStatus, ResponseStatus)),
+    InvalidGenerator((u64, u64)),
+    InvalidIntermediateKey(u64),
     InvalidMessage(MessageType),
     InvalidMessageRaw((MessageType, u8)),
+    InvalidPackingLength((usize, usize)),
+    InvalidPrime(u64),
+    InvalidSequenceCount((u32, u32)),
     InvalidStatus((ResponseStatus, ResponseStatus)),
     InvalidSTX(u8),
     PollingReinit,
     QueueTimeout,
+    Resync(usize),
+    Stuffing((usize, usize)),
+    UnencryptedResponse(MessageType),
     #[cfg(feature = "std")]
     Io(String),
     #[cfg(feature = "std")]
@@ -48,6 +57,12 @@ impl fmt::Display for Error {
             Error::Crc((have, exp)) => {
                 write!(f, "Bad CRC-16, have: 0x{have:04x}, expected: 0x{exp:04x}")
             }
+            Error::EncryptedCrc((have, exp)) => {
+                write!(
+                    f,
+                    "Bad encrypted CRC-16, have: 0x{have:04x}, expected: 0x{exp:04x}"
+                )
+            }
             Error::Encryption(err) => write!(f, "Error sending encrypted message: {err}"),
             Error::Generic(err) => write!(f, "Generic: {err}"),
             Error::InvalidBarcodeCharacters(num) => {
@@ -65,6 +80,15 @@ impl fmt::Display for Error {
             Error::InvalidEvent((have, exp)) => {
                 write!(f, "Invalid device event, have: {have}, expected: {exp}")
             }
+            Error::InvalidGenerator((gen, modulus)) => {
+                write!(
+                    f,
+                    "Invalid eSSP generator: {gen}, must be larger than the modulus: {modulus}"
+                )
+            }
+            Error::InvalidIntermediateKey(key) => {
+                write!(f, "Invalid eSSP intermediate key: 0x{key:x}")
+            }
             Error::InvalidInhibitChannels => {
                 write!(f, "Trying to set an invalid number of inhibit channels")
             }
@@ -75,6 +99,16 @@ impl fmt::Display for Error {
             Error::InvalidMessageRaw((msg, raw)) => {
                 write!(f, "Invalid message type: {msg}, raw type: 0x{raw:02x}")
             }
+            Error::InvalidPackingLength((have, exp)) => {
+                write!(
+                    f,
+                    "Invalid encrypted packing length, have: {have}, expected: {exp}"
+                )
+            }
+            Error::InvalidPrime(num) => write!(f, "Invalid eSSP key, {num} is not prime"),
+            Error::InvalidSequenceCount((have, exp)) => {
+                write!(f, "Invalid encryption sequence count, have: {have}, expected: {exp}, possible replayed packet or out-of-sync session")
+            }
             Error::InvalidStatus((have, exp)) => {
                 write!(f, "Invalid response status, have: {have}, expected: {exp}")
             }
@@ -87,6 +121,19 @@ impl fmt::Display for Error {
             Error::QueueTimeout => {
                 write!(f, "Failed to retrieve a queued event before timeout")
             }
+            Error::Resync(discarded) => {
+                write!(f, "Frame resynchronization, discarded {discarded} byte(s)")
+            }
+            Error::Stuffing((have, exp)) => {
+                write!(
+                    f,
+                    "Invalid encrypted byte stuffing, have length: {have}, expected: {exp}"
+                )
+            }
+            Error::UnencryptedResponse(msg) => write!(
+                f,
+                "Unencrypted response to {msg} command during an encrypted session"
+            ),
             #[cfg(feature = "std")]
             Error::Io(err) => write!(f, "I/O error: {err}"),
             #[cfg(feature = "std")]
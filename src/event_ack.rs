@@ -3,6 +3,8 @@
 
 mod command;
 mod response;
+mod tracker;
 
 pub use command::*;
 pub use response::*;
+pub use tracker::*;
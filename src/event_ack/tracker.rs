@@ -0,0 +1,152 @@
+use crate::{PollEventIter, PollWithAckResponse, ProtocolVersion, Vec};
+
+/// Tracks events processed by the host, but not yet acknowledged with an
+/// [EventAckCommand](crate::EventAckCommand).
+///
+/// When polling with [PollWithAckCommand](crate::PollWithAckCommand), the device repeats events
+/// requiring an ACK until it receives an [EventAckCommand](crate::EventAckCommand). If the ACK is
+/// lost, the next poll will report the same events again. The tracker filters these repeats, so
+/// events (e.g. credits) are only processed once.
+///
+/// The intended flow:
+///
+/// - poll the device, and get the unprocessed events with [new_events](Self::new_events)
+/// - durably process the events
+/// - mark the events as processed with [processed](Self::processed)
+/// - send the [EventAckCommand](crate::EventAckCommand)
+/// - clear the pending events with [acknowledged](Self::acknowledged)
+///
+/// [Device::poll_with_ack_events](crate::Device::poll_with_ack_events) implements this flow.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventAckTracker {
+    pending: Vec<u8>,
+}
+
+impl EventAckTracker {
+    /// Creates a new [EventAckTracker].
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+        }
+    }
+
+    /// Gets whether there are processed events waiting for an ACK.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Gets the raw data of processed events waiting for an ACK.
+    pub fn pending(&self) -> &[u8] {
+        self.pending.as_ref()
+    }
+
+    /// Gets an iterator over the events in `response` not yet processed by the host.
+    ///
+    /// Events repeated from the pending (processed, but un-ACKed) events are skipped. Only events
+    /// at the start of `response` that repeat the end of the pending events are skipped, the device
+    /// does not perform further note actions until the ACK is received.
+    pub fn new_events<'a>(
+        &self,
+        response: &'a PollWithAckResponse,
+        protocol: ProtocolVersion,
+    ) -> PollEventIter<'a> {
+        let data = response.event_data();
+        let repeated = self.repeated_len(data, protocol);
+
+        PollEventIter::new(&data[repeated..], protocol)
+    }
+
+    /// Marks the events in `response` as processed by the host, and waiting for an ACK.
+    pub fn processed(&mut self, response: &PollWithAckResponse) {
+        self.pending.clear();
+        // event data is bounded by the maximum data length, so this can not fail
+        self.pending.extend_from_slice(response.event_data()).ok();
+    }
+
+    /// Clears the pending events after the device has acknowledged the
+    /// [EventAckCommand](crate::EventAckCommand).
+    pub fn acknowledged(&mut self) {
+        self.pending.clear();
+    }
+
+    // Gets the length of the longest run of pending events repeated at the start of `data`.
+    fn repeated_len(&self, data: &[u8], protocol: ProtocolVersion) -> usize {
+        let pending = self.pending.as_slice();
+        let mut iter = PollEventIter::new(pending, protocol);
+
+        loop {
+            let rest = iter.remaining();
+
+            if rest.is_empty() {
+                return 0;
+            } else if data.starts_with(rest) {
+                return rest.len();
+            }
+
+            match iter.next() {
+                Some(Ok(_)) => (),
+                _ => return 0,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{message::index, EventPayload, MessageOps, ResponseOps, ResponseStatus, Result};
+
+    fn poll_response(events: &[u8]) -> PollWithAckResponse {
+        let mut res = PollWithAckResponse::new();
+
+        res.set_data_len((events.len() + 1) as u8);
+        res.set_response_status(ResponseStatus::Ok);
+        res.buf_mut()[index::DATA + 1..index::DATA + 1 + events.len()].copy_from_slice(events);
+
+        res
+    }
+
+    #[test]
+    fn test_event_ack_tracker() -> Result<()> {
+        let credit = [ResponseStatus::NoteCredit.to_u8(), 0x01];
+        let read = [ResponseStatus::Read.to_u8(), 0x00];
+        let stacked = [ResponseStatus::Stacked.to_u8()];
+
+        let mut tracker = EventAckTracker::new();
+        let first = poll_response([read, credit].concat().as_ref());
+
+        assert_eq!(
+            tracker
+                .new_events(&first, ProtocolVersion::Six)
+                .collect::<Result<alloc::vec::Vec<EventPayload>>>()?
+                .len(),
+            2
+        );
+
+        tracker.processed(&first);
+        assert!(tracker.is_pending());
+
+        // ACK was lost, the device repeats the credit, followed by a new event
+        let repeat = poll_response([credit.as_ref(), stacked.as_ref()].concat().as_ref());
+        let events = tracker
+            .new_events(&repeat, ProtocolVersion::Six)
+            .collect::<Result<alloc::vec::Vec<EventPayload>>>()?;
+
+        assert_eq!(events.len(), 1);
+        assert!(events[0].is_stacked_event());
+
+        // after the ACK, identical events are new events
+        tracker.acknowledged();
+        assert!(!tracker.is_pending());
+
+        assert_eq!(
+            tracker
+                .new_events(&first, ProtocolVersion::Six)
+                .collect::<Result<alloc::vec::Vec<EventPayload>>>()?
+                .len(),
+            2
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+//! Float the payout device to a monetary value, sending the remaining notes to the cashbox.
+
+mod command;
+mod response;
+
+pub use command::*;
+pub use response::*;
@@ -0,0 +1,175 @@
+use crate::{
+    impl_command_ops, impl_default, impl_message_from_buf, impl_message_ops, len, std::fmt,
+    CommandOps, CountryCode, MessageOps, MessageType, PayoutOption,
+};
+
+mod index {
+    pub const MIN_PAYOUT: usize = 4;
+    pub const MIN_PAYOUT_END: usize = 8;
+    pub const AMOUNT: usize = 8;
+    pub const AMOUNT_END: usize = 12;
+    pub const CURRENCY: usize = 12;
+    pub const CURRENCY_END: usize = 15;
+    pub const PAYOUT_OPTION: usize = 15;
+}
+
+/// FloatAmount - Command (0x3D)
+///
+/// A command to float the payout unit to leave a requested value of money, with a requested
+/// minimum possible payout level. All monies not required to meet float value are routed to
+/// cashbox.
+///
+/// The minimum payout, and float values are sent as 4 byte little endian integers of the full
+/// penny amount, followed by the 3 byte ASCII country code of the currency to float.
+///
+/// The host also adds an option byte to the end of the command array ([`PayoutOption`]).
+/// This will allow a pre-test of the ability to float the requested amount before actual float
+/// executes.
+///
+/// ***Uses the SMART Payout command format for Protocol Version 6 or above.***
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatAmountCommand {
+    buf: [u8; len::FLOAT_AMOUNT_COMMAND],
+}
+
+impl FloatAmountCommand {
+    /// Creates a new [FloatAmountCommand] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::FLOAT_AMOUNT_COMMAND],
+        };
+
+        msg.init();
+        msg.set_command(MessageType::FloatAmount);
+        msg.set_payout_option(PayoutOption::default());
+
+        msg
+    }
+
+    /// Gets the minimum payout that should remain available after the float.
+    pub fn min_payout(&self) -> u32 {
+        u32::from_le_bytes(
+            self.buf[index::MIN_PAYOUT..index::MIN_PAYOUT_END]
+                .try_into()
+                .unwrap_or([0; 4]),
+        )
+    }
+
+    /// Sets the minimum payout that should remain available after the float.
+    pub fn set_min_payout(&mut self, min_payout: u32) {
+        self.buf[index::MIN_PAYOUT..index::MIN_PAYOUT_END]
+            .copy_from_slice(min_payout.to_le_bytes().as_ref());
+    }
+
+    /// Builder function that sets the minimum payout that should remain available after the float.
+    pub fn with_min_payout(mut self, min_payout: u32) -> Self {
+        self.set_min_payout(min_payout);
+        self
+    }
+
+    /// Gets the amount to leave in the payout device.
+    pub fn amount(&self) -> u32 {
+        u32::from_le_bytes(
+            self.buf[index::AMOUNT..index::AMOUNT_END]
+                .try_into()
+                .unwrap_or([0; 4]),
+        )
+    }
+
+    /// Sets the amount to leave in the payout device.
+    pub fn set_amount(&mut self, amount: u32) {
+        self.buf[index::AMOUNT..index::AMOUNT_END].copy_from_slice(amount.to_le_bytes().as_ref());
+    }
+
+    /// Builder function that sets the amount to leave in the payout device.
+    pub fn with_amount(mut self, amount: u32) -> Self {
+        self.set_amount(amount);
+        self
+    }
+
+    /// Gets the [CountryCode] of the currency to float.
+    pub fn currency(&self) -> CountryCode {
+        CountryCode::from(&self.buf[index::CURRENCY..index::CURRENCY_END])
+    }
+
+    /// Sets the [CountryCode] of the currency to float.
+    pub fn set_currency(&mut self, currency: CountryCode) {
+        self.buf[index::CURRENCY..index::CURRENCY_END]
+            .copy_from_slice(<&str>::from(currency).as_bytes());
+    }
+
+    /// Builder function that sets the [CountryCode] of the currency to float.
+    pub fn with_currency(mut self, currency: CountryCode) -> Self {
+        self.set_currency(currency);
+        self
+    }
+
+    /// Gets the [PayoutOption].
+    pub fn payout_option(&self) -> PayoutOption {
+        self.buf[index::PAYOUT_OPTION].into()
+    }
+
+    /// Sets the [PayoutOption].
+    pub fn set_payout_option(&mut self, option: PayoutOption) {
+        self.buf[index::PAYOUT_OPTION] = option.into();
+    }
+
+    /// Builder function that sets the [PayoutOption].
+    pub fn with_payout_option(mut self, option: PayoutOption) -> Self {
+        self.set_payout_option(option);
+        self
+    }
+}
+
+impl_default!(FloatAmountCommand);
+impl_message_from_buf!(FloatAmountCommand);
+impl_message_ops!(FloatAmountCommand);
+impl_command_ops!(FloatAmountCommand);
+
+impl fmt::Display for FloatAmountCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stx = self.stx();
+        let seqid = self.sequence_id();
+        let len = self.data_len();
+        let cmd = self.command();
+        let min_payout = self.min_payout();
+        let amount = self.amount();
+        let currency = self.currency();
+        let option = self.payout_option();
+        let crc = self.checksum();
+
+        write!(f, "STX: 0x{stx:02x} | SEQID: {seqid} | LEN: 0x{len:02x} | Command: {cmd} | Minimum payout: {min_payout} | Amount: {amount} | Currency: {currency} | Payout option: {option} | CRC-16: 0x{crc:04x}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Result;
+
+    #[test]
+    fn test_float_amount_command() -> Result<()> {
+        let mut cmd = FloatAmountCommand::new()
+            .with_min_payout(500)
+            .with_amount(10_000)
+            .with_currency(CountryCode::EUR)
+            .with_payout_option(PayoutOption::TestPayoutAmount);
+        cmd.calculate_checksum();
+
+        assert_eq!(cmd.data_len(), 13);
+        assert_eq!(
+            cmd.data(),
+            [0x3d, 0xf4, 0x01, 0x00, 0x00, 0x10, 0x27, 0x00, 0x00, 0x45, 0x55, 0x52, 0x19].as_ref()
+        );
+
+        assert_eq!(cmd.min_payout(), 500);
+        assert_eq!(cmd.amount(), 10_000);
+        assert_eq!(cmd.currency(), CountryCode::EUR);
+        assert_eq!(cmd.payout_option(), PayoutOption::TestPayoutAmount);
+
+        assert_eq!(FloatAmountCommand::try_from(cmd.buf())?, cmd);
+
+        Ok(())
+    }
+}
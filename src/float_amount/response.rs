@@ -0,0 +1,32 @@
+use crate::{
+    impl_default, impl_message_from_buf, impl_message_ops, impl_response_display,
+    impl_response_ops, len, MessageOps, MessageType,
+};
+
+/// FloatAmount - Response (0x3D)
+///
+/// Represents a response to an [FloatAmountCommand](crate::FloatAmountCommand) message.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatAmountResponse {
+    buf: [u8; len::FLOAT_AMOUNT_RESPONSE],
+}
+
+impl FloatAmountResponse {
+    /// Creates a new [FloatAmountResponse] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::FLOAT_AMOUNT_RESPONSE],
+        };
+
+        msg.init();
+
+        msg
+    }
+}
+
+impl_default!(FloatAmountResponse);
+impl_message_from_buf!(FloatAmountResponse);
+impl_message_ops!(FloatAmountResponse, MessageType::FloatAmount);
+impl_response_ops!(FloatAmountResponse);
+impl_response_display!(FloatAmountResponse);
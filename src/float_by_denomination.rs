@@ -0,0 +1,7 @@
+//! Float the payout device to the requested levels of individual denominations.
+
+mod command;
+mod response;
+
+pub use command::*;
+pub use response::*;
@@ -0,0 +1,35 @@
+use crate::{
+    impl_default, impl_message_from_buf, impl_message_ops, impl_response_display,
+    impl_response_ops, len, MessageOps, MessageType,
+};
+
+/// FloatByDenomination - Response (0x44)
+///
+/// Represents a response to an [FloatByDenominationCommand](crate::FloatByDenominationCommand) message.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatByDenominationResponse {
+    buf: [u8; len::FLOAT_BY_DENOMINATION_RESPONSE],
+}
+
+impl FloatByDenominationResponse {
+    /// Creates a new [FloatByDenominationResponse] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::FLOAT_BY_DENOMINATION_RESPONSE],
+        };
+
+        msg.init();
+
+        msg
+    }
+}
+
+impl_default!(FloatByDenominationResponse);
+impl_message_from_buf!(FloatByDenominationResponse);
+impl_message_ops!(
+    FloatByDenominationResponse,
+    MessageType::FloatByDenomination
+);
+impl_response_ops!(FloatByDenominationResponse);
+impl_response_display!(FloatByDenominationResponse);
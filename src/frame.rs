@@ -0,0 +1,444 @@
+//! Streaming encoder and decoder for the byte-stuffed SSP wire format.
+//!
+//! From the SSP Protocol Manual:
+//!
+//! ```no_build,no_run
+//! If 0x7F (STX) appears in the data to be transmitted, then it should be replaced by 0x7F, 0x7F.
+//! ```
+//!
+//! Both the [FrameEncoder] and [FrameDecoder] work on fixed-size buffers, and are usable in
+//! `no_std` environments, e.g. driving a UART from an interrupt handler.
+
+use heapless::Deque;
+
+use crate::{
+    crc::crc16, len, message::index, Error, MessageOps, MessageType, MessageVariant, Result, STX,
+};
+
+/// Maximum number of decoded frames (and errors) the [FrameDecoder] will queue.
+pub const FRAME_QUEUE_LEN: usize = 4;
+
+/// Applies SSP byte stuffing to a message frame.
+///
+/// Any `STX` byte after the first is repeated to indicate it is not the start of a new packet.
+///
+/// Returns the length of the stuffed frame written to `out`.
+pub fn stuff_frame(frame: &[u8], out: &mut [u8]) -> Result<usize> {
+    if frame.is_empty() {
+        return Ok(0);
+    }
+
+    let out_len = out.len();
+    let mut idx = 0;
+
+    for (i, &byte) in frame.iter().enumerate() {
+        let stuffed = i != 0 && byte == STX;
+        let need = if stuffed { 2 } else { 1 };
+
+        if idx + need > out_len {
+            return Err(Error::InvalidLength((idx + need, out_len)));
+        }
+
+        out[idx] = byte;
+        idx += 1;
+
+        if stuffed {
+            out[idx] = STX;
+            idx += 1;
+        }
+    }
+
+    Ok(idx)
+}
+
+/// Complete, un-stuffed SSP message frame.
+///
+/// Contains the full message from `STX` through the CRC-16 checksum.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    buf: [u8; len::MAX_MESSAGE],
+    len: usize,
+}
+
+impl Frame {
+    /// Gets the frame bytes, including the `STX` byte and CRC-16 checksum.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf[..self.len].as_ref()
+    }
+
+    /// Gets the full length of the frame.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Gets whether the frame is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets the `SEQID` byte of the frame.
+    pub fn seq_id(&self) -> u8 {
+        self.buf[index::SEQ_ID]
+    }
+
+    /// Gets the data field of the frame.
+    pub fn data(&self) -> &[u8] {
+        self.buf[index::DATA..self.len - len::FOOTER].as_ref()
+    }
+
+    /// Parses the frame into a response [MessageVariant].
+    ///
+    /// The `command` type is needed to infer the type of response message.
+    pub fn to_variant(&self, command: MessageType) -> Result<MessageVariant> {
+        MessageVariant::from_buf(self.as_bytes(), command)
+    }
+}
+
+impl Default for Frame {
+    fn default() -> Self {
+        Self {
+            buf: [0u8; len::MAX_MESSAGE],
+            len: 0,
+        }
+    }
+}
+
+/// Encodes message frames into the byte-stuffed wire format.
+pub struct FrameEncoder {
+    buf: [u8; len::MAX_STUFFED_MESSAGE],
+}
+
+impl FrameEncoder {
+    /// Creates a new [FrameEncoder].
+    pub const fn new() -> Self {
+        Self {
+            buf: [0u8; len::MAX_STUFFED_MESSAGE],
+        }
+    }
+
+    /// Encodes an un-stuffed message frame.
+    ///
+    /// Returns the stuffed bytes ready for transmission.
+    pub fn encode(&mut self, frame: &[u8]) -> Result<&[u8]> {
+        match frame.first() {
+            Some(&STX) => (),
+            Some(&stx) => return Err(Error::InvalidSTX(stx)),
+            None => return Err(Error::InvalidLength((0, len::METADATA))),
+        }
+
+        let stuffed_len = stuff_frame(frame, self.buf.as_mut())?;
+
+        Ok(self.buf[..stuffed_len].as_ref())
+    }
+
+    /// Encodes a message, calculating the CRC-16 checksum before stuffing.
+    ///
+    /// Returns the stuffed bytes ready for transmission.
+    pub fn encode_message(&mut self, message: &mut dyn MessageOps) -> Result<&[u8]> {
+        self.encode(message.as_bytes())
+    }
+}
+
+impl Default for FrameEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resumable decoder for byte-stuffed message frames.
+///
+/// Bytes are pushed into the decoder as they arrive, in chunks of any size. Complete frames, and
+/// any errors encountered while decoding, are queued in arrival order to be popped by the caller.
+///
+/// The decoder reports:
+///
+/// - [Error::Resync] when bytes are discarded searching for the start of a frame, or when a
+///   partial frame is interrupted by a new `STX`
+/// - [Error::Crc] when a complete frame fails CRC-16 validation
+pub struct FrameDecoder {
+    frame: Frame,
+    frame_len: usize,
+    in_frame: bool,
+    escape: bool,
+    discarded: usize,
+    queue: Deque<Result<Frame>, FRAME_QUEUE_LEN>,
+}
+
+impl FrameDecoder {
+    /// Creates a new [FrameDecoder].
+    pub fn new() -> Self {
+        Self {
+            frame: Frame::default(),
+            frame_len: len::METADATA,
+            in_frame: false,
+            escape: false,
+            discarded: 0,
+            queue: Deque::new(),
+        }
+    }
+
+    /// Pushes bytes into the decoder.
+    ///
+    /// Returns the number of bytes consumed. Decoding stops early when the queue is full, and the
+    /// remaining bytes should be pushed again after popping frames with
+    /// [pop_frame](Self::pop_frame).
+    pub fn push_bytes(&mut self, bytes: &[u8]) -> usize {
+        let mut consumed = 0;
+
+        for &byte in bytes.iter() {
+            if self.queue.is_full() {
+                break;
+            }
+
+            self.push_byte(byte);
+            consumed += 1;
+        }
+
+        consumed
+    }
+
+    /// Pops the next decoded frame, or decoding error, from the queue.
+    pub fn pop_frame(&mut self) -> Option<Result<Frame>> {
+        self.queue.pop_front()
+    }
+
+    /// Gets whether the decoder has queued frames, or decoding errors.
+    pub fn has_frames(&self) -> bool {
+        !self.queue.is_empty()
+    }
+
+    /// Gets whether the decoder is in the middle of decoding a frame.
+    pub fn in_frame(&self) -> bool {
+        self.in_frame
+    }
+
+    /// Resets the decoder, discarding any partial frame, and queued frames.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn push_byte(&mut self, byte: u8) {
+        if self.escape {
+            self.escape = false;
+
+            if byte == STX {
+                self.store(STX);
+            } else {
+                // un-stuffed STX is the start of a new frame
+                self.enqueue(Err(Error::Resync(self.frame.len)));
+                self.start_frame();
+                self.store(byte);
+            }
+        } else if !self.in_frame {
+            if byte == STX {
+                self.start_frame();
+            } else {
+                self.discarded += 1;
+            }
+        } else if byte == STX {
+            if self.frame.len == index::SEQ_ID {
+                // SEQID is never STX, so the previous STX was noise
+                self.discarded += 1;
+                self.start_frame();
+            } else {
+                self.escape = true;
+            }
+        } else {
+            self.store(byte);
+        }
+    }
+
+    fn start_frame(&mut self) {
+        if self.discarded != 0 {
+            let discarded = self.discarded;
+            self.enqueue(Err(Error::Resync(discarded)));
+            self.discarded = 0;
+        }
+
+        self.frame.buf[index::STX] = STX;
+        self.frame.len = index::SEQ_ID;
+        self.frame_len = len::METADATA;
+        self.in_frame = true;
+    }
+
+    fn store(&mut self, byte: u8) {
+        let idx = self.frame.len;
+
+        self.frame.buf[idx] = byte;
+        self.frame.len += 1;
+
+        if idx == index::LEN {
+            self.frame_len = len::METADATA + byte as usize;
+        }
+
+        if self.frame.len == self.frame_len {
+            self.finish_frame();
+        }
+    }
+
+    fn finish_frame(&mut self) {
+        let frame_len = self.frame_len;
+        let buf = self.frame.buf.as_ref();
+
+        let crc = u16::from_le_bytes([buf[frame_len - 2], buf[frame_len - 1]]);
+        let exp_crc = crc16(buf[index::SEQ_ID..frame_len - 2].as_ref());
+
+        let res = if crc == exp_crc {
+            Ok(self.frame.clone())
+        } else {
+            Err(Error::Crc((crc, exp_crc)))
+        };
+
+        self.enqueue(res);
+
+        self.frame.len = 0;
+        self.in_frame = false;
+    }
+
+    fn enqueue(&mut self, res: Result<Frame>) {
+        if self.queue.push_back(res).is_err() {
+            log::warn!("Frame queue is full, dropping decoded frame");
+        }
+    }
+}
+
+impl Default for FrameDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SyncCommand;
+
+    fn stuffed_frame() -> ([u8; 7], [u8; 8]) {
+        // SEQID, LEN, DATA (0x7f 0xaa), CRC
+        let mut frame = [STX, 0x80, 0x02, 0x7f, 0xaa, 0x00, 0x00];
+        let crc = crc16(frame[1..5].as_ref()).to_le_bytes();
+        frame[5..].copy_from_slice(crc.as_ref());
+
+        let mut stuffed = [0u8; 8];
+        assert_eq!(stuff_frame(frame.as_ref(), stuffed.as_mut()), Ok(8));
+
+        (frame, stuffed)
+    }
+
+    #[test]
+    fn test_stuff_frame() -> Result<()> {
+        let frame = [STX, 0x80, 0x01, 0x7f, 0xaa, 0x7f];
+        let exp = [STX, 0x80, 0x01, 0x7f, 0x7f, 0xaa, 0x7f, 0x7f];
+
+        let mut out = [0u8; 8];
+        let out_len = stuff_frame(frame.as_ref(), out.as_mut())?;
+
+        assert_eq!(out_len, exp.len());
+        assert_eq!(out, exp);
+
+        let mut short = [0u8; 7];
+        assert!(stuff_frame(frame.as_ref(), short.as_mut()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_frame_encoder() -> Result<()> {
+        let (frame, stuffed) = stuffed_frame();
+
+        let mut encoder = FrameEncoder::new();
+
+        assert_eq!(encoder.encode(frame.as_ref())?, stuffed.as_ref());
+        assert_eq!(
+            encoder.encode([0x00, 0x80].as_ref()),
+            Err(Error::InvalidSTX(0x00))
+        );
+
+        let mut msg = SyncCommand::new();
+        let exp = msg.as_bytes().to_vec();
+
+        assert_eq!(encoder.encode_message(&mut msg)?, exp.as_slice());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_frame_decoder_split() -> Result<()> {
+        let (frame, stuffed) = stuffed_frame();
+
+        let mut decoder = FrameDecoder::new();
+
+        // split the stream in the middle of the stuffed STX
+        assert_eq!(decoder.push_bytes(stuffed[..4].as_ref()), 4);
+        assert!(decoder.in_frame());
+        assert!(decoder.pop_frame().is_none());
+
+        assert_eq!(decoder.push_bytes(stuffed[4..].as_ref()), 4);
+
+        let decoded = decoder.pop_frame().unwrap()?;
+
+        assert_eq!(decoded.as_bytes(), frame.as_ref());
+        assert_eq!(decoded.seq_id(), 0x80);
+        assert_eq!(decoded.data(), [0x7f, 0xaa].as_ref());
+        assert!(decoder.pop_frame().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_frame_decoder_resync() -> Result<()> {
+        let (frame, stuffed) = stuffed_frame();
+
+        let mut decoder = FrameDecoder::new();
+
+        // leading noise
+        decoder.push_bytes([0x00, 0x11, 0x22].as_ref());
+        // partial frame interrupted by a new STX
+        decoder.push_bytes([STX, 0x80, 0x04, 0x01].as_ref());
+        decoder.push_bytes(stuffed.as_ref());
+
+        assert_eq!(decoder.pop_frame(), Some(Err(Error::Resync(3))));
+        assert_eq!(decoder.pop_frame(), Some(Err(Error::Resync(4))));
+        assert_eq!(decoder.pop_frame().unwrap()?.as_bytes(), frame.as_ref());
+        assert!(decoder.pop_frame().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_frame_decoder_crc() {
+        let (_, mut stuffed) = stuffed_frame();
+        stuffed[7] ^= 0xff;
+
+        let mut decoder = FrameDecoder::new();
+        decoder.push_bytes(stuffed.as_ref());
+
+        assert!(matches!(decoder.pop_frame(), Some(Err(Error::Crc(_)))));
+        assert!(!decoder.in_frame());
+    }
+
+    #[test]
+    fn test_frame_decoder_full_queue() -> Result<()> {
+        let mut msg = SyncCommand::new();
+        let bytes = msg.as_bytes().to_vec();
+
+        let mut stream = [0u8; 6 * 5];
+        for chunk in stream.chunks_exact_mut(bytes.len()) {
+            chunk.copy_from_slice(bytes.as_slice());
+        }
+
+        let mut decoder = FrameDecoder::new();
+
+        let consumed = decoder.push_bytes(stream.as_ref());
+        assert_eq!(consumed, FRAME_QUEUE_LEN * bytes.len());
+
+        while let Some(res) = decoder.pop_frame() {
+            assert_eq!(res?.as_bytes(), bytes.as_slice());
+        }
+
+        assert_eq!(decoder.push_bytes(stream[consumed..].as_ref()), bytes.len());
+        assert!(decoder.pop_frame().is_some());
+
+        Ok(())
+    }
+}
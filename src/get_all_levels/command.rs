@@ -0,0 +1,34 @@
+use crate::{
+    impl_command_display, impl_command_ops, impl_default, impl_message_from_buf, impl_message_ops,
+    len, CommandOps, MessageOps, MessageType,
+};
+
+/// GetAllLevels - Command (0x22)
+///
+/// Single byte command that causes the payout device to report the number of notes stored for
+/// all denominations.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GetAllLevelsCommand {
+    buf: [u8; len::GET_ALL_LEVELS_COMMAND],
+}
+
+impl GetAllLevelsCommand {
+    /// Creates a new [GetAllLevelsCommand] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::GET_ALL_LEVELS_COMMAND],
+        };
+
+        msg.init();
+        msg.set_command(MessageType::GetAllLevels);
+
+        msg
+    }
+}
+
+impl_default!(GetAllLevelsCommand);
+impl_command_display!(GetAllLevelsCommand);
+impl_message_from_buf!(GetAllLevelsCommand);
+impl_message_ops!(GetAllLevelsCommand);
+impl_command_ops!(GetAllLevelsCommand);
@@ -0,0 +1,148 @@
+use crate::{
+    impl_default, impl_message_from_buf, impl_response_ops, impl_var_message_ops, len, std::fmt,
+    DenominationLevel, DenominationLevelList, Error, LevelVec, MessageOps, MessageType,
+    ResponseOps, Result, MAX_LEVELS,
+};
+
+mod index {
+    pub const NUM_LEVELS: usize = 4;
+    pub const LEVEL_BLOCKS: usize = 5;
+}
+
+/// GetAllLevels - Response (0x22)
+///
+/// Represents a response to an [GetAllLevelsCommand](crate::GetAllLevelsCommand) message.
+///
+/// Contains the number of denominations, followed by a [DenominationLevel] block for each
+/// denomination: the 2 byte little endian count of notes stored, the 4 byte little endian value,
+/// and the 3 byte ASCII country code.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GetAllLevelsResponse {
+    buf: [u8; len::GET_ALL_LEVELS_RESPONSE],
+}
+
+impl GetAllLevelsResponse {
+    /// Creates a new [GetAllLevelsResponse] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::GET_ALL_LEVELS_RESPONSE],
+        };
+
+        msg.init();
+
+        msg
+    }
+
+    /// Gets the number of [DenominationLevel] blocks in the response.
+    pub fn number_of_levels(&self) -> usize {
+        self.buf[index::NUM_LEVELS].into()
+    }
+
+    /// Gets the list of [DenominationLevel]s in the response.
+    pub fn levels(&self) -> Result<DenominationLevelList> {
+        let num = self.number_of_levels();
+        let start = index::LEVEL_BLOCKS;
+        let end = start + (num * len::LEVEL_BLOCK);
+        let data_end = len::HEADER + self.data_len();
+
+        if num > MAX_LEVELS {
+            Err(Error::InvalidLength((num, MAX_LEVELS)))
+        } else if end > data_end {
+            Err(Error::InvalidLength((data_end, end)))
+        } else {
+            let mut list = LevelVec::new();
+
+            for block in self.buf[start..end].chunks_exact(len::LEVEL_BLOCK) {
+                // `push` only fails if `heapless::Vec` is full
+                // since we checked valid range above, `push` will not fail
+                list.push(DenominationLevel::try_from(block)?).ok();
+            }
+
+            Ok(list.into())
+        }
+    }
+
+    /// Sets the list of [DenominationLevel]s in the response.
+    pub fn set_levels(&mut self, list: &DenominationLevelList) {
+        // length is guaranteed valid because of type constraints
+        let num = list.len();
+        let start = index::LEVEL_BLOCKS;
+        let end = start + (num * len::LEVEL_BLOCK);
+
+        self.buf[index::NUM_LEVELS] = num as u8;
+        self.set_data_len((num * len::LEVEL_BLOCK + 2) as u8);
+
+        for (chunk, level) in self.buf[start..end]
+            .chunks_exact_mut(len::LEVEL_BLOCK)
+            .zip(list.iter())
+        {
+            // `to_buffer` will not fail because the chunk length is valid
+            level.to_buffer(chunk).ok();
+        }
+    }
+}
+
+impl_default!(GetAllLevelsResponse);
+impl_message_from_buf!(GetAllLevelsResponse);
+impl_var_message_ops!(GetAllLevelsResponse, MessageType::GetAllLevels);
+impl_response_ops!(GetAllLevelsResponse);
+
+impl fmt::Display for GetAllLevelsResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stx = self.stx();
+        let seqid = self.sequence_id();
+        let len = self.data_len();
+        let status = self.response_status();
+        let levels = self.levels().unwrap_or_default();
+        let crc = self.checksum();
+
+        write!(f, "STX: 0x{stx:02x} | SEQID: {seqid} | LEN: 0x{len:02x} | Response status: {status} | Levels: {levels} | CRC-16: 0x{crc:04x}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChannelValue, CountryCode, ResponseStatus};
+
+    #[test]
+    fn test_get_all_levels_response() -> Result<()> {
+        // Three stored EUR denominations, from the SSP Implementation Guide
+        let data = [
+            0xf0, 0x03, 0x05, 0x00, 0xf4, 0x01, 0x00, 0x00, 0x45, 0x55, 0x52, 0x0e, 0x00, 0xe8,
+            0x03, 0x00, 0x00, 0x45, 0x55, 0x52, 0x05, 0x00, 0xd0, 0x07, 0x00, 0x00, 0x45, 0x55,
+            0x52,
+        ];
+
+        let mut buf = [0u8; len::METADATA + 29];
+        buf[0] = crate::STX;
+        buf[1] = 0x80;
+        buf[2] = data.len() as u8;
+        buf[3..3 + data.len()].copy_from_slice(data.as_ref());
+
+        let crc = crate::crc::crc16(buf[1..3 + data.len()].as_ref());
+        buf[3 + data.len()..].copy_from_slice(crc.to_le_bytes().as_ref());
+
+        let res = GetAllLevelsResponse::try_from(buf)?;
+
+        let exp_levels = DenominationLevelList::from([
+            DenominationLevel::create(5, ChannelValue::from(500), CountryCode::EUR),
+            DenominationLevel::create(14, ChannelValue::from(1000), CountryCode::EUR),
+            DenominationLevel::create(5, ChannelValue::from(2000), CountryCode::EUR),
+        ]);
+
+        assert_eq!(res.response_status(), ResponseStatus::Ok);
+        assert_eq!(res.number_of_levels(), 3);
+        assert_eq!(res.levels()?, exp_levels);
+
+        let mut set_res = GetAllLevelsResponse::new();
+        set_res.set_response_status(ResponseStatus::Ok);
+        set_res.set_levels(&exp_levels);
+
+        assert_eq!(set_res.levels()?, exp_levels);
+        assert_eq!(set_res.data(), data.as_ref());
+
+        Ok(())
+    }
+}
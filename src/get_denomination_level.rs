@@ -0,0 +1,7 @@
+//! Get the stored level of a denomination in the payout device.
+
+mod command;
+mod response;
+
+pub use command::*;
+pub use response::*;
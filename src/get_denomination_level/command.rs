@@ -0,0 +1,92 @@
+use crate::{
+    impl_command_ops, impl_default, impl_message_from_buf, impl_message_ops, len, std::fmt,
+    ChannelValue, CommandOps, CountryCode, MessageOps, MessageType,
+};
+
+mod index {
+    pub const VALUE: usize = 4;
+    pub const VALUE_END: usize = 8;
+    pub const CURRENCY: usize = 8;
+    pub const CURRENCY_END: usize = 11;
+}
+
+/// GetDenominationLevel - Command (0x35)
+///
+/// A command to request the level of a denomination stored in the payout device.
+///
+/// The value of the denomination is sent as a 4 byte little endian integer, followed by the 3 byte
+/// ASCII country code.
+///
+/// ***Uses the command format for Protocol Version 6 or above.***
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GetDenominationLevelCommand {
+    buf: [u8; len::GET_DENOMINATION_LEVEL_COMMAND],
+}
+
+impl GetDenominationLevelCommand {
+    /// Creates a new [GetDenominationLevelCommand] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::GET_DENOMINATION_LEVEL_COMMAND],
+        };
+
+        msg.init();
+        msg.set_command(MessageType::GetDenominationLevel);
+
+        msg
+    }
+
+    /// Gets the denomination [ChannelValue].
+    pub fn value(&self) -> ChannelValue {
+        self.buf[index::VALUE..index::VALUE_END].into()
+    }
+
+    /// Sets the denomination [ChannelValue].
+    pub fn set_value(&mut self, value: ChannelValue) {
+        self.buf[index::VALUE..index::VALUE_END]
+            .copy_from_slice(value.as_inner().to_le_bytes().as_ref());
+    }
+
+    /// Builder function that sets the denomination [ChannelValue].
+    pub fn with_value(mut self, value: ChannelValue) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Gets the denomination [CountryCode].
+    pub fn currency(&self) -> CountryCode {
+        CountryCode::from(&self.buf[index::CURRENCY..index::CURRENCY_END])
+    }
+
+    /// Sets the denomination [CountryCode].
+    pub fn set_currency(&mut self, currency: CountryCode) {
+        self.buf[index::CURRENCY..index::CURRENCY_END]
+            .copy_from_slice(<&str>::from(currency).as_bytes());
+    }
+
+    /// Builder function that sets the denomination [CountryCode].
+    pub fn with_currency(mut self, currency: CountryCode) -> Self {
+        self.set_currency(currency);
+        self
+    }
+}
+
+impl_default!(GetDenominationLevelCommand);
+impl_message_from_buf!(GetDenominationLevelCommand);
+impl_message_ops!(GetDenominationLevelCommand);
+impl_command_ops!(GetDenominationLevelCommand);
+
+impl fmt::Display for GetDenominationLevelCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stx = self.stx();
+        let seqid = self.sequence_id();
+        let len = self.data_len();
+        let cmd = self.command();
+        let value = self.value();
+        let currency = self.currency();
+        let crc = self.checksum();
+
+        write!(f, "STX: 0x{stx:02x} | SEQID: {seqid} | LEN: 0x{len:02x} | Command: {cmd} | Value: {value} | Currency: {currency} | CRC-16: 0x{crc:04x}")
+    }
+}
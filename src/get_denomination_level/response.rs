@@ -0,0 +1,88 @@
+use crate::{
+    impl_default, impl_message_from_buf, impl_message_ops, impl_response_ops, len, std::fmt,
+    MessageOps, MessageType, ResponseOps,
+};
+
+mod index {
+    pub const LEVEL: usize = 4;
+    pub const LEVEL_END: usize = 6;
+}
+
+/// GetDenominationLevel - Response (0x35)
+///
+/// Represents a response to an [GetDenominationLevelCommand](crate::GetDenominationLevelCommand)
+/// message.
+///
+/// Contains the number of notes stored as a 2 byte little endian integer.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GetDenominationLevelResponse {
+    buf: [u8; len::GET_DENOMINATION_LEVEL_RESPONSE],
+}
+
+impl GetDenominationLevelResponse {
+    /// Creates a new [GetDenominationLevelResponse] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::GET_DENOMINATION_LEVEL_RESPONSE],
+        };
+
+        msg.init();
+
+        msg
+    }
+
+    /// Gets the number of notes stored.
+    pub fn level(&self) -> u16 {
+        u16::from_le_bytes(
+            self.buf[index::LEVEL..index::LEVEL_END]
+                .try_into()
+                .unwrap_or([0; 2]),
+        )
+    }
+
+    /// Sets the number of notes stored.
+    pub fn set_level(&mut self, level: u16) {
+        self.buf[index::LEVEL..index::LEVEL_END].copy_from_slice(level.to_le_bytes().as_ref());
+    }
+}
+
+impl_default!(GetDenominationLevelResponse);
+impl_message_from_buf!(GetDenominationLevelResponse);
+impl_message_ops!(
+    GetDenominationLevelResponse,
+    MessageType::GetDenominationLevel
+);
+impl_response_ops!(GetDenominationLevelResponse);
+
+impl fmt::Display for GetDenominationLevelResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stx = self.stx();
+        let seqid = self.sequence_id();
+        let len = self.data_len();
+        let status = self.response_status();
+        let level = self.level();
+        let crc = self.checksum();
+
+        write!(f, "STX: 0x{stx:02x} | SEQID: {seqid} | LEN: 0x{len:02x} | Response status: {status} | Level: {level} | CRC-16: 0x{crc:04x}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ResponseStatus, Result};
+
+    #[test]
+    fn test_get_denomination_level_response() -> Result<()> {
+        // Level of 12 EUR 5.00 notes, from the SSP Protocol Manual
+        let buf = [0x7f, 0x80, 0x03, 0xf0, 0x0c, 0x00, 0xc3, 0x80];
+
+        let res = GetDenominationLevelResponse::try_from(buf)?;
+
+        assert_eq!(res.response_status(), ResponseStatus::Ok);
+        assert_eq!(res.level(), 12);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+//! Get the route of a denomination: the payout device, or the cashbox.
+
+mod command;
+mod response;
+
+pub use command::*;
+pub use response::*;
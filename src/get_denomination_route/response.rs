@@ -0,0 +1,62 @@
+use crate::{
+    impl_default, impl_message_from_buf, impl_message_ops, impl_response_ops, len, std::fmt,
+    DenominationRoute, MessageOps, MessageType, ResponseOps,
+};
+
+mod index {
+    pub const ROUTE: usize = 4;
+}
+
+/// GetDenominationRoute - Response (0x3C)
+///
+/// Represents a response to an [GetDenominationRouteCommand](crate::GetDenominationRouteCommand)
+/// message.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GetDenominationRouteResponse {
+    buf: [u8; len::GET_DENOMINATION_ROUTE_RESPONSE],
+}
+
+impl GetDenominationRouteResponse {
+    /// Creates a new [GetDenominationRouteResponse] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::GET_DENOMINATION_ROUTE_RESPONSE],
+        };
+
+        msg.init();
+
+        msg
+    }
+
+    /// Gets the [DenominationRoute].
+    pub fn route(&self) -> DenominationRoute {
+        self.buf[index::ROUTE].into()
+    }
+
+    /// Sets the [DenominationRoute].
+    pub fn set_route(&mut self, route: DenominationRoute) {
+        self.buf[index::ROUTE] = route.into();
+    }
+}
+
+impl_default!(GetDenominationRouteResponse);
+impl_message_from_buf!(GetDenominationRouteResponse);
+impl_message_ops!(
+    GetDenominationRouteResponse,
+    MessageType::GetDenominationRoute
+);
+impl_response_ops!(GetDenominationRouteResponse);
+
+impl fmt::Display for GetDenominationRouteResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stx = self.stx();
+        let seqid = self.sequence_id();
+        let len = self.data_len();
+        let status = self.response_status();
+        let route = self.route();
+        let crc = self.checksum();
+
+        write!(f, "STX: 0x{stx:02x} | SEQID: {seqid} | LEN: 0x{len:02x} | Response status: {status} | Route: {route} | CRC-16: 0x{crc:04x}")
+    }
+}
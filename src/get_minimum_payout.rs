@@ -0,0 +1,7 @@
+//! Get the minimum value the payout device can dispense.
+
+mod command;
+mod response;
+
+pub use command::*;
+pub use response::*;
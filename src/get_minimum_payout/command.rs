@@ -0,0 +1,71 @@
+use crate::{
+    impl_command_ops, impl_default, impl_message_from_buf, impl_message_ops, len, std::fmt,
+    CommandOps, CountryCode, MessageOps, MessageType,
+};
+
+mod index {
+    pub const CURRENCY: usize = 4;
+    pub const CURRENCY_END: usize = 7;
+}
+
+/// GetMinimumPayout - Command (0x3E)
+///
+/// A command to request the minimum possible payout amount that this device can provide.
+///
+/// The command includes the 3 byte ASCII country code of the requested currency.
+///
+/// ***Uses the command format for Protocol Version 6 or above.***
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GetMinimumPayoutCommand {
+    buf: [u8; len::GET_MINIMUM_PAYOUT_COMMAND],
+}
+
+impl GetMinimumPayoutCommand {
+    /// Creates a new [GetMinimumPayoutCommand] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::GET_MINIMUM_PAYOUT_COMMAND],
+        };
+
+        msg.init();
+        msg.set_command(MessageType::GetMinimumPayout);
+
+        msg
+    }
+
+    /// Gets the [CountryCode] of the requested currency.
+    pub fn currency(&self) -> CountryCode {
+        CountryCode::from(&self.buf[index::CURRENCY..index::CURRENCY_END])
+    }
+
+    /// Sets the [CountryCode] of the requested currency.
+    pub fn set_currency(&mut self, currency: CountryCode) {
+        self.buf[index::CURRENCY..index::CURRENCY_END]
+            .copy_from_slice(<&str>::from(currency).as_bytes());
+    }
+
+    /// Builder function that sets the [CountryCode] of the requested currency.
+    pub fn with_currency(mut self, currency: CountryCode) -> Self {
+        self.set_currency(currency);
+        self
+    }
+}
+
+impl_default!(GetMinimumPayoutCommand);
+impl_message_from_buf!(GetMinimumPayoutCommand);
+impl_message_ops!(GetMinimumPayoutCommand);
+impl_command_ops!(GetMinimumPayoutCommand);
+
+impl fmt::Display for GetMinimumPayoutCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stx = self.stx();
+        let seqid = self.sequence_id();
+        let len = self.data_len();
+        let cmd = self.command();
+        let currency = self.currency();
+        let crc = self.checksum();
+
+        write!(f, "STX: 0x{stx:02x} | SEQID: {seqid} | LEN: 0x{len:02x} | Command: {cmd} | Currency: {currency} | CRC-16: 0x{crc:04x}")
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{
+    impl_default, impl_message_from_buf, impl_message_ops, impl_response_ops, len, std::fmt,
+    MessageOps, MessageType, ResponseOps,
+};
+
+mod index {
+    pub const MIN_PAYOUT: usize = 4;
+    pub const MIN_PAYOUT_END: usize = 8;
+}
+
+/// GetMinimumPayout - Response (0x3E)
+///
+/// Represents a response to an [GetMinimumPayoutCommand](crate::GetMinimumPayoutCommand) message.
+///
+/// Contains the minimum payout value as a 4 byte little endian integer of the full penny amount.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GetMinimumPayoutResponse {
+    buf: [u8; len::GET_MINIMUM_PAYOUT_RESPONSE],
+}
+
+impl GetMinimumPayoutResponse {
+    /// Creates a new [GetMinimumPayoutResponse] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::GET_MINIMUM_PAYOUT_RESPONSE],
+        };
+
+        msg.init();
+
+        msg
+    }
+
+    /// Gets the minimum payout value.
+    pub fn min_payout(&self) -> u32 {
+        u32::from_le_bytes(
+            self.buf[index::MIN_PAYOUT..index::MIN_PAYOUT_END]
+                .try_into()
+                .unwrap_or([0; 4]),
+        )
+    }
+
+    /// Sets the minimum payout value.
+    pub fn set_min_payout(&mut self, min_payout: u32) {
+        self.buf[index::MIN_PAYOUT..index::MIN_PAYOUT_END]
+            .copy_from_slice(min_payout.to_le_bytes().as_ref());
+    }
+}
+
+impl_default!(GetMinimumPayoutResponse);
+impl_message_from_buf!(GetMinimumPayoutResponse);
+impl_message_ops!(GetMinimumPayoutResponse, MessageType::GetMinimumPayout);
+impl_response_ops!(GetMinimumPayoutResponse);
+
+impl fmt::Display for GetMinimumPayoutResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stx = self.stx();
+        let seqid = self.sequence_id();
+        let len = self.data_len();
+        let status = self.response_status();
+        let min_payout = self.min_payout();
+        let crc = self.checksum();
+
+        write!(f, "STX: 0x{stx:02x} | SEQID: {seqid} | LEN: 0x{len:02x} | Response status: {status} | Minimum payout: {min_payout} | CRC-16: 0x{crc:04x}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Result;
+
+    #[test]
+    fn test_get_minimum_payout_response() -> Result<()> {
+        // Minimum payout of EUR 5.00, from the SSP Protocol Manual
+        let buf = [0x7f, 0x80, 0x05, 0xf0, 0xf4, 0x01, 0x00, 0x00, 0xba, 0x72];
+
+        let res = GetMinimumPayoutResponse::try_from(buf)?;
+
+        assert_eq!(res.response_status(), crate::ResponseStatus::Ok);
+        assert_eq!(res.min_payout(), 500);
+
+        Ok(())
+    }
+}
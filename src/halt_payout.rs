@@ -0,0 +1,7 @@
+//! Stop the current payout operation.
+
+mod command;
+mod response;
+
+pub use command::*;
+pub use response::*;
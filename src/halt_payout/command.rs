@@ -0,0 +1,34 @@
+use crate::{
+    impl_command_display, impl_command_ops, impl_default, impl_message_from_buf, impl_message_ops,
+    len, CommandOps, MessageOps, MessageType,
+};
+
+/// HaltPayout - Command (0x38)
+///
+/// A command to stop the execution of an existing payout. The device will stop payout at the
+/// earliest convenient place and generate a Halted event giving the value paid up to that point.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HaltPayoutCommand {
+    buf: [u8; len::HALT_PAYOUT_COMMAND],
+}
+
+impl HaltPayoutCommand {
+    /// Creates a new [HaltPayoutCommand] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::HALT_PAYOUT_COMMAND],
+        };
+
+        msg.init();
+        msg.set_command(MessageType::HaltPayout);
+
+        msg
+    }
+}
+
+impl_default!(HaltPayoutCommand);
+impl_command_display!(HaltPayoutCommand);
+impl_message_from_buf!(HaltPayoutCommand);
+impl_message_ops!(HaltPayoutCommand);
+impl_command_ops!(HaltPayoutCommand);
@@ -0,0 +1,32 @@
+use crate::{
+    impl_default, impl_message_from_buf, impl_message_ops, impl_response_display,
+    impl_response_ops, len, MessageOps, MessageType,
+};
+
+/// HaltPayout - Response (0x38)
+///
+/// Represents a response to an [HaltPayoutCommand](crate::HaltPayoutCommand) message.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HaltPayoutResponse {
+    buf: [u8; len::HALT_PAYOUT_RESPONSE],
+}
+
+impl HaltPayoutResponse {
+    /// Creates a new [HaltPayoutResponse] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::HALT_PAYOUT_RESPONSE],
+        };
+
+        msg.init();
+
+        msg
+    }
+}
+
+impl_default!(HaltPayoutResponse);
+impl_message_from_buf!(HaltPayoutResponse);
+impl_message_ops!(HaltPayoutResponse, MessageType::HaltPayout);
+impl_response_ops!(HaltPayoutResponse);
+impl_response_display!(HaltPayoutResponse);
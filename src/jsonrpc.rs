@@ -2,16 +2,24 @@
 
 use alloc::string::String;
 
-use crate::std::sync::atomic::{AtomicU64, Ordering};
-
+#[cfg(feature = "async")]
+mod async_client;
 #[cfg(feature = "std")]
 mod client;
+#[cfg(feature = "std")]
+mod transport;
+#[cfg(feature = "websocket")]
+mod websocket;
 
 mod request;
 mod response;
 
+#[cfg(feature = "async")]
+pub use async_client::*;
 #[cfg(feature = "std")]
 pub use client::*;
+#[cfg(feature = "std")]
+pub use transport::*;
 
 pub use request::*;
 pub use response::*;
@@ -19,6 +27,11 @@ pub use response::*;
 pub const JSONRPC_ENV_SOCK: &str = "JSONRPC_SOCKET";
 pub const JSONRPC_SOCKET_PATH: &str = "/tmp/ssp-jsonrpc.sock";
 
+/// Environment variable for an extra TCP address (`host:port`) for the server to listen on.
+pub const JSONRPC_ENV_TCP: &str = "JSONRPC_TCP_ADDR";
+/// Environment variable for an extra WebSocket address (`host:port`) for the server to listen on.
+pub const JSONRPC_ENV_WS: &str = "JSONRPC_WS_ADDR";
+
 /// Gets the socket path specified by the environment variable `env`.
 ///
 /// Returns the `default` path if the variable is unset, or another error occurs.
@@ -35,46 +48,58 @@ pub fn get_socket_path(_env: &str, default: &str) -> String {
     default.into()
 }
 
-static ID: AtomicU64 = AtomicU64::new(1);
-
-/// Gets the global JSON-RPC ID.
+/// Gets the [Endpoint] specified by the environment variable `env`.
 ///
-/// IDs must be kept in sync between client and server.
-pub fn jsonrpc_id() -> u64 {
-    ID.load(Ordering::Relaxed)
+/// The variable accepts any [Endpoint] string, e.g. `tcp://127.0.0.1:7878`, or a bare Unix socket
+/// path. Returns the `default` endpoint if the variable is unset, and an error if the endpoint is
+/// invalid.
+#[cfg(feature = "std")]
+pub fn get_endpoint(env: &str, default: &str) -> crate::Result<Endpoint> {
+    get_socket_path(env, default).parse()
 }
 
-/// Sets the global JSON-RPC ID.
-///
-/// IDs must be kept in sync between client and server.
+/// Generates JSON-RPC request IDs for a single connection.
 ///
-/// Returns the previously set ID.
-pub fn set_jsonrpc_id(id: u64) -> u64 {
-    let last = jsonrpc_id();
-    ID.store(id, Ordering::SeqCst);
-    last
+/// IDs start at one, and increase with each request. Since responses are matched to requests by
+/// ID, each connection should use its own [IdGenerator].
+#[derive(Clone, Debug, PartialEq)]
+pub struct IdGenerator {
+    next: u64,
 }
 
-/// Sets the globa JSON-RPC ID from a `serde_json::Value`.
-///
-/// Return the parsed value, if it can be converted to a `u64`, `None` otherwise.
-pub fn set_jsonrpc_id_from_val(val: &serde_json::Value) -> Option<u64> {
-    if val.is_number() {
-        if let Some(v) = val.as_u64() {
-            set_jsonrpc_id(v);
-            Some(v)
-        } else if let Some(v) = val.as_i64() {
-            let v = v as u64;
-            set_jsonrpc_id(v);
-            Some(v)
-        } else if let Some(v) = val.as_f64() {
-            let v = v as u64;
-            set_jsonrpc_id(v);
-            Some(v)
-        } else {
-            None
-        }
-    } else {
-        None
+impl IdGenerator {
+    /// Creates a new [IdGenerator].
+    pub const fn new() -> Self {
+        Self { next: 1 }
+    }
+
+    /// Gets the next request ID.
+    pub fn next_id(&mut self) -> u64 {
+        let id = self.next;
+        // skip zero on overflow, some clients treat it as a missing ID
+        self.next = self.next.checked_add(1).unwrap_or(1);
+        id
+    }
+}
+
+impl_default!(IdGenerator);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_generator() {
+        let mut ids = IdGenerator::new();
+        let mut other_ids = IdGenerator::new();
+
+        assert_eq!(ids.next_id(), 1);
+        assert_eq!(ids.next_id(), 2);
+        // generators are independent
+        assert_eq!(other_ids.next_id(), 1);
+
+        let mut ids = IdGenerator { next: u64::MAX };
+        assert_eq!(ids.next_id(), u64::MAX);
+        assert_eq!(ids.next_id(), 1);
     }
 }
@@ -0,0 +1,426 @@
+//! Asynchronous JSON-RPC client for communication with the SSP/eSSP server.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::io;
+use std::time::Duration;
+
+use futures_core::Stream;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UnixStream};
+use tokio::time::Instant;
+
+use crate::{EnableEvent, Error, Event, Method, ProtocolVersion, Result};
+
+use super::{Endpoint, MessageQueue, DEFAULT_READ_TIMEOUT_MS, DEFAULT_WRITE_TIMEOUT_MS};
+
+// size of each socket read
+const READ_CHUNK: usize = 1024;
+
+/// Asynchronous JSON-RPC client connection to the SSP/eSSP server, using the `tokio` runtime.
+///
+/// The asynchronous counterpart of [Client](super::Client), with the same request ID, timeout,
+/// and reconnect handling. Connects to Unix domain socket and TCP [Endpoint]s.
+///
+/// Partial messages are kept between reads, so cancelling a read does not lose data.
+pub struct AsyncClient {
+    endpoint: Endpoint,
+    stream: AsyncStream,
+    partial: Vec<u8>,
+    read_timeout: Duration,
+    write_timeout: Duration,
+    messages: MessageQueue,
+}
+
+impl AsyncClient {
+    /// Creates a new [AsyncClient] connected to the Unix domain socket at `socket_path`.
+    pub async fn new<S>(socket_path: S) -> Result<Self>
+    where
+        S: Into<String>,
+    {
+        let socket_path: String = socket_path.into();
+        Self::connect(Endpoint::Unix(socket_path.into())).await
+    }
+
+    /// Creates a new [AsyncClient] connected to the server at the [Endpoint].
+    pub async fn connect(endpoint: Endpoint) -> Result<Self> {
+        let stream = AsyncStream::connect(&endpoint).await?;
+
+        Ok(Self {
+            endpoint,
+            stream,
+            partial: Vec::new(),
+            read_timeout: Duration::from_millis(DEFAULT_READ_TIMEOUT_MS),
+            write_timeout: Duration::from_millis(DEFAULT_WRITE_TIMEOUT_MS),
+            messages: MessageQueue::new(),
+        })
+    }
+
+    /// Gets the server [Endpoint].
+    pub fn endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
+    /// Gets the time to wait for a response from the server.
+    pub fn read_timeout(&self) -> Duration {
+        self.read_timeout
+    }
+
+    /// Sets the time to wait for a response from the server.
+    pub fn set_read_timeout(&mut self, timeout: Duration) {
+        self.read_timeout = timeout;
+    }
+
+    /// Builder function that sets the time to wait for a response from the server.
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.set_read_timeout(timeout);
+        self
+    }
+
+    /// Gets the time to wait for a request to be written to the server.
+    pub fn write_timeout(&self) -> Duration {
+        self.write_timeout
+    }
+
+    /// Sets the time to wait for a request to be written to the server.
+    pub fn set_write_timeout(&mut self, timeout: Duration) {
+        self.write_timeout = timeout;
+    }
+
+    /// Builder function that sets the time to wait for a request to be written to the server.
+    pub fn with_write_timeout(mut self, timeout: Duration) -> Self {
+        self.set_write_timeout(timeout);
+        self
+    }
+
+    /// Send a JSON-RPC request to the server, and return the response
+    pub async fn send(&mut self, method: Method) -> Result<Event> {
+        let event = if method == Method::Enable {
+            Event::from(EnableEvent::from(ProtocolVersion::Eight))
+        } else {
+            Event::from(method)
+        };
+
+        self.send_event(&event).await
+    }
+
+    /// Send a JSON-RPC request with the [Event] parameters to the server, and return the response.
+    pub async fn send_event(&mut self, event: &Event) -> Result<Event> {
+        let id = self.request(event).await?;
+        self.response(id).await
+    }
+
+    /// Sends a JSON-RPC request to the server without waiting for the response.
+    ///
+    /// Returns the request ID, used to get the response with [AsyncClient::response].
+    pub async fn request(&mut self, event: &Event) -> Result<u64> {
+        let (id, mut req_json) = self.messages.encode_request(event)?;
+        req_json.push('\n');
+
+        if let Err(err) = self.write_message(req_json.as_bytes()).await {
+            if !matches!(
+                err.kind(),
+                io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset
+            ) {
+                return Err(err.into());
+            }
+
+            // the server went away since the last request, try once more on a new connection
+            self.reconnect().await?;
+            self.write_message(req_json.as_bytes()).await?;
+        }
+
+        self.messages.sent(id);
+
+        Ok(id)
+    }
+
+    /// Waits for the response to the request with the provided `id`.
+    ///
+    /// Responses to other requests, and notifications, received while waiting are buffered.
+    ///
+    /// Returns [Error::Timeout] if no response is received within the read timeout.
+    pub async fn response(&mut self, id: u64) -> Result<Event> {
+        self.messages.check_request(id)?;
+
+        let deadline = Instant::now() + self.read_timeout;
+
+        loop {
+            if let Some(res) = self.messages.take_response(id) {
+                return Ok(res);
+            }
+
+            match self.read_message(Some(deadline)).await? {
+                Some(line) => self.messages.handle_message(line.as_str()),
+                None if Instant::now() >= deadline => {
+                    self.messages.timed_out(id);
+                    return Err(Error::Timeout(format!("no response to request ID: {id}")));
+                }
+                None => (),
+            }
+        }
+    }
+
+    /// Gets the number of requests still waiting for a response.
+    pub fn in_flight(&self) -> usize {
+        self.messages.in_flight()
+    }
+
+    /// Waits for the next notification from the server.
+    ///
+    /// Returns [Error::Timeout] if no notification is received before the `timeout` expires.
+    pub async fn next_event(&mut self, timeout: Duration) -> Result<Event> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if let Some(event) = self.messages.next_notification() {
+                return Ok(event);
+            }
+
+            match self.read_message(Some(deadline)).await? {
+                Some(line) => self.messages.handle_message(line.as_str()),
+                None if Instant::now() >= deadline => {
+                    return Err(Error::Timeout("no event from the server".into()))
+                }
+                None => (),
+            }
+        }
+    }
+
+    /// Streams notifications from the server.
+    ///
+    /// Waits indefinitely for each notification. Connection errors are streamed as errors, and
+    /// the stream continues on the next connection. The stream never ends, so drop it to stop
+    /// receiving notifications.
+    pub fn events(&mut self) -> impl Stream<Item = Result<Event>> + Send + '_ {
+        futures_util::stream::unfold(self, |client| async move {
+            loop {
+                if let Some(event) = client.messages.next_notification() {
+                    return Some((Ok(event), client));
+                }
+
+                match client.read_message(None).await {
+                    Ok(Some(line)) => client.messages.handle_message(line.as_str()),
+                    Ok(None) => (),
+                    Err(err) => return Some((Err(err), client)),
+                }
+            }
+        })
+    }
+
+    async fn reconnect(&mut self) -> Result<()> {
+        log::info!("Reconnecting to {}", self.endpoint);
+
+        self.stream = AsyncStream::connect(&self.endpoint).await?;
+        self.partial.clear();
+        self.messages.connection_closed();
+
+        Ok(())
+    }
+
+    async fn write_message(&mut self, msg: &[u8]) -> io::Result<()> {
+        let timeout = self.write_timeout;
+        let write = async {
+            self.stream.write_all(msg).await?;
+            self.stream.flush().await
+        };
+
+        tokio::time::timeout(timeout, write)
+            .await
+            .map_err(|_| io::Error::from(io::ErrorKind::TimedOut))?
+    }
+
+    /// Reads the next message from the server, waiting until the `deadline`, if any.
+    ///
+    /// Returns `None` if no complete message is read before the `deadline`, or if the connection
+    /// was reset.
+    async fn read_message(&mut self, deadline: Option<Instant>) -> Result<Option<String>> {
+        let mut chunk = [0u8; READ_CHUNK];
+
+        loop {
+            if let Some(msg) = self.pop_line() {
+                return Ok(Some(msg));
+            }
+
+            let read = self.stream.read(&mut chunk);
+
+            // reads are cancel safe, so no data is lost on timeout
+            let res = match deadline {
+                Some(deadline) => match tokio::time::timeout_at(deadline, read).await {
+                    Ok(res) => res,
+                    Err(_) => return Ok(None),
+                },
+                None => read.await,
+            };
+
+            match res {
+                Ok(0) => {
+                    // requests in flight now have a failure response
+                    self.reconnect().await?;
+                    return Ok(None);
+                }
+                Ok(n) => self.partial.extend_from_slice(&chunk[..n]),
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::ConnectionReset | io::ErrorKind::BrokenPipe
+                    ) =>
+                {
+                    self.reconnect().await?;
+                    return Ok(None);
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
+    fn pop_line(&mut self) -> Option<String> {
+        loop {
+            let end = self.partial.iter().position(|&b| b == b'\n')?;
+            let line: Vec<u8> = self.partial.drain(..=end).collect();
+
+            match core::str::from_utf8(&line) {
+                Ok(line) if line.trim().is_empty() => (),
+                Ok(line) => return Some(line.trim().into()),
+                Err(err) => log::warn!("Dropping invalid message: {err}"),
+            }
+        }
+    }
+}
+
+/// Socket stream to the server.
+enum AsyncStream {
+    Unix(UnixStream),
+    Tcp(TcpStream),
+}
+
+impl AsyncStream {
+    async fn connect(endpoint: &Endpoint) -> Result<Self> {
+        match endpoint {
+            Endpoint::Unix(path) => Ok(Self::Unix(UnixStream::connect(path).await?)),
+            Endpoint::Tcp(addr) => {
+                let stream = TcpStream::connect(addr.as_str()).await?;
+                stream.set_nodelay(true)?;
+
+                Ok(Self::Tcp(stream))
+            }
+            #[cfg(feature = "websocket")]
+            Endpoint::WebSocket(_) => Err(Error::JsonRpc(format!(
+                "unsupported endpoint for the async client: {endpoint}"
+            ))),
+        }
+    }
+
+    async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Unix(stream) => stream.read(buf).await,
+            Self::Tcp(stream) => stream.read(buf).await,
+        }
+    }
+
+    async fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match self {
+            Self::Unix(stream) => stream.write_all(buf).await,
+            Self::Tcp(stream) => stream.write_all(buf).await,
+        }
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Unix(stream) => stream.flush().await,
+            Self::Tcp(stream) => stream.flush().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+    use smol_jsonrpc::{Request, Response};
+    use tokio::io::{AsyncBufReadExt, BufReader};
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::{DisableEvent, NoteCreditEvent};
+
+    async fn write_json<T: serde::Serialize>(stream: &mut TcpStream, msg: &T) {
+        let mut json = serde_json::to_string(msg).unwrap();
+        json += "\n";
+        stream.write_all(json.as_bytes()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_async_client() -> Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            let mut line = String::new();
+            BufReader::new(&mut stream)
+                .read_line(&mut line)
+                .await
+                .unwrap();
+            let req = serde_json::from_str::<Request>(line.as_str()).unwrap();
+
+            // a notification split across writes, then the response
+            let mut json =
+                serde_json::to_string(&Request::from(Event::from(NoteCreditEvent::default())))
+                    .unwrap();
+            json += "\n";
+
+            let (head, tail) = json.as_bytes().split_at(json.len() / 2);
+            stream.write_all(head).await.unwrap();
+            stream.flush().await.unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            stream.write_all(tail).await.unwrap();
+
+            write_json(
+                &mut stream,
+                &Response::from(Event::from(DisableEvent::new())).with_id(req.id().unwrap()),
+            )
+            .await;
+
+            write_json(
+                &mut stream,
+                &Request::from(Event::from(NoteCreditEvent::default())),
+            )
+            .await;
+
+            stream
+        });
+
+        let endpoint: Endpoint = format!("tcp://{addr}").parse()?;
+        let mut client = AsyncClient::connect(endpoint)
+            .await?
+            .with_read_timeout(Duration::from_secs(1));
+
+        assert_eq!(
+            client.send(Method::Disable).await?.method(),
+            Method::Disable
+        );
+        assert_eq!(client.in_flight(), 0);
+
+        let _stream = server.await.unwrap();
+
+        assert_eq!(
+            client.next_event(Duration::from_secs(1)).await?.method(),
+            Method::NoteCredit
+        );
+
+        {
+            let events = client.events();
+            futures_util::pin_mut!(events);
+            assert_eq!(events.next().await.unwrap()?.method(), Method::NoteCredit);
+        }
+
+        assert!(matches!(
+            client.next_event(Duration::from_millis(20)).await,
+            Err(Error::Timeout(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -1,144 +1,681 @@
-//! JSON-RPC client for communication with the SSP/eSSP server over Unix domain sockets.
+//! JSON-RPC client for communication with the SSP/eSSP server.
 
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
 use alloc::string::String;
-use std::io::{Read, Write};
-use std::os::unix::net::UnixStream;
+use std::io;
+use std::time::{Duration, Instant};
 
 use smol_jsonrpc::{Request, Response};
 
-use crate::{EnableEvent, Event, Method, ProtocolVersion, Result};
-
+use crate::{EnableEvent, Error, Event, EventPayload, Method, ProtocolVersion, Result};
+
+use super::{Connection, Endpoint, IdGenerator};
+
+/// Default time to wait for a response from the server.
+pub const DEFAULT_READ_TIMEOUT_MS: u64 = 5_000;
+/// Default time to wait for a request to be written to the server.
+pub const DEFAULT_WRITE_TIMEOUT_MS: u64 = 1_000;
+
+/// JSON-RPC client connection to the SSP/eSSP server.
+///
+/// Connects to the server at any [Endpoint]: a Unix domain socket, TCP, or WebSocket. Partial
+/// messages are kept between reads, so a message split across socket reads is never lost.
+///
+/// Each [Client] assigns its own request IDs, and matches responses to requests by ID. Several
+/// requests can be in flight at once, see [Client::request] and [Client::response].
+///
+/// Notifications pushed by the server are buffered separately from responses, and returned by
+/// [Client::receive] and [Client::next_event].
+///
+/// If the server closes the connection, the [Client] reconnects on the next read or write.
+/// Requests in flight when the connection closed get a [Method::Fail] response.
 pub struct Client {
-    socket_path: String,
-    socket: UnixStream,
+    endpoint: Endpoint,
+    conn: Box<dyn Connection>,
+    read_timeout: Duration,
+    write_timeout: Duration,
+    messages: MessageQueue,
 }
 
 impl Client {
-    /// Creates a new JSON-RPC [Client].
+    /// Creates a new JSON-RPC [Client] connected to the Unix domain socket at `socket_path`.
     pub fn new<S>(socket_path: S) -> Result<Self>
     where
         S: Into<String>,
     {
         let socket_path: String = socket_path.into();
-        let socket = UnixStream::connect(&socket_path)?;
+        Self::connect(Endpoint::Unix(socket_path.into()))
+    }
+
+    /// Creates a new JSON-RPC [Client] connected to the server at the [Endpoint].
+    pub fn connect(endpoint: Endpoint) -> Result<Self> {
+        let conn = endpoint.connect()?;
 
         Ok(Self {
-            socket_path,
-            socket,
+            endpoint,
+            conn,
+            read_timeout: Duration::from_millis(DEFAULT_READ_TIMEOUT_MS),
+            write_timeout: Duration::from_millis(DEFAULT_WRITE_TIMEOUT_MS),
+            messages: MessageQueue::new(),
         })
     }
 
-    /// Gets the socket path.
-    pub fn socket_path(&self) -> &str {
-        self.socket_path.as_str()
+    /// Gets the server [Endpoint].
+    pub fn endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
+    /// Gets the time to wait for a response from the server.
+    pub fn read_timeout(&self) -> Duration {
+        self.read_timeout
+    }
+
+    /// Sets the time to wait for a response from the server.
+    pub fn set_read_timeout(&mut self, timeout: Duration) {
+        self.read_timeout = timeout;
+    }
+
+    /// Builder function that sets the time to wait for a response from the server.
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.set_read_timeout(timeout);
+        self
+    }
+
+    /// Gets the time to wait for a request to be written to the server.
+    pub fn write_timeout(&self) -> Duration {
+        self.write_timeout
+    }
+
+    /// Sets the time to wait for a request to be written to the server.
+    pub fn set_write_timeout(&mut self, timeout: Duration) {
+        self.write_timeout = timeout;
+    }
+
+    /// Builder function that sets the time to wait for a request to be written to the server.
+    pub fn with_write_timeout(mut self, timeout: Duration) -> Self {
+        self.set_write_timeout(timeout);
+        self
     }
 
     /// Send a JSON-RPC request to the server, and return the response
-    pub fn send(&mut self, method: Method) -> Result<Vec<Event>> {
+    pub fn send(&mut self, method: Method) -> Result<Event> {
         let event = if method == Method::Enable {
             Event::from(EnableEvent::from(ProtocolVersion::Eight))
         } else {
             Event::from(method)
         };
 
-        let req = Request::from(event);
-        let mut req_json = serde_json::to_string(&req)?;
-        req_json += "\n";
+        self.send_event(&event)
+    }
 
-        log::debug!("Sending message: {req_json}");
+    /// Send a JSON-RPC request with the [Event] parameters to the server, and return the response.
+    ///
+    /// Useful for requests that carry parameters, e.g. [DispenseEvent](crate::DispenseEvent).
+    pub fn send_event(&mut self, event: &Event) -> Result<Event> {
+        let id = self.request(event)?;
+        self.response(id)
+    }
 
-        self.socket.write_all(req_json.as_bytes())?;
-        self.socket.flush()?;
+    /// Sends a JSON-RPC request to the server without waiting for the response.
+    ///
+    /// Returns the request ID, used to get the response with [Client::response].
+    pub fn request(&mut self, event: &Event) -> Result<u64> {
+        let (id, req_json) = self.messages.encode_request(event)?;
+
+        if let Err(err) = self.conn.send_message(&req_json, self.write_timeout) {
+            if !matches!(
+                err.kind(),
+                io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset
+            ) {
+                return Err(err.into());
+            }
+
+            // the server went away since the last request, try once more on a new connection
+            self.reconnect()?;
+            self.conn.send_message(&req_json, self.write_timeout)?;
+        }
 
-        let mut res = vec![0u8; 1024];
-        let mut idx = 0;
+        self.messages.sent(id);
+
+        Ok(id)
+    }
+
+    /// Waits for the response to the request with the provided `id`.
+    ///
+    /// Responses to other requests, and notifications, received while waiting are buffered.
+    ///
+    /// Returns [Error::Timeout] if no response is received within the read timeout.
+    pub fn response(&mut self, id: u64) -> Result<Event> {
+        self.messages.check_request(id)?;
 
         log::debug!("Reading response...");
 
-        while let Ok(ret) = self.socket.read(&mut res) {
-            if ret == 0 {
-                return Ok(Vec::new());
+        let deadline = Instant::now() + self.read_timeout;
+
+        loop {
+            if let Some(res) = self.messages.take_response(id) {
+                return Ok(res);
             }
 
-            idx += ret;
+            match self.read_message(deadline)? {
+                Some(line) => self.messages.handle_message(line.as_str()),
+                None if Instant::now() >= deadline => {
+                    self.messages.timed_out(id);
+                    return Err(Error::Timeout(format!("no response to request ID: {id}")));
+                }
+                None => (),
+            }
+        }
+    }
+
+    /// Gets the number of requests still waiting for a response.
+    pub fn in_flight(&self) -> usize {
+        self.messages.in_flight()
+    }
+
+    /// Receive notifications from the server.
+    ///
+    /// Returns the notifications buffered while waiting for responses, and any that are
+    /// immediately available. Does not block waiting for new notifications.
+    pub fn receive(&mut self) -> Result<Vec<Event>> {
+        let now = Instant::now();
+
+        while let Some(line) = self.read_message(now)? {
+            self.messages.handle_message(line.as_str());
+        }
+
+        Ok(self.messages.drain_notifications())
+    }
+
+    /// Waits for the next notification from the server.
+    ///
+    /// Returns [Error::Timeout] if no notification is received before the `timeout` expires.
+    pub fn next_event(&mut self, timeout: Duration) -> Result<Event> {
+        let deadline = Instant::now() + timeout;
 
-            if idx == res.len() {
-                res.resize(2 * res.len(), 0u8);
+        loop {
+            if let Some(event) = self.messages.next_notification() {
+                return Ok(event);
             }
 
-            if res.contains(&b'\n') {
-                break;
+            match self.read_message(deadline)? {
+                Some(line) => self.messages.handle_message(line.as_str()),
+                None if Instant::now() >= deadline => {
+                    return Err(Error::Timeout("no event from the server".into()))
+                }
+                None => (),
             }
         }
+    }
 
-        if idx == 0 {
-            return Ok(Vec::new());
-        }
+    fn reconnect(&mut self) -> Result<()> {
+        log::info!("Reconnecting to {}", self.endpoint);
 
-        let res_str = std::str::from_utf8(res[..idx].as_ref())?;
-
-        let mut responses = Vec::with_capacity(10);
-
-        for string in res_str.split('\n') {
-            if !string.is_empty() {
-                log::info!("server response: {string}");
-                let res = match serde_json::from_str::<Response>(string) {
-                    Ok(res) => Event::from(res),
-                    Err(err) => {
-                        log::error!(
-                            "Error decoding JSON-RPC response, json: {:?}, error: {err}",
-                            string.as_bytes()
-                        );
-                        return Err(err.into());
-                    }
-                };
-                responses.push(res.into());
+        self.conn = self.endpoint.connect()?;
+        self.messages.connection_closed();
+
+        Ok(())
+    }
+
+    /// Reads the next message from the server, waiting until the `deadline`.
+    ///
+    /// Returns `None` if no complete message is read before the `deadline`, or if the connection
+    /// was reset.
+    fn read_message(&mut self, deadline: Instant) -> Result<Option<String>> {
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+
+            match self.conn.recv_message(timeout) {
+                Ok(Some(msg)) => return Ok(Some(msg)),
+                Ok(None) => (),
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::UnexpectedEof
+                            | io::ErrorKind::ConnectionReset
+                            | io::ErrorKind::BrokenPipe
+                    ) =>
+                {
+                    // requests in flight now have a failure response
+                    self.reconnect()?;
+                    return Ok(None);
+                }
+                Err(err) => return Err(err.into()),
+            }
+
+            if Instant::now() >= deadline {
+                return Ok(None);
             }
         }
+    }
+}
+
+// maximum number of timed out requests to remember, to drop their late responses
+const MAX_TIMED_OUT: usize = 64;
+
+/// Request ID and message bookkeeping shared by the blocking and asynchronous clients.
+///
+/// Matches responses to in-flight requests by ID, and buffers notifications separately.
+#[derive(Debug)]
+pub(crate) struct MessageQueue {
+    ids: IdGenerator,
+    in_flight: BTreeSet<u64>,
+    timed_out: BTreeSet<u64>,
+    responses: BTreeMap<u64, Event>,
+    notifications: VecDeque<Event>,
+}
 
-        Ok(responses)
+impl MessageQueue {
+    /// Creates a new [MessageQueue].
+    pub const fn new() -> Self {
+        Self {
+            ids: IdGenerator::new(),
+            in_flight: BTreeSet::new(),
+            timed_out: BTreeSet::new(),
+            responses: BTreeMap::new(),
+            notifications: VecDeque::new(),
+        }
     }
 
-    /// Receive messages from the server.
-    pub fn receive(&mut self) -> Result<Vec<Event>> {
-        let mut res = vec![0u8; 1024];
-        let mut idx = 0;
+    /// Encodes a request for the [Event] with the next request ID.
+    ///
+    /// Returns the request ID, and the JSON request.
+    pub fn encode_request(&mut self, event: &Event) -> Result<(u64, String)> {
+        let id = self.ids.next_id();
+        let req = Request::from(event).with_id(id);
 
-        self.socket.set_nonblocking(true)?;
+        let req_json = serde_json::to_string(&req)?;
 
-        while let Ok(ret) = self.socket.read(&mut res) {
-            if ret == 0 {
-                return Ok(Vec::new());
-            }
+        log::debug!("Sending message: {req_json}");
 
-            idx += ret;
+        Ok((id, req_json))
+    }
 
-            if idx >= res.len() {
-                res.resize(2 * res.len(), 0u8);
+    /// Marks the request with the provided `id` as in flight.
+    pub fn sent(&mut self, id: u64) {
+        self.in_flight.insert(id);
+    }
+
+    /// Checks that a response is expected, or available, for the request `id`.
+    pub fn check_request(&self, id: u64) -> Result<()> {
+        if self.in_flight.contains(&id) || self.responses.contains_key(&id) {
+            Ok(())
+        } else {
+            Err(Error::JsonRpc(format!(
+                "no request in flight with ID: {id}"
+            )))
+        }
+    }
+
+    /// Takes the response to the request `id`, if it has been received.
+    pub fn take_response(&mut self, id: u64) -> Option<Event> {
+        self.responses.remove(&id)
+    }
+
+    /// Gets the number of requests still waiting for a response.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Stops waiting for the response to the request `id`.
+    ///
+    /// A late response to the request is dropped when it arrives.
+    pub fn timed_out(&mut self, id: u64) {
+        self.responses.remove(&id);
+
+        if self.in_flight.remove(&id) {
+            self.timed_out.insert(id);
+
+            // the server may never answer, only remember the most recent requests
+            while self.timed_out.len() > MAX_TIMED_OUT {
+                self.timed_out.pop_first();
             }
         }
+    }
 
-        if idx == 0 {
-            return Ok(Vec::new());
+    /// Takes the oldest buffered notification.
+    pub fn next_notification(&mut self) -> Option<Event> {
+        self.notifications.pop_front()
+    }
+
+    /// Takes all buffered notifications.
+    pub fn drain_notifications(&mut self) -> Vec<Event> {
+        self.notifications.drain(..).collect()
+    }
+
+    /// Fails the requests in flight, since their responses were lost with the connection.
+    pub fn connection_closed(&mut self) {
+        // late responses can not arrive on a new connection
+        self.timed_out.clear();
+
+        while let Some(id) = self.in_flight.pop_first() {
+            let err = Error::Io(format!("connection closed before response to ID: {id}"));
+            self.responses
+                .insert(id, Event::new(Method::Fail, EventPayload::Error(err)));
         }
+    }
 
-        let res_str = std::str::from_utf8(res[..idx].as_ref())?;
+    /// Buffers a response or notification received from the server.
+    pub fn handle_message(&mut self, msg: &str) {
+        log::info!("server message: {msg}");
 
-        let mut responses = Vec::with_capacity(10);
+        let json = match serde_json::from_str::<serde_json::Value>(msg) {
+            Ok(json) => json,
+            Err(err) => {
+                log::warn!("Dropping invalid message: {err}, json: {msg}");
+                return;
+            }
+        };
 
-        for string in res_str.split('\n') {
-            if !string.is_empty() {
-                log::info!("server response: {string}");
-                if string.contains("params") {
-                    responses.push(serde_json::from_str::<Request>(string)?.into());
-                } else if string.contains("result") {
-                    responses.push(serde_json::from_str::<Response>(string)?.into());
-                } else {
-                    responses.push(serde_json::from_str::<Response>(string)?.into());
+        if json.get("method").is_some_and(|m| !m.is_null()) {
+            match serde_json::from_value::<Request>(json) {
+                Ok(req) => self.notifications.push_back(Event::from(&req)),
+                Err(err) => log::warn!("Dropping invalid notification: {err}, json: {msg}"),
+            }
+        } else {
+            let res = match serde_json::from_value::<Response>(json) {
+                Ok(res) => res,
+                Err(err) => {
+                    log::warn!("Dropping invalid response: {err}, json: {msg}");
+                    return;
                 }
+            };
+
+            // the server could not read the request ID, assume it was the oldest request,
+            // including requests that timed out, since the server answers in order
+            let id = match res.id() {
+                Some(id) => id,
+                None => match self.in_flight.iter().chain(self.timed_out.iter()).min() {
+                    Some(&id) => id,
+                    None => {
+                        log::warn!("Dropping response without a request: {msg}");
+                        return;
+                    }
+                },
+            };
+
+            if self.timed_out.remove(&id) {
+                log::warn!("Dropping late response to timed out request ID: {id}");
+            } else if self.in_flight.remove(&id) {
+                self.responses.insert(id, Event::from(&res));
+            } else {
+                log::warn!("Dropping response to unknown request ID: {id}");
             }
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::PathBuf;
+    use std::sync::mpsc;
+    use std::thread;
+
+    use crate::{DisableEvent, EventPayload, NoteCreditEvent, RejectEvent};
+
+    fn write_json<T: serde::Serialize>(stream: &mut UnixStream, msg: &T) {
+        let mut json = serde_json::to_string(msg).unwrap();
+        json += "\n";
+        stream.write_all(json.as_bytes()).unwrap();
+    }
+
+    fn socket_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "ssp-jsonrpc-client-{name}-{}.sock",
+            std::process::id()
+        ));
+        std::fs::remove_file(&path).ok();
+        path
+    }
+
+    #[test]
+    fn test_client_out_of_order_responses() -> Result<()> {
+        let socket_path = socket_path("order");
+
+        let listener = UnixListener::bind(&socket_path)?;
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut ids = Vec::new();
+            for _ in 0..3 {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let req = serde_json::from_str::<Request>(line.as_str()).unwrap();
+                ids.push(req.id().unwrap());
+            }
+
+            // notification, then responses in reverse order, one with the ID missing
+            write_json(
+                &mut stream,
+                &Request::from(Event::from(NoteCreditEvent::default())),
+            );
+            write_json(
+                &mut stream,
+                &Response::from(Event::from(RejectEvent::new())).with_id(ids[2]),
+            );
+            write_json(
+                &mut stream,
+                &Response::from(Event::from(DisableEvent::new())).with_id(ids[1]),
+            );
+            write_json(
+                &mut stream,
+                &Response::from(Event::new(
+                    Method::Fail,
+                    EventPayload::Error(Error::JsonRpc("invalid request".into())),
+                )),
+            );
+
+            // keep the connection open, closing it makes the client reconnect
+            (ids, stream)
+        });
+
+        let mut client = Client::new(socket_path.to_string_lossy())?;
+
+        let first = client.request(&Event::from(Method::Status))?;
+        let second = client.request(&Event::from(Method::Disable))?;
+        let third = client.request(&Event::from(Method::Reject))?;
+
+        let (ids, _stream) = server.join().unwrap();
+        assert_eq!(ids, [first, second, third]);
+        assert_eq!(client.in_flight(), 3);
+
+        assert_eq!(client.response(second)?.method(), Method::Disable);
+        assert_eq!(client.response(third)?.method(), Method::Reject);
+        assert_eq!(client.response(first)?.method(), Method::Fail);
+        assert_eq!(client.in_flight(), 0);
+
+        assert!(client.response(first).is_err());
+
+        let notifications = client.receive()?;
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].method(), Method::NoteCredit);
+
+        std::fs::remove_file(&socket_path).ok();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_client_partial_lines() -> Result<()> {
+        let socket_path = socket_path("partial");
+        let listener = UnixListener::bind(&socket_path)?;
+
+        let (tx, rx) = mpsc::channel::<()>();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let mut json =
+                serde_json::to_string(&Request::from(Event::from(NoteCreditEvent::default())))
+                    .unwrap();
+            json += "\n";
+
+            let (head, tail) = json.as_bytes().split_at(json.len() / 2);
+
+            stream.write_all(head).unwrap();
+            rx.recv().unwrap();
+            stream.write_all(tail).unwrap();
+            rx.recv().unwrap();
+        });
+
+        let mut client =
+            Client::new(socket_path.to_string_lossy())?.with_read_timeout(Duration::from_secs(1));
+
+        // only half the message is available
+        assert!(matches!(
+            client.next_event(Duration::from_millis(50)),
+            Err(Error::Timeout(_))
+        ));
+        assert!(client.receive()?.is_empty());
+
+        tx.send(()).unwrap();
+
+        assert_eq!(
+            client.next_event(Duration::from_secs(1))?.method(),
+            Method::NoteCredit
+        );
+
+        tx.send(()).unwrap();
+        server.join().unwrap();
+
+        std::fs::remove_file(&socket_path).ok();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_client_reconnect() -> Result<()> {
+        let socket_path = socket_path("reconnect");
+        let listener = UnixListener::bind(&socket_path)?;
+
+        let server = thread::spawn(move || {
+            // close the first connection without answering
+            let (stream, _) = listener.accept().unwrap();
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+            drop(stream);
+
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let req = serde_json::from_str::<Request>(line.as_str()).unwrap();
+
+            write_json(
+                &mut stream,
+                &Response::from(Event::from(DisableEvent::new())).with_id(req.id().unwrap()),
+            );
+
+            // never answer the next request
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+
+            stream
+        });
+
+        let mut client =
+            Client::new(socket_path.to_string_lossy())?.with_read_timeout(Duration::from_secs(1));
+
+        let res = client.send(Method::Status)?;
+        assert_eq!(res.method(), Method::Fail);
+        assert_eq!(client.in_flight(), 0);
+
+        assert_eq!(client.send(Method::Disable)?.method(), Method::Disable);
+
+        client.set_read_timeout(Duration::from_millis(50));
+        assert!(matches!(
+            client.send(Method::Reject),
+            Err(Error::Timeout(_))
+        ));
+
+        let _stream = server.join().unwrap();
+
+        std::fs::remove_file(&socket_path).ok();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_client_late_response() -> Result<()> {
+        let socket_path = socket_path("late");
+        let listener = UnixListener::bind(&socket_path)?;
+
+        let (tx, rx) = mpsc::channel::<()>();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut ids = Vec::new();
+            for _ in 0..3 {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                ids.push(
+                    serde_json::from_str::<Request>(line.as_str())
+                        .unwrap()
+                        .id()
+                        .unwrap(),
+                );
+
+                // answer the first two requests after the client gave up on them
+                if ids.len() == 2 {
+                    rx.recv().unwrap();
+                }
+            }
+
+            write_json(
+                &mut stream,
+                &Response::from(Event::from(DisableEvent::new())).with_id(ids[0]),
+            );
+            // the server could not read the ID of the second request
+            write_json(
+                &mut stream,
+                &Response::from(Event::new(
+                    Method::Fail,
+                    EventPayload::Error(Error::JsonRpc("invalid request".into())),
+                )),
+            );
+            write_json(
+                &mut stream,
+                &Response::from(Event::from(RejectEvent::new())).with_id(ids[2]),
+            );
+
+            stream
+        });
+
+        let mut client = Client::new(socket_path.to_string_lossy())?
+            .with_read_timeout(Duration::from_millis(50));
+
+        let first = client.request(&Event::from(Method::Disable))?;
+        let second = client.request(&Event::from(Method::Status))?;
+
+        assert!(matches!(client.response(first), Err(Error::Timeout(_))));
+        assert!(matches!(client.response(second), Err(Error::Timeout(_))));
+        assert_eq!(client.in_flight(), 0);
+
+        tx.send(()).unwrap();
+        client.set_read_timeout(Duration::from_secs(1));
+
+        // the late responses are dropped, instead of matching the new request
+        let third = client.request(&Event::from(Method::Reject))?;
+        assert_eq!(client.response(third)?.method(), Method::Reject);
+
+        assert!(client.messages.responses.is_empty());
+        assert!(client.messages.timed_out.is_empty());
+        assert!(client.response(first).is_err());
+
+        let _stream = server.join().unwrap();
+
+        std::fs::remove_file(&socket_path).ok();
 
-        Ok(responses)
+        Ok(())
     }
 }
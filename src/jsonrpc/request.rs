@@ -3,7 +3,6 @@
 use alloc::format;
 use smol_jsonrpc::Request;
 
-use super::jsonrpc_id;
 use crate::types::events::*;
 use crate::{Error, Event, EventPayload, Method};
 
@@ -44,6 +43,10 @@ impl From<&Request> for Event {
                 val.params::<DispenseEvent>()
                     .unwrap_or(DispenseEvent::new()),
             ),
+            Method::SetInhibits => EventPayload::SetInhibitsEvent(
+                val.params::<SetInhibitsEvent>()
+                    .unwrap_or(SetInhibitsEvent::new()),
+            ),
             Method::CashboxRemoved => EventPayload::CashboxRemovedEvent(
                 val.params::<CashboxRemovedEvent>()
                     .unwrap_or(CashboxRemovedEvent::new()),
@@ -101,6 +104,85 @@ impl From<&Request> for Event {
                 val.params::<UnsafeJamEvent>()
                     .unwrap_or(UnsafeJamEvent::new()),
             ),
+            Method::Dispensing => EventPayload::DispensingEvent(
+                val.params::<DispensingEvent>()
+                    .unwrap_or(DispensingEvent::default()),
+            ),
+            Method::Dispensed => EventPayload::DispensedEvent(
+                val.params::<DispensedEvent>()
+                    .unwrap_or(DispensedEvent::default()),
+            ),
+            Method::Jammed => EventPayload::JammedEvent(
+                val.params::<JammedEvent>()
+                    .unwrap_or(JammedEvent::default()),
+            ),
+            Method::Halted => EventPayload::HaltedEvent(
+                val.params::<HaltedEvent>()
+                    .unwrap_or(HaltedEvent::default()),
+            ),
+            Method::Floating => EventPayload::FloatingEvent(
+                val.params::<FloatingEvent>()
+                    .unwrap_or(FloatingEvent::default()),
+            ),
+            Method::Floated => EventPayload::FloatedEvent(
+                val.params::<FloatedEvent>()
+                    .unwrap_or(FloatedEvent::default()),
+            ),
+            Method::Timeout => EventPayload::TimeoutEvent(
+                val.params::<TimeoutEvent>()
+                    .unwrap_or(TimeoutEvent::default()),
+            ),
+            Method::IncompletePayout => EventPayload::IncompletePayoutEvent(
+                val.params::<IncompletePayoutEvent>()
+                    .unwrap_or(IncompletePayoutEvent::default()),
+            ),
+            Method::IncompleteFloat => EventPayload::IncompleteFloatEvent(
+                val.params::<IncompleteFloatEvent>()
+                    .unwrap_or(IncompleteFloatEvent::default()),
+            ),
+            Method::Emptying => EventPayload::EmptyingEvent(
+                val.params::<EmptyingEvent>()
+                    .unwrap_or(EmptyingEvent::new()),
+            ),
+            Method::Emptied => EventPayload::EmptiedEvent(
+                val.params::<EmptiedEvent>().unwrap_or(EmptiedEvent::new()),
+            ),
+            Method::SmartEmptying => EventPayload::SmartEmptyingEvent(
+                val.params::<SmartEmptyingEvent>()
+                    .unwrap_or(SmartEmptyingEvent::default()),
+            ),
+            Method::SmartEmptied => EventPayload::SmartEmptiedEvent(
+                val.params::<SmartEmptiedEvent>()
+                    .unwrap_or(SmartEmptiedEvent::default()),
+            ),
+            Method::NoteStoredInPayout => EventPayload::NoteStoredInPayoutEvent(
+                val.params::<NoteStoredInPayoutEvent>()
+                    .unwrap_or(NoteStoredInPayoutEvent::new()),
+            ),
+            Method::NoteTransferredToStacker => EventPayload::NoteTransferredToStackerEvent(
+                val.params::<NoteTransferredToStackerEvent>()
+                    .unwrap_or(NoteTransferredToStackerEvent::default()),
+            ),
+            Method::NoteHeldInBezel => EventPayload::NoteHeldInBezelEvent(
+                val.params::<NoteHeldInBezelEvent>()
+                    .unwrap_or(NoteHeldInBezelEvent::default()),
+            ),
+            Method::DeviceFull => EventPayload::DeviceFullEvent(
+                val.params::<DeviceFullEvent>()
+                    .unwrap_or(DeviceFullEvent::new()),
+            ),
+            Method::PayoutOutOfService => EventPayload::PayoutOutOfServiceEvent(
+                val.params::<PayoutOutOfServiceEvent>()
+                    .unwrap_or(PayoutOutOfServiceEvent::new()),
+            ),
+            Method::NotePathOpen => EventPayload::NotePathOpenEvent(
+                val.params::<NotePathOpenEvent>()
+                    .unwrap_or(NotePathOpenEvent::new()),
+            ),
+            Method::ChannelDisable => EventPayload::ChannelDisableEvent(
+                val.params::<ChannelDisableEvent>()
+                    .unwrap_or(ChannelDisableEvent::new()),
+            ),
             Method::Reserved(m) => {
                 EventPayload::Error(Error::JsonRpc(format!("reserved method: {m}")))
             }
@@ -118,8 +200,8 @@ impl From<Request> for Event {
 
 impl From<&Event> for Request {
     fn from(val: &Event) -> Self {
+        // no ID (a notification), clients set the ID from their own `IdGenerator`
         Request::new()
-            .with_id(jsonrpc_id())
             .with_method(val.method().to_str())
             .with_params(val.payload().to_json())
     }
@@ -134,7 +216,10 @@ impl From<Event> for Request {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{CountryCode, PayoutDenomination, PayoutDenominationList, PayoutVec, Result};
+    use crate::{
+        ChannelValue, CountryCode, CurrencyValue, PayoutDenomination, PayoutDenominationList,
+        PayoutVec, Result,
+    };
 
     #[test]
     fn test_deserialize_dispense_request() -> Result<()> {
@@ -155,4 +240,41 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_deserialize_set_inhibits_request() -> Result<()> {
+        let req_str =
+            r#"{"jsonrpc":"2.0","id":1,"method":"set_inhibits","params":{"channels":[1,2,3]}}"#;
+
+        let request = serde_json::from_str::<Request>(req_str)?;
+
+        let exp_event = SetInhibitsEvent::create(InhibitChannelVec::from_slice(&[1, 2, 3])?);
+
+        assert_eq!(request.params::<SetInhibitsEvent>()?, exp_event);
+        assert_eq!(
+            Event::from(&request),
+            Event::new(
+                Method::SetInhibits,
+                EventPayload::SetInhibitsEvent(exp_event)
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_note_held_in_bezel_request() -> Result<()> {
+        let exp_event = Event::from(NoteHeldInBezelEvent::new(CurrencyValue::create(
+            ChannelValue::from(1000),
+            CountryCode::EUR,
+        )));
+
+        let req_str = serde_json::to_string(&Request::from(&exp_event))?;
+        let request = serde_json::from_str::<Request>(req_str.as_str())?;
+
+        assert_eq!(request.method(), Some("note_held_in_bezel"));
+        assert_eq!(Event::from(&request), exp_event);
+
+        Ok(())
+    }
 }
@@ -1,12 +1,12 @@
 use alloc::format;
 use smol_jsonrpc::Response;
 
-use super::jsonrpc_id;
 use crate::{Error, Event, EventPayload, Method};
 
 impl From<&Event> for Response {
     fn from(val: &Event) -> Self {
-        let res = Self::new().with_id(jsonrpc_id());
+        // servers set the ID of the request being answered
+        let res = Self::new();
 
         match val.payload() {
             EventPayload::Error(err) => res.with_error(err.into()),
@@ -0,0 +1,394 @@
+//! Transports for JSON-RPC messages between clients and the server.
+//!
+//! Unix domain sockets and TCP carry newline-delimited JSON. WebSockets carry one JSON message
+//! per text frame, for clients running in a browser.
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::str::FromStr;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::{std::fmt, Error, Result};
+
+#[cfg(feature = "websocket")]
+use super::websocket::WebSocketListener;
+
+/// Shortest socket timeout, zero durations are rejected by the socket API.
+pub(crate) const MIN_TIMEOUT: Duration = Duration::from_millis(1);
+
+const TCP_SCHEME: &str = "tcp://";
+const UNIX_SCHEME: &str = "unix://";
+const WS_SCHEME: &str = "ws://";
+
+/// Bidirectional connection carrying JSON-RPC messages.
+///
+/// Implementations handle message framing, so each call sends or receives one complete message.
+pub trait Connection: Send {
+    /// Sends a single JSON-RPC message, waiting up to `timeout` for the write to complete.
+    fn send_message(&mut self, msg: &str, timeout: Duration) -> io::Result<()>;
+
+    /// Receives the next JSON-RPC message, waiting up to `timeout`.
+    ///
+    /// Returns `Ok(None)` if no complete message arrives in time. Partially received messages
+    /// are kept for the next call.
+    ///
+    /// Returns an [UnexpectedEof](io::ErrorKind::UnexpectedEof) error when the peer closes the
+    /// connection.
+    fn recv_message(&mut self, timeout: Duration) -> io::Result<Option<String>>;
+
+    /// Creates a new handle to the same connection, e.g. to send messages from another thread.
+    ///
+    /// Only one handle should receive messages.
+    fn try_clone(&self) -> io::Result<Box<dyn Connection>>;
+
+    /// Shuts down both directions of the connection.
+    fn shutdown(&self) -> io::Result<()>;
+}
+
+/// Server side listener accepting JSON-RPC [Connection]s.
+pub trait Listener: Send {
+    /// Waits for the next client, and returns its [Connection].
+    fn accept(&self) -> io::Result<Box<dyn Connection>>;
+
+    /// Gets the [Endpoint] the listener is bound to.
+    ///
+    /// Useful to find the port assigned when binding to port zero.
+    fn local_endpoint(&self) -> io::Result<Endpoint>;
+}
+
+/// Address of a JSON-RPC server.
+///
+/// Parsed from strings with a scheme prefix:
+///
+/// - `unix:///path/to/socket`, or a bare path: Unix domain socket
+/// - `tcp://host:port`: TCP socket
+/// - `ws://host:port[/path]`: WebSocket (requires the `websocket` feature)
+///
+/// ```
+/// # use ssp::jsonrpc::Endpoint;
+/// let endpoint: Endpoint = "tcp://127.0.0.1:7878".parse().unwrap();
+/// assert_eq!(endpoint, Endpoint::Tcp("127.0.0.1:7878".into()));
+///
+/// let endpoint: Endpoint = "/tmp/ssp-jsonrpc.sock".parse().unwrap();
+/// assert_eq!(endpoint.to_string(), "unix:///tmp/ssp-jsonrpc.sock");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Endpoint {
+    /// Unix domain socket path.
+    Unix(PathBuf),
+    /// TCP socket address, as `host:port`.
+    Tcp(String),
+    /// WebSocket address, as `host:port[/path]`.
+    #[cfg(feature = "websocket")]
+    WebSocket(String),
+}
+
+impl Endpoint {
+    /// Connects to the server at the [Endpoint].
+    pub fn connect(&self) -> Result<Box<dyn Connection>> {
+        match self {
+            Self::Unix(path) => Ok(Box::new(LineConnection::new(UnixStream::connect(path)?)?)),
+            Self::Tcp(addr) => Ok(Box::new(LineConnection::new(TcpStream::connect(
+                addr.as_str(),
+            )?)?)),
+            #[cfg(feature = "websocket")]
+            Self::WebSocket(addr) => Ok(Box::new(super::websocket::connect(addr.as_str())?)),
+        }
+    }
+
+    /// Listens for client connections at the [Endpoint].
+    ///
+    /// For Unix domain sockets, any stale socket file left at the path is removed before binding.
+    pub fn bind(&self) -> Result<Box<dyn Listener>> {
+        match self {
+            Self::Unix(path) => {
+                if path.exists() {
+                    std::fs::remove_file(path)?;
+                }
+
+                Ok(Box::new(UnixListener::bind(path)?))
+            }
+            Self::Tcp(addr) => Ok(Box::new(TcpListener::bind(addr.as_str())?)),
+            #[cfg(feature = "websocket")]
+            Self::WebSocket(addr) => Ok(Box::new(WebSocketListener::bind(addr.as_str())?)),
+        }
+    }
+
+    /// Gets the Unix domain socket path, if the [Endpoint] is a Unix socket.
+    pub fn socket_path(&self) -> Option<&Path> {
+        match self {
+            Self::Unix(path) => Some(path.as_path()),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Endpoint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::JsonRpc(format!("invalid endpoint: {s}"));
+
+        if let Some(addr) = s.strip_prefix(TCP_SCHEME) {
+            if addr.is_empty() || addr.contains('/') {
+                Err(invalid())
+            } else {
+                Ok(Self::Tcp(addr.into()))
+            }
+        } else if let Some(addr) = s.strip_prefix(WS_SCHEME) {
+            #[cfg(feature = "websocket")]
+            {
+                if addr.is_empty() || addr.starts_with('/') {
+                    Err(invalid())
+                } else {
+                    Ok(Self::WebSocket(addr.into()))
+                }
+            }
+            #[cfg(not(feature = "websocket"))]
+            {
+                let _ = addr;
+                Err(Error::JsonRpc(format!(
+                    "WebSocket endpoints require the `websocket` feature: {s}"
+                )))
+            }
+        } else {
+            let path = s.strip_prefix(UNIX_SCHEME).unwrap_or(s);
+
+            if path.is_empty() || path.contains("://") {
+                Err(invalid())
+            } else {
+                Ok(Self::Unix(path.into()))
+            }
+        }
+    }
+}
+
+impl From<PathBuf> for Endpoint {
+    fn from(val: PathBuf) -> Self {
+        Self::Unix(val)
+    }
+}
+
+impl From<&Path> for Endpoint {
+    fn from(val: &Path) -> Self {
+        Self::Unix(val.into())
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unix(path) => write!(f, "{UNIX_SCHEME}{}", path.display()),
+            Self::Tcp(addr) => write!(f, "{TCP_SCHEME}{addr}"),
+            #[cfg(feature = "websocket")]
+            Self::WebSocket(addr) => write!(f, "{WS_SCHEME}{addr}"),
+        }
+    }
+}
+
+/// Socket types that carry newline-delimited JSON.
+trait LineStream: Read + Write + Send + Sized + 'static {
+    fn try_clone(&self) -> io::Result<Self>;
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+    fn shutdown(&self, how: Shutdown) -> io::Result<()>;
+}
+
+macro_rules! impl_line_stream {
+    ($stream:ident) => {
+        impl LineStream for $stream {
+            fn try_clone(&self) -> io::Result<Self> {
+                $stream::try_clone(self)
+            }
+
+            fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+                $stream::set_read_timeout(self, timeout)
+            }
+
+            fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+                $stream::set_write_timeout(self, timeout)
+            }
+
+            fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+                $stream::shutdown(self, how)
+            }
+        }
+    };
+}
+
+impl_line_stream!(UnixStream);
+impl_line_stream!(TcpStream);
+
+/// [Connection] sending one JSON message per line.
+struct LineConnection<S: LineStream> {
+    writer: S,
+    reader: BufReader<S>,
+    partial: Vec<u8>,
+}
+
+impl<S: LineStream> LineConnection<S> {
+    fn new(stream: S) -> io::Result<Self> {
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+            partial: Vec::new(),
+        })
+    }
+}
+
+impl<S: LineStream> Connection for LineConnection<S> {
+    fn send_message(&mut self, msg: &str, timeout: Duration) -> io::Result<()> {
+        // write the message in one piece, so the peer does not see a partial line
+        let mut line = String::with_capacity(msg.len() + 1);
+        line.push_str(msg);
+        line.push('\n');
+
+        self.writer
+            .set_write_timeout(Some(timeout.max(MIN_TIMEOUT)))?;
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.flush()
+    }
+
+    fn recv_message(&mut self, timeout: Duration) -> io::Result<Option<String>> {
+        self.reader
+            .get_ref()
+            .set_read_timeout(Some(timeout.max(MIN_TIMEOUT)))?;
+
+        // bytes read before a timeout stay in `partial` until the rest of the line arrives
+        match self.reader.read_until(b'\n', &mut self.partial) {
+            Ok(_) if self.partial.last() == Some(&b'\n') => {
+                let line = String::from_utf8(core::mem::take(&mut self.partial))
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                let line = line.trim();
+
+                Ok((!line.is_empty()).then(|| line.into()))
+            }
+            Ok(_) => {
+                if !self.partial.is_empty() {
+                    log::warn!("Dropping partial message at connection close");
+                    self.partial.clear();
+                }
+
+                Err(io::ErrorKind::UnexpectedEof.into())
+            }
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::WouldBlock
+                        | io::ErrorKind::TimedOut
+                        | io::ErrorKind::Interrupted
+                ) =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn try_clone(&self) -> io::Result<Box<dyn Connection>> {
+        Ok(Box::new(Self::new(self.writer.try_clone()?)?))
+    }
+
+    fn shutdown(&self) -> io::Result<()> {
+        self.writer.shutdown(Shutdown::Both)
+    }
+}
+
+impl Listener for UnixListener {
+    fn accept(&self) -> io::Result<Box<dyn Connection>> {
+        let (stream, _) = UnixListener::accept(self)?;
+        Ok(Box::new(LineConnection::new(stream)?))
+    }
+
+    fn local_endpoint(&self) -> io::Result<Endpoint> {
+        self.local_addr()?
+            .as_pathname()
+            .map(Endpoint::from)
+            .ok_or_else(|| io::Error::other("unnamed Unix socket"))
+    }
+}
+
+impl Listener for TcpListener {
+    fn accept(&self) -> io::Result<Box<dyn Connection>> {
+        let (stream, addr) = TcpListener::accept(self)?;
+        log::debug!("TCP connection from {addr}");
+
+        stream.set_nodelay(true)?;
+
+        Ok(Box::new(LineConnection::new(stream)?))
+    }
+
+    fn local_endpoint(&self) -> io::Result<Endpoint> {
+        Ok(Endpoint::Tcp(self.local_addr()?.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_from_str() -> Result<()> {
+        assert_eq!(
+            "/tmp/ssp.sock".parse::<Endpoint>()?,
+            Endpoint::Unix("/tmp/ssp.sock".into())
+        );
+        assert_eq!(
+            "unix:///tmp/ssp.sock".parse::<Endpoint>()?,
+            Endpoint::Unix("/tmp/ssp.sock".into())
+        );
+        assert_eq!(
+            "tcp://localhost:7878".parse::<Endpoint>()?,
+            Endpoint::Tcp("localhost:7878".into())
+        );
+
+        for endpoint in ["", "tcp://", "tcp://host:1/path", "http://host:1"] {
+            assert!(endpoint.parse::<Endpoint>().is_err(), "{endpoint}");
+        }
+
+        for endpoint in ["unix:///tmp/ssp.sock", "tcp://localhost:7878"] {
+            assert_eq!(endpoint.parse::<Endpoint>()?.to_string(), endpoint);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tcp_line_connection() -> Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+
+        let mut client = Endpoint::Tcp(addr.to_string()).connect()?;
+        let mut server = Listener::accept(&listener)?;
+
+        let timeout = Duration::from_millis(50);
+
+        assert_eq!(client.recv_message(timeout)?, None);
+
+        client.send_message(r#"{"jsonrpc":"2.0"}"#, timeout)?;
+        assert_eq!(
+            server.recv_message(Duration::from_secs(1))?.as_deref(),
+            Some(r#"{"jsonrpc":"2.0"}"#)
+        );
+
+        let mut writer = server.try_clone()?;
+        writer.send_message("{}", timeout)?;
+        assert_eq!(
+            client.recv_message(Duration::from_secs(1))?.as_deref(),
+            Some("{}")
+        );
+
+        server.shutdown()?;
+        assert_eq!(
+            client
+                .recv_message(Duration::from_secs(1))
+                .map_err(|e| e.kind()),
+            Err(io::ErrorKind::UnexpectedEof)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+//! WebSocket transport for JSON-RPC messages.
+//!
+//! Each JSON-RPC message is sent as a single text frame, so browser clients can use the
+//! standard `WebSocket` API without any extra framing.
+
+use alloc::{boxed::Box, string::String, sync::Arc};
+use std::io;
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use tungstenite::{Message, WebSocket};
+
+use crate::{Error, Result};
+
+use super::transport::{Connection, Endpoint, Listener, MIN_TIMEOUT};
+
+// longest time a read holds the socket lock, so writes from other handles are not starved
+const READ_SLICE: Duration = Duration::from_millis(20);
+
+/// [Connection] sending one JSON message per WebSocket text frame.
+///
+/// Handles created by [Connection::try_clone] share the socket, since WebSocket framing state
+/// cannot be split between a reader and writer.
+pub(crate) struct WebSocketConnection {
+    socket: Arc<Mutex<WebSocket<TcpStream>>>,
+}
+
+impl WebSocketConnection {
+    fn new(socket: WebSocket<TcpStream>) -> Self {
+        Self {
+            socket: Arc::new(Mutex::new(socket)),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, WebSocket<TcpStream>> {
+        self.socket.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Connection for WebSocketConnection {
+    fn send_message(&mut self, msg: &str, timeout: Duration) -> io::Result<()> {
+        let mut socket = self.lock();
+
+        socket
+            .get_ref()
+            .set_write_timeout(Some(timeout.max(MIN_TIMEOUT)))?;
+        socket.send(Message::text(msg)).map_err(to_io_error)
+    }
+
+    fn recv_message(&mut self, timeout: Duration) -> io::Result<Option<String>> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            {
+                let mut socket = self.lock();
+
+                socket
+                    .get_ref()
+                    .set_read_timeout(Some(remaining.clamp(MIN_TIMEOUT, READ_SLICE)))?;
+
+                match socket.read() {
+                    Ok(Message::Text(text)) => return Ok(Some(text.as_str().into())),
+                    Ok(Message::Binary(data)) => {
+                        return String::from_utf8(data.into())
+                            .map(Some)
+                            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+                    }
+                    Ok(Message::Close(_)) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                    // pings are answered by the socket on the next read or write
+                    Ok(_) => (),
+                    Err(tungstenite::Error::Io(err))
+                        if matches!(
+                            err.kind(),
+                            io::ErrorKind::WouldBlock
+                                | io::ErrorKind::TimedOut
+                                | io::ErrorKind::Interrupted
+                        ) => {}
+                    Err(err) => return Err(to_io_error(err)),
+                }
+            }
+
+            if Instant::now() >= deadline {
+                return Ok(None);
+            }
+        }
+    }
+
+    fn try_clone(&self) -> io::Result<Box<dyn Connection>> {
+        Ok(Box::new(Self {
+            socket: Arc::clone(&self.socket),
+        }))
+    }
+
+    fn shutdown(&self) -> io::Result<()> {
+        let mut socket = self.lock();
+
+        socket.close(None).ok();
+        socket.flush().ok();
+        socket.get_ref().shutdown(Shutdown::Both)
+    }
+}
+
+/// [Listener] accepting WebSocket clients.
+pub(crate) struct WebSocketListener {
+    listener: TcpListener,
+    path: String,
+}
+
+impl WebSocketListener {
+    /// Listens for WebSocket clients on `addr`, as `host:port[/path]`.
+    ///
+    /// Clients are accepted on any request path.
+    pub fn bind(addr: &str) -> Result<Self> {
+        let (host, path) = split_addr(addr);
+
+        Ok(Self {
+            listener: TcpListener::bind(host)?,
+            path: path.into(),
+        })
+    }
+}
+
+impl Listener for WebSocketListener {
+    fn accept(&self) -> io::Result<Box<dyn Connection>> {
+        let (stream, addr) = self.listener.accept()?;
+        log::debug!("WebSocket connection from {addr}");
+
+        stream.set_nodelay(true)?;
+
+        let socket = tungstenite::accept(stream).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::ConnectionAborted,
+                format!("WebSocket handshake failed: {err}"),
+            )
+        })?;
+
+        Ok(Box::new(WebSocketConnection::new(socket)))
+    }
+
+    fn local_endpoint(&self) -> io::Result<Endpoint> {
+        let addr = self.listener.local_addr()?;
+        Ok(Endpoint::WebSocket(format!("{addr}{}", self.path)))
+    }
+}
+
+/// Connects to the WebSocket server at `addr`, as `host:port[/path]`.
+pub(crate) fn connect(addr: &str) -> Result<WebSocketConnection> {
+    let (host, _path) = split_addr(addr);
+
+    let stream = TcpStream::connect(host)?;
+    stream.set_nodelay(true)?;
+
+    let (socket, _res) = tungstenite::client(format!("ws://{addr}").as_str(), stream)
+        .map_err(|err| Error::Io(format!("WebSocket handshake failed: {err}")))?;
+
+    Ok(WebSocketConnection::new(socket))
+}
+
+fn split_addr(addr: &str) -> (&str, &str) {
+    addr.find('/')
+        .map(|i| addr.split_at(i))
+        .unwrap_or((addr, ""))
+}
+
+fn to_io_error(err: tungstenite::Error) -> io::Error {
+    match err {
+        tungstenite::Error::Io(err) => err,
+        tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed => {
+            io::ErrorKind::UnexpectedEof.into()
+        }
+        err => io::Error::other(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_websocket_connection() -> Result<()> {
+        let listener = WebSocketListener::bind("127.0.0.1:0/jsonrpc")?;
+        let endpoint = listener.local_endpoint()?;
+
+        let server = std::thread::spawn(move || -> io::Result<()> {
+            let mut conn = listener.accept()?;
+            let msg = conn.recv_message(Duration::from_secs(1))?;
+            assert_eq!(msg.as_deref(), Some(r#"{"id":1}"#));
+
+            // writes through another handle go to the same socket
+            let mut writer = conn.try_clone()?;
+            writer.send_message(r#"{"id":2}"#, Duration::from_secs(1))?;
+
+            assert_eq!(
+                conn.recv_message(Duration::from_secs(1))
+                    .map_err(|e| e.kind()),
+                Err(io::ErrorKind::UnexpectedEof)
+            );
+
+            Ok(())
+        });
+
+        assert!(endpoint.to_string().ends_with("/jsonrpc"));
+        let mut client = endpoint.connect()?;
+
+        assert_eq!(client.recv_message(Duration::from_millis(50))?, None);
+
+        client.send_message(r#"{"id":1}"#, Duration::from_secs(1))?;
+        assert_eq!(
+            client.recv_message(Duration::from_secs(1))?.as_deref(),
+            Some(r#"{"id":2}"#)
+        );
+
+        client.shutdown()?;
+        server.join().unwrap()?;
+
+        Ok(())
+    }
+}
@@ -85,6 +85,11 @@ make_key!(
 );
 
 impl RandomKey {
+    /// Generates a [RandomKey] from the CSPRNG of a prime [Generator].
+    pub fn from_generator(gen: &mut Generator) -> Self {
+        gen.next_u64().into()
+    }
+
     /// Generates a [RandomKey] from a seed.
     ///
     /// See notes on generating a sufficiently random seed from the
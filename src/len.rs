@@ -162,6 +162,58 @@ pub const ENABLE_PAYOUT_RESPONSE: usize = 6;
 pub const DISABLE_PAYOUT_COMMAND: usize = 6;
 /// DisablePayout Response full message length.
 pub const DISABLE_PAYOUT_RESPONSE: usize = 6;
+/// PayoutAmount Command full message length.
+pub const PAYOUT_AMOUNT_COMMAND: usize = 14;
+/// PayoutAmount Response full message length.
+pub const PAYOUT_AMOUNT_RESPONSE: usize = 6;
+/// HaltPayout Command full message length.
+pub const HALT_PAYOUT_COMMAND: usize = 6;
+/// HaltPayout Response full message length.
+pub const HALT_PAYOUT_RESPONSE: usize = 6;
+/// FloatAmount Command full message length.
+pub const FLOAT_AMOUNT_COMMAND: usize = 18;
+/// FloatAmount Response full message length.
+pub const FLOAT_AMOUNT_RESPONSE: usize = 6;
+/// GetMinimumPayout Command full message length.
+pub const GET_MINIMUM_PAYOUT_COMMAND: usize = 9;
+/// GetMinimumPayout Response full message length.
+pub const GET_MINIMUM_PAYOUT_RESPONSE: usize = 10;
+/// FloatByDenomination Command maximum full message length.
+///
+/// Because float messages have variable lengths, set the static length to maximum
+/// possible. Actual length is determined by reading the LEN field.
+pub const FLOAT_BY_DENOMINATION_COMMAND: usize = MAX_MESSAGE;
+/// FloatByDenomination Response full message length.
+pub const FLOAT_BY_DENOMINATION_RESPONSE: usize = 6;
+/// SetDenominationLevel Command full message length.
+pub const SET_DENOMINATION_LEVEL_COMMAND: usize = 15;
+/// SetDenominationLevel Response full message length.
+pub const SET_DENOMINATION_LEVEL_RESPONSE: usize = 6;
+/// GetDenominationLevel Command full message length.
+pub const GET_DENOMINATION_LEVEL_COMMAND: usize = 13;
+/// GetDenominationLevel Response full message length.
+pub const GET_DENOMINATION_LEVEL_RESPONSE: usize = 8;
+/// GetAllLevels Command full message length.
+pub const GET_ALL_LEVELS_COMMAND: usize = 6;
+/// GetAllLevels Response maximum full message length.
+///
+/// Because level messages have variable response lengths, set the static length to maximum
+/// possible. Actual length is determined by reading the LEN field.
+pub const GET_ALL_LEVELS_RESPONSE: usize = MAX_MESSAGE;
+/// Length of a serialized [DenominationLevel](crate::DenominationLevel).
+pub const LEVEL_BLOCK: usize = 9;
+/// SetDenominationRoute Command full message length.
+pub const SET_DENOMINATION_ROUTE_COMMAND: usize = 14;
+/// SetDenominationRoute Response full message length.
+pub const SET_DENOMINATION_ROUTE_RESPONSE: usize = 6;
+/// GetDenominationRoute Command full message length.
+pub const GET_DENOMINATION_ROUTE_COMMAND: usize = 13;
+/// GetDenominationRoute Response full message length.
+pub const GET_DENOMINATION_ROUTE_RESPONSE: usize = 7;
+/// Length of a serialized [CurrencyValue](crate::CurrencyValue).
+pub const CURRENCY_VALUE_BLOCK: usize = 7;
+/// Length of a serialized [IncompleteValue](crate::IncompleteValue).
+pub const INCOMPLETE_VALUE_BLOCK: usize = 11;
 /// ProgramFirmware Command full message length.
 pub const PROGRAM_FIRMWARE_COMMAND: usize = 7;
 /// ProgramFirmware Response full message length.
@@ -207,9 +259,16 @@ pub const MAX_DATA: usize = 255;
 ///
 /// Because encrypted messages must be wrapped in a standard SSP message, the full message must be
 /// able to fit inside a standard SSP data field.
-pub const MAX_ENCRYPTED_DATA: usize = MAX_DATA - ENCRYPTED_METADATA;
+///
+/// The encrypted fields are also packed to a multiple of the [AES block length](AES), so the
+/// maximum is the largest packed length that fits after the `STEX` byte.
+pub const MAX_ENCRYPTED_DATA: usize = ((MAX_DATA - 1) / AES * AES) - (ENCRYPTED_METADATA - 1);
 /// Maximum full length for a message.
 pub const MAX_MESSAGE: usize = METADATA + MAX_DATA;
+/// Maximum length for a byte-stuffed message on the wire.
+///
+/// Worst case, every byte after `STX` is repeated.
+pub const MAX_STUFFED_MESSAGE: usize = (2 * MAX_MESSAGE) - 1;
 /// Maximum full length for an encrypted message.
 pub const MAX_ENCRYPTED_MESSAGE: usize = MAX_DATA;
 
@@ -221,6 +280,8 @@ pub const FIRMWARE_VERSION: usize = 4;
 pub const AES: usize = 16;
 /// The size of an AES-128 key.
 pub const AES_KEY: usize = 16;
+/// The size of the fixed part of an AES-128 `eSSP` key.
+pub const FIXED_KEY: usize = 8;
 
 /// Gets the length of additional data needed to be a multiple of the AES block length.
 pub fn aes_packing_len(raw_len: usize) -> usize {
@@ -35,6 +35,7 @@ mod channels;
 pub mod configure_bezel;
 pub mod crc;
 pub mod dataset_version;
+pub mod device;
 pub mod disable;
 pub mod disable_payout;
 pub mod display_off;
@@ -47,9 +48,17 @@ pub mod encryption_reset;
 pub mod error;
 pub mod event_ack;
 pub mod firmware;
+pub mod float_amount;
+pub mod float_by_denomination;
+pub mod frame;
+pub mod get_all_levels;
 pub mod get_barcode_data;
 pub mod get_barcode_inhibit;
 pub mod get_barcode_reader_configuration;
+pub mod get_denomination_level;
+pub mod get_denomination_route;
+pub mod get_minimum_payout;
+pub mod halt_payout;
 pub mod hold;
 pub mod host_protocol_version;
 #[cfg(feature = "jsonrpc")]
@@ -58,6 +67,7 @@ pub mod keys;
 pub mod last_reject_code;
 pub mod len;
 pub mod message;
+pub mod payout_amount;
 pub mod payout_by_denomination;
 pub mod poll;
 pub mod poll_with_ack;
@@ -69,13 +79,18 @@ pub mod rng;
 pub mod serial_number;
 pub mod set_barcode_inhibit;
 pub mod set_barcode_reader_configuration;
+pub mod set_denomination_level;
+pub mod set_denomination_route;
 pub mod set_encryption_key;
 pub mod set_generator;
 pub mod set_inhibits;
 pub mod set_modulus;
 pub mod setup_request;
+#[cfg(feature = "std")]
+pub mod simulator;
 pub mod smart_empty;
 pub mod sync;
+pub mod transport;
 pub mod types;
 pub mod unit_data;
 
@@ -83,6 +98,7 @@ pub use channel_value_data::*;
 pub use channels::*;
 pub use configure_bezel::*;
 pub use dataset_version::*;
+pub use device::*;
 pub use disable::*;
 pub use disable_payout::*;
 pub use display_off::*;
@@ -95,14 +111,23 @@ pub use encryption_reset::*;
 pub use error::*;
 pub use event_ack::*;
 pub use firmware::*;
+pub use float_amount::*;
+pub use float_by_denomination::*;
+pub use frame::*;
+pub use get_all_levels::*;
 pub use get_barcode_data::*;
 pub use get_barcode_inhibit::*;
 pub use get_barcode_reader_configuration::*;
+pub use get_denomination_level::*;
+pub use get_denomination_route::*;
+pub use get_minimum_payout::*;
+pub use halt_payout::*;
 pub use hold::*;
 pub use host_protocol_version::*;
 pub use keys::*;
 pub use last_reject_code::*;
 pub use message::{index as message_index, *};
+pub use payout_amount::*;
 pub use payout_by_denomination::*;
 pub use poll::*;
 pub use poll_with_ack::*;
@@ -113,6 +138,8 @@ pub use rng::*;
 pub use serial_number::*;
 pub use set_barcode_inhibit::*;
 pub use set_barcode_reader_configuration::*;
+pub use set_denomination_level::*;
+pub use set_denomination_route::*;
 pub use set_encryption_key::*;
 pub use set_generator::*;
 pub use set_inhibits::*;
@@ -120,6 +147,7 @@ pub use set_modulus::*;
 pub use setup_request::*;
 pub use smart_empty::*;
 pub use sync::*;
+pub use transport::*;
 pub use types::*;
 pub use unit_data::*;
 
@@ -517,6 +517,10 @@ macro_rules! impl_var_message_ops {
                 true
             }
 
+            fn max_len(&self) -> usize {
+                self.buf.len()
+            }
+
             fn len(&self) -> usize {
                 // Read the LEN message field, because the actual length is variable.
                 (self.buf[$crate::message::index::LEN] as usize) + $crate::len::METADATA
@@ -574,6 +578,10 @@ macro_rules! impl_var_message_ops {
                 true
             }
 
+            fn max_len(&self) -> usize {
+                self.buf.len()
+            }
+
             fn len(&self) -> usize {
                 // Read the LEN message field, because the actual length is variable.
                 self.data_len() + $crate::len::METADATA
@@ -615,6 +623,10 @@ macro_rules! impl_encrypted_message_ops {
                 true
             }
 
+            fn max_len(&self) -> usize {
+                self.buf.len()
+            }
+
             fn len(&self) -> usize {
                 // Read the LEN message field, because the actual length is variable.
                 let meta = self.metadata_len();
@@ -673,12 +685,6 @@ macro_rules! impl_encrypted_message_ops {
                 use $crate::encrypted::encrypted_index as index;
 
                 self.buf[index::STEX] = $crate::encrypted::STEX;
-
-                let count_start = index::COUNT;
-                let count_end = index::COUNT_END;
-                let count = $crate::encrypted::sequence_count().as_inner().to_be_bytes();
-
-                self.buf[count_start..count_end].copy_from_slice(count.as_ref());
             }
 
             fn buf(&self) -> &[u8] {
@@ -703,6 +709,10 @@ macro_rules! impl_encrypted_message_ops {
                 true
             }
 
+            fn max_len(&self) -> usize {
+                self.buf.len()
+            }
+
             fn len(&self) -> usize {
                 // Read the LEN message field, because the actual length is variable.
                 let meta = self.metadata_len();
@@ -783,6 +793,17 @@ macro_rules! impl_wrapped_message_ops {
                 self.buf[..len].as_mut()
             }
 
+            fn set_sequence_id(&mut self, id: $crate::SequenceId) {
+                use $crate::message::index;
+
+                self.buf[index::SEQ_ID] = id.into();
+
+                // the checksum covers the SEQID, so update it unless there is byte stuffing
+                if self.stuffing == 0 {
+                    self.calculate_checksum();
+                }
+            }
+
             fn as_bytes(&mut self) -> &[u8] {
                 // don't calculate the checksum here, there may be byte stuffing
                 // checksum is calculated when the message is encrypted and wrapped
@@ -827,6 +848,10 @@ macro_rules! impl_wrapped_message_ops {
                 true
             }
 
+            fn max_len(&self) -> usize {
+                self.buf.len()
+            }
+
             fn len(&self) -> usize {
                 self.data_len() + $crate::len::METADATA
             }
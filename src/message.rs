@@ -8,6 +8,9 @@ use crate::{crc::crc16, len::METADATA, Error, MessageType, ResponseStatus, Resul
 mod variant;
 pub use variant::*;
 
+#[cfg(all(test, feature = "std"))]
+mod proptests;
+
 /// Single byte indicating the start of a packet, defined as 0x7F. If any other part of the packet
 /// contains 0x7F, the last step before transmission the byte should be repeated (0x7F becomes 0x7F 0x7F)
 /// to indicate it is not a STX byte; this is called byte stuffing.
@@ -57,6 +60,13 @@ pub trait MessageOps {
         self.metadata_len() + self.data_len()
     }
 
+    /// Gets the maximum length of the message buffer.
+    ///
+    /// Equal to the message length for messages with a fixed data length.
+    fn max_len(&self) -> usize {
+        self.len()
+    }
+
     /// Gets the length of the metadata fields in the message buffer.
     fn metadata_len(&self) -> usize {
         super::len::METADATA
@@ -202,24 +212,41 @@ pub trait MessageOps {
     }
 
     /// Constructs a message from a byte buffer.
+    ///
+    /// The buffer may contain trailing bytes after the message, which are ignored.
     #[allow(clippy::wrong_self_convention)]
     fn from_buf(&mut self, buf: &[u8]) -> Result<()> {
         let len = self.len();
-        let mut buf_len = buf.len();
+        let buf_len = buf.len();
         let is_variable = self.is_variable();
 
         if !is_variable && buf_len < len {
             return Err(Error::InvalidLength((buf_len, len)));
         }
 
+        if buf_len < METADATA {
+            return Err(Error::InvalidLength((buf_len, METADATA)));
+        }
+
         let stx = buf[index::STX];
         if stx != STX {
             return Err(Error::InvalidSTX(stx));
         }
 
         let buf_data_len = buf[index::LEN] as usize;
+        let msg_len = buf_data_len + METADATA;
 
-        if !is_variable {
+        if is_variable {
+            // every message has at least a command or status byte
+            let max_data_len = self.max_len().saturating_sub(METADATA);
+            if !(1..=max_data_len).contains(&buf_data_len) {
+                return Err(Error::InvalidDataLength((buf_data_len, max_data_len)));
+            }
+
+            if buf_len < msg_len {
+                return Err(Error::InvalidLength((buf_len, msg_len)));
+            }
+        } else {
             let data_len = self.data_len();
             if buf_data_len != data_len {
                 return Err(Error::InvalidDataLength((buf_data_len, data_len)));
@@ -236,15 +263,18 @@ pub trait MessageOps {
             }
         }
 
-        buf_len = std::cmp::min(buf_len, buf_data_len + METADATA);
-        let buf_crc = u16::from_le_bytes(buf[buf_len - 2..].try_into().unwrap());
-        let exp_crc = crc16(buf[index::SEQ_ID..buf_len - 2].as_ref());
+        let buf_crc = u16::from_le_bytes([buf[msg_len - 2], buf[msg_len - 1]]);
+        let exp_crc = crc16(buf[index::SEQ_ID..msg_len - 2].as_ref());
 
         if buf_crc != exp_crc && msg_type != MessageType::Encrypted {
             return Err(Error::Crc((buf_crc, exp_crc)));
         }
 
-        self.buf_mut()[..buf_len].copy_from_slice(buf[..buf_len].as_ref());
+        if is_variable {
+            self.set_data_len(buf_data_len as u8);
+        }
+
+        self.buf_mut()[..msg_len].copy_from_slice(buf[..msg_len].as_ref());
 
         Ok(())
     }
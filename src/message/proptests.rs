@@ -0,0 +1,230 @@
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use crate::*;
+
+/// Fills the message with the `seq_id`, and as much of `data` as the message holds.
+///
+/// Command messages keep their command byte. Variable length messages take the length of `data`,
+/// within the limits of the message buffer.
+fn fill_message(msg: &mut dyn MessageOps, seq_id: u8, data: &[u8]) {
+    msg.set_sequence_id(SequenceId::from(seq_id));
+
+    let start = usize::from(msg.is_command());
+
+    if msg.is_variable() {
+        let max_data_len = msg
+            .max_len()
+            .saturating_sub(len::METADATA)
+            .min(len::MAX_DATA);
+        let data_len = data.len().clamp(start.max(1), max_data_len);
+
+        let mut msg_data = [0u8; len::MAX_DATA];
+        msg_data[..start].copy_from_slice(&msg.data()[..start]);
+        for (dst, src) in msg_data[start..data_len].iter_mut().zip(data.iter()) {
+            *dst = *src;
+        }
+
+        msg.set_data(&msg_data[..data_len]).unwrap();
+    } else {
+        for (dst, src) in msg.data_mut()[start..].iter_mut().zip(data.iter()) {
+            *dst = *src;
+        }
+    }
+}
+
+macro_rules! round_trip {
+    ($($test:ident: $msg:ident),+ $(,)?) => {
+        proptest! {
+            $(
+                #[test]
+                fn $test(
+                    seq_id in any::<u8>(),
+                    data in vec(any::<u8>(), 0..=len::MAX_DATA),
+                    trailing in vec(any::<u8>(), 0..8),
+                ) {
+                    let mut msg = $msg::new();
+                    fill_message(&mut msg, seq_id, &data);
+
+                    let mut bytes = msg.as_bytes().to_vec();
+                    prop_assert_eq!(&$msg::try_from(bytes.as_slice()).unwrap(), &msg);
+
+                    // trailing bytes after the message are ignored
+                    bytes.extend_from_slice(&trailing);
+                    prop_assert_eq!(&$msg::try_from(bytes.as_slice()).unwrap(), &msg);
+
+                    // a corrupted checksum is rejected
+                    let crc_idx = msg.len() - 1;
+                    bytes[crc_idx] ^= 0xff;
+                    prop_assert!($msg::try_from(bytes.as_slice()).is_err());
+                }
+            )+
+        }
+    };
+}
+
+round_trip! {
+    test_channel_value_data_command: ChannelValueDataCommand,
+    test_channel_value_data_response: ChannelValueDataResponse,
+    test_configure_bezel_command: ConfigureBezelCommand,
+    test_configure_bezel_response: ConfigureBezelResponse,
+    test_dataset_version_command: DatasetVersionCommand,
+    test_dataset_version_response: DatasetVersionResponse,
+    test_disable_command: DisableCommand,
+    test_disable_response: DisableResponse,
+    test_disable_payout_command: DisablePayoutCommand,
+    test_disable_payout_response: DisablePayoutResponse,
+    test_display_off_command: DisplayOffCommand,
+    test_display_off_response: DisplayOffResponse,
+    test_display_on_command: DisplayOnCommand,
+    test_display_on_response: DisplayOnResponse,
+    test_empty_command: EmptyCommand,
+    test_empty_response: EmptyResponse,
+    test_enable_command: EnableCommand,
+    test_enable_response: EnableResponse,
+    test_enable_payout_command: EnablePayoutCommand,
+    test_enable_payout_response: EnablePayoutResponse,
+    test_encryption_reset_command: EncryptionResetCommand,
+    test_encryption_reset_response: EncryptionResetResponse,
+    test_event_ack_command: EventAckCommand,
+    test_event_ack_response: EventAckResponse,
+    test_firmware_header_command: FirmwareHeaderCommand,
+    test_firmware_header_response: FirmwareHeaderResponse,
+    test_float_amount_command: FloatAmountCommand,
+    test_float_amount_response: FloatAmountResponse,
+    test_float_by_denomination_command: FloatByDenominationCommand,
+    test_float_by_denomination_response: FloatByDenominationResponse,
+    test_get_all_levels_command: GetAllLevelsCommand,
+    test_get_all_levels_response: GetAllLevelsResponse,
+    test_get_barcode_data_command: GetBarcodeDataCommand,
+    test_get_barcode_data_response: GetBarcodeDataResponse,
+    test_get_barcode_inhibit_command: GetBarcodeInhibitCommand,
+    test_get_barcode_inhibit_response: GetBarcodeInhibitResponse,
+    test_get_barcode_reader_configuration_command: GetBarcodeReaderConfigurationCommand,
+    test_get_barcode_reader_configuration_response: GetBarcodeReaderConfigurationResponse,
+    test_get_denomination_level_command: GetDenominationLevelCommand,
+    test_get_denomination_level_response: GetDenominationLevelResponse,
+    test_get_denomination_route_command: GetDenominationRouteCommand,
+    test_get_denomination_route_response: GetDenominationRouteResponse,
+    test_get_minimum_payout_command: GetMinimumPayoutCommand,
+    test_get_minimum_payout_response: GetMinimumPayoutResponse,
+    test_halt_payout_command: HaltPayoutCommand,
+    test_halt_payout_response: HaltPayoutResponse,
+    test_hold_command: HoldCommand,
+    test_hold_response: HoldResponse,
+    test_host_protocol_version_command: HostProtocolVersionCommand,
+    test_host_protocol_version_response: HostProtocolVersionResponse,
+    test_last_reject_code_command: LastRejectCodeCommand,
+    test_last_reject_code_response: LastRejectCodeResponse,
+    test_payout_amount_command: PayoutAmountCommand,
+    test_payout_amount_response: PayoutAmountResponse,
+    test_payout_by_denomination_command: PayoutByDenominationCommand,
+    test_payout_by_denomination_response: PayoutByDenominationResponse,
+    test_poll_command: PollCommand,
+    test_poll_response: PollResponse,
+    test_poll_with_ack_command: PollWithAckCommand,
+    test_poll_with_ack_response: PollWithAckResponse,
+    test_program_firmware_command: ProgramFirmwareCommand,
+    test_program_firmware_response: ProgramFirmwareResponse,
+    test_reject_command: RejectCommand,
+    test_reject_response: RejectResponse,
+    test_request_key_exchange_command: RequestKeyExchangeCommand,
+    test_request_key_exchange_response: RequestKeyExchangeResponse,
+    test_reset_command: ResetCommand,
+    test_serial_number_command: SerialNumberCommand,
+    test_serial_number_response: SerialNumberResponse,
+    test_set_barcode_inhibit_command: SetBarcodeInhibitCommand,
+    test_set_barcode_inhibit_response: SetBarcodeInhibitResponse,
+    test_set_barcode_reader_configuration_command: SetBarcodeReaderConfigurationCommand,
+    test_set_barcode_reader_configuration_response: SetBarcodeReaderConfigurationResponse,
+    test_set_denomination_level_command: SetDenominationLevelCommand,
+    test_set_denomination_level_response: SetDenominationLevelResponse,
+    test_set_denomination_route_command: SetDenominationRouteCommand,
+    test_set_denomination_route_response: SetDenominationRouteResponse,
+    test_set_encryption_key_command: SetEncryptionKeyCommand,
+    test_set_encryption_key_response: SetEncryptionKeyResponse,
+    test_set_generator_command: SetGeneratorCommand,
+    test_set_generator_response: SetGeneratorResponse,
+    test_set_inhibits_command: SetInhibitsCommand,
+    test_set_inhibits_response: SetInhibitsResponse,
+    test_set_modulus_command: SetModulusCommand,
+    test_set_modulus_response: SetModulusResponse,
+    test_setup_request_command: SetupRequestCommand,
+    test_setup_request_response: SetupRequestResponse,
+    test_smart_empty_command: SmartEmptyCommand,
+    test_smart_empty_response: SmartEmptyResponse,
+    test_sync_command: SyncCommand,
+    test_sync_response: SyncResponse,
+    test_unit_data_command: UnitDataCommand,
+    test_unit_data_response: UnitDataResponse,
+}
+
+proptest! {
+    #[test]
+    fn test_encrypted_command(
+        key in any::<[u8; len::AES]>(),
+        count in any::<u32>(),
+        data in vec(any::<u8>(), 0..=len::SET_INHIBITS_COMMAND - len::METADATA - 1),
+    ) {
+        let mut host = EsspSession::from_aes_key(AesKey::clone_from_slice(key.as_ref()))
+            .with_count(count.into());
+        let mut device = host.clone();
+
+        let mut msg = SetInhibitsCommand::new();
+        fill_message(&mut msg, 0, &data);
+
+        let enc_msg = EncryptedCommand::new().with_message_data(&msg).unwrap();
+        let dec_msg = EncryptedCommand::decrypt(&mut device, enc_msg.encrypt(&mut host).unwrap()).unwrap();
+
+        dec_msg.verify_checksum().unwrap();
+        prop_assert_eq!(dec_msg.message_data(), msg.data());
+        prop_assert_eq!(dec_msg.count(), count.into());
+        prop_assert_eq!(host.count(), device.count());
+    }
+
+    #[test]
+    fn test_encrypted_response(
+        key in any::<[u8; len::AES]>(),
+        count in any::<u32>(),
+        data in vec(any::<u8>(), 0..=len::MAX_ENCRYPTED_DATA),
+    ) {
+        let mut device = EsspSession::from_aes_key(AesKey::clone_from_slice(key.as_ref()))
+            .with_count(count.into());
+        let mut host = device.clone();
+
+        let mut msg = PollResponse::new();
+        fill_message(&mut msg, 0, &data);
+
+        let mut enc_msg = EncryptedResponse::new();
+        enc_msg.set_message_data(&msg).unwrap();
+
+        let dec_msg = EncryptedResponse::decrypt(&mut host, enc_msg.encrypt(&mut device).unwrap()).unwrap();
+
+        dec_msg.verify_checksum().unwrap();
+        prop_assert_eq!(dec_msg.message_data(), msg.data());
+        prop_assert_eq!(dec_msg.count(), count.into());
+        prop_assert_eq!(host.count(), device.count());
+    }
+
+    #[test]
+    fn test_variant_from_buf_arbitrary(
+        msg_type in any::<u8>(),
+        buf in vec(any::<u8>(), 0..=len::MAX_MESSAGE + 8),
+    ) {
+        // only checks that hostile input returns an error, instead of panicking
+        let _ = MessageVariant::from_buf(buf.as_ref(), MessageType::from(msg_type));
+    }
+
+    #[test]
+    fn test_variant_from_buf_header(
+        msg_type in any::<u8>(),
+        data_len in any::<u8>(),
+        buf in vec(any::<u8>(), 0..=len::MAX_MESSAGE + 8),
+    ) {
+        // a valid header reaches the length and checksum checks
+        let mut buf = buf;
+        buf.splice(..buf.len().min(3), [STX, 0, data_len]);
+
+        let _ = MessageVariant::from_buf(buf.as_ref(), MessageType::from(msg_type));
+    }
+}
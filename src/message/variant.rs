@@ -2,13 +2,16 @@ use crate::{
     std::fmt, ChannelValueDataResponse, ConfigureBezelResponse, DatasetVersionResponse,
     DisablePayoutResponse, DisableResponse, DisplayOffResponse, DisplayOnResponse, EmptyResponse,
     EnablePayoutResponse, EnableResponse, EncryptionResetResponse, Error, EventAckResponse,
-    GetBarcodeDataResponse, GetBarcodeInhibitResponse, GetBarcodeReaderConfigurationResponse,
-    HoldResponse, HostProtocolVersionResponse, LastRejectCodeResponse, MessageType, PollResponse,
-    PollWithAckResponse, RejectResponse, RequestKeyExchangeResponse, ResponseOps, Result,
-    SerialNumberResponse, SetBarcodeInhibitResponse, SetBarcodeReaderConfigurationResponse,
-    SetEncryptionKeyResponse, SetGeneratorResponse, SetInhibitsResponse, SetModulusResponse,
-    SetupRequestResponse, SmartEmptyResponse, SyncResponse, UnitDataResponse,
-    WrappedEncryptedMessage,
+    FloatAmountResponse, FloatByDenominationResponse, GetAllLevelsResponse, GetBarcodeDataResponse,
+    GetBarcodeInhibitResponse, GetBarcodeReaderConfigurationResponse, GetDenominationLevelResponse,
+    GetDenominationRouteResponse, GetMinimumPayoutResponse, HaltPayoutResponse, HoldResponse,
+    HostProtocolVersionResponse, LastRejectCodeResponse, MessageType, PayoutAmountResponse,
+    PayoutByDenominationResponse, PollResponse, PollWithAckResponse, RejectResponse,
+    RequestKeyExchangeResponse, ResponseOps, Result, SerialNumberResponse,
+    SetBarcodeInhibitResponse, SetBarcodeReaderConfigurationResponse, SetDenominationLevelResponse,
+    SetDenominationRouteResponse, SetEncryptionKeyResponse, SetGeneratorResponse,
+    SetInhibitsResponse, SetModulusResponse, SetupRequestResponse, SmartEmptyResponse,
+    SyncResponse, UnitDataResponse, WrappedEncryptedMessage,
 };
 
 #[cfg(test)]
@@ -32,6 +35,9 @@ pub enum MessageVariant {
     EnableResponse(EnableResponse),
     EnablePayoutResponse(EnablePayoutResponse),
     EventAckResponse(EventAckResponse),
+    GetAllLevelsResponse(GetAllLevelsResponse),
+    GetDenominationLevelResponse(GetDenominationLevelResponse),
+    GetDenominationRouteResponse(GetDenominationRouteResponse),
     GetBarcodeDataResponse(GetBarcodeDataResponse),
     GetBarcodeInhibitResponse(GetBarcodeInhibitResponse),
     GetBarcodeReaderConfigurationResponse(GetBarcodeReaderConfigurationResponse),
@@ -40,10 +46,18 @@ pub enum MessageVariant {
     HoldResponse(HoldResponse),
     HostProtocolVersionResponse(HostProtocolVersionResponse),
     LastRejectCodeResponse(LastRejectCodeResponse),
+    PayoutByDenominationResponse(PayoutByDenominationResponse),
+    PayoutAmountResponse(PayoutAmountResponse),
+    HaltPayoutResponse(HaltPayoutResponse),
+    FloatAmountResponse(FloatAmountResponse),
+    GetMinimumPayoutResponse(GetMinimumPayoutResponse),
+    FloatByDenominationResponse(FloatByDenominationResponse),
     PollResponse(PollResponse),
     PollWithAckResponse(PollWithAckResponse),
     RejectResponse(RejectResponse),
     SerialNumberResponse(SerialNumberResponse),
+    SetDenominationLevelResponse(SetDenominationLevelResponse),
+    SetDenominationRouteResponse(SetDenominationRouteResponse),
     SetEncryptionKeyResponse(SetEncryptionKeyResponse),
     SetGeneratorResponse(SetGeneratorResponse),
     SetModulusResponse(SetModulusResponse),
@@ -79,6 +93,13 @@ impl MessageVariant {
             MessageType::Enable => Self::EnableResponse(EnableResponse::new()),
             MessageType::EnablePayout => Self::EnablePayoutResponse(EnablePayoutResponse::new()),
             MessageType::EventAck => Self::EventAckResponse(EventAckResponse::new()),
+            MessageType::GetAllLevels => Self::GetAllLevelsResponse(GetAllLevelsResponse::new()),
+            MessageType::GetDenominationLevel => {
+                Self::GetDenominationLevelResponse(GetDenominationLevelResponse::new())
+            }
+            MessageType::GetDenominationRoute => {
+                Self::GetDenominationRouteResponse(GetDenominationRouteResponse::new())
+            }
             MessageType::GetBarcodeData => {
                 Self::GetBarcodeDataResponse(GetBarcodeDataResponse::new())
             }
@@ -105,10 +126,28 @@ impl MessageVariant {
             MessageType::LastRejectCode => {
                 Self::LastRejectCodeResponse(LastRejectCodeResponse::new())
             }
+            MessageType::PayoutByDenomination => {
+                Self::PayoutByDenominationResponse(PayoutByDenominationResponse::new())
+            }
+            MessageType::PayoutAmount => Self::PayoutAmountResponse(PayoutAmountResponse::new()),
+            MessageType::HaltPayout => Self::HaltPayoutResponse(HaltPayoutResponse::new()),
+            MessageType::FloatAmount => Self::FloatAmountResponse(FloatAmountResponse::new()),
+            MessageType::GetMinimumPayout => {
+                Self::GetMinimumPayoutResponse(GetMinimumPayoutResponse::new())
+            }
+            MessageType::FloatByDenomination => {
+                Self::FloatByDenominationResponse(FloatByDenominationResponse::new())
+            }
             MessageType::Poll => Self::PollResponse(PollResponse::new()),
             MessageType::PollWithAck => Self::PollWithAckResponse(PollWithAckResponse::new()),
             MessageType::Reject => Self::RejectResponse(RejectResponse::new()),
             MessageType::SerialNumber => Self::SerialNumberResponse(SerialNumberResponse::new()),
+            MessageType::SetDenominationLevel => {
+                Self::SetDenominationLevelResponse(SetDenominationLevelResponse::new())
+            }
+            MessageType::SetDenominationRoute => {
+                Self::SetDenominationRouteResponse(SetDenominationRouteResponse::new())
+            }
             MessageType::SetEncryptionKey => {
                 Self::SetEncryptionKeyResponse(SetEncryptionKeyResponse::new())
             }
@@ -142,6 +181,9 @@ impl MessageVariant {
             Self::EnablePayoutResponse(msg) => msg,
             Self::EncryptionResetResponse(msg) => msg,
             Self::EventAckResponse(msg) => msg,
+            Self::GetAllLevelsResponse(msg) => msg,
+            Self::GetDenominationLevelResponse(msg) => msg,
+            Self::GetDenominationRouteResponse(msg) => msg,
             Self::GetBarcodeDataResponse(msg) => msg,
             Self::GetBarcodeInhibitResponse(msg) => msg,
             Self::GetBarcodeReaderConfigurationResponse(msg) => msg,
@@ -150,10 +192,18 @@ impl MessageVariant {
             Self::HoldResponse(msg) => msg,
             Self::HostProtocolVersionResponse(msg) => msg,
             Self::LastRejectCodeResponse(msg) => msg,
+            Self::PayoutByDenominationResponse(msg) => msg,
+            Self::PayoutAmountResponse(msg) => msg,
+            Self::HaltPayoutResponse(msg) => msg,
+            Self::FloatAmountResponse(msg) => msg,
+            Self::GetMinimumPayoutResponse(msg) => msg,
+            Self::FloatByDenominationResponse(msg) => msg,
             Self::PollResponse(msg) => msg,
             Self::PollWithAckResponse(msg) => msg,
             Self::RejectResponse(msg) => msg,
             Self::SerialNumberResponse(msg) => msg,
+            Self::SetDenominationLevelResponse(msg) => msg,
+            Self::SetDenominationRouteResponse(msg) => msg,
             Self::SetEncryptionKeyResponse(msg) => msg,
             Self::SetGeneratorResponse(msg) => msg,
             Self::SetModulusResponse(msg) => msg,
@@ -182,6 +232,9 @@ impl MessageVariant {
             Self::EnablePayoutResponse(msg) => msg,
             Self::EncryptionResetResponse(msg) => msg,
             Self::EventAckResponse(msg) => msg,
+            Self::GetAllLevelsResponse(msg) => msg,
+            Self::GetDenominationLevelResponse(msg) => msg,
+            Self::GetDenominationRouteResponse(msg) => msg,
             Self::GetBarcodeDataResponse(msg) => msg,
             Self::GetBarcodeInhibitResponse(msg) => msg,
             Self::GetBarcodeReaderConfigurationResponse(msg) => msg,
@@ -190,10 +243,18 @@ impl MessageVariant {
             Self::HoldResponse(msg) => msg,
             Self::HostProtocolVersionResponse(msg) => msg,
             Self::LastRejectCodeResponse(msg) => msg,
+            Self::PayoutByDenominationResponse(msg) => msg,
+            Self::PayoutAmountResponse(msg) => msg,
+            Self::HaltPayoutResponse(msg) => msg,
+            Self::FloatAmountResponse(msg) => msg,
+            Self::GetMinimumPayoutResponse(msg) => msg,
+            Self::FloatByDenominationResponse(msg) => msg,
             Self::PollResponse(msg) => msg,
             Self::PollWithAckResponse(msg) => msg,
             Self::RejectResponse(msg) => msg,
             Self::SerialNumberResponse(msg) => msg,
+            Self::SetDenominationLevelResponse(msg) => msg,
+            Self::SetDenominationRouteResponse(msg) => msg,
             Self::SetEncryptionKeyResponse(msg) => msg,
             Self::SetGeneratorResponse(msg) => msg,
             Self::SetModulusResponse(msg) => msg,
@@ -245,6 +306,15 @@ impl MessageVariant {
                 EncryptionResetResponse::try_from(buf)?,
             )),
             MessageType::EventAck => Ok(Self::EventAckResponse(EventAckResponse::try_from(buf)?)),
+            MessageType::GetAllLevels => Ok(Self::GetAllLevelsResponse(
+                GetAllLevelsResponse::try_from(buf)?,
+            )),
+            MessageType::GetDenominationLevel => Ok(Self::GetDenominationLevelResponse(
+                GetDenominationLevelResponse::try_from(buf)?,
+            )),
+            MessageType::GetDenominationRoute => Ok(Self::GetDenominationRouteResponse(
+                GetDenominationRouteResponse::try_from(buf)?,
+            )),
             MessageType::GetBarcodeData => Ok(Self::GetBarcodeDataResponse(
                 GetBarcodeDataResponse::try_from(buf)?,
             )),
@@ -271,6 +341,24 @@ impl MessageVariant {
             MessageType::LastRejectCode => Ok(Self::LastRejectCodeResponse(
                 LastRejectCodeResponse::try_from(buf)?,
             )),
+            MessageType::PayoutByDenomination => Ok(Self::PayoutByDenominationResponse(
+                PayoutByDenominationResponse::try_from(buf)?,
+            )),
+            MessageType::PayoutAmount => Ok(Self::PayoutAmountResponse(
+                PayoutAmountResponse::try_from(buf)?,
+            )),
+            MessageType::HaltPayout => {
+                Ok(Self::HaltPayoutResponse(HaltPayoutResponse::try_from(buf)?))
+            }
+            MessageType::FloatAmount => Ok(Self::FloatAmountResponse(
+                FloatAmountResponse::try_from(buf)?,
+            )),
+            MessageType::GetMinimumPayout => Ok(Self::GetMinimumPayoutResponse(
+                GetMinimumPayoutResponse::try_from(buf)?,
+            )),
+            MessageType::FloatByDenomination => Ok(Self::FloatByDenominationResponse(
+                FloatByDenominationResponse::try_from(buf)?,
+            )),
             MessageType::Poll => Ok(Self::PollResponse(PollResponse::try_from(buf)?)),
             MessageType::PollWithAck => Ok(Self::PollWithAckResponse(
                 PollWithAckResponse::try_from(buf)?,
@@ -279,6 +367,12 @@ impl MessageVariant {
             MessageType::SerialNumber => Ok(Self::SerialNumberResponse(
                 SerialNumberResponse::try_from(buf)?,
             )),
+            MessageType::SetDenominationLevel => Ok(Self::SetDenominationLevelResponse(
+                SetDenominationLevelResponse::try_from(buf)?,
+            )),
+            MessageType::SetDenominationRoute => Ok(Self::SetDenominationRouteResponse(
+                SetDenominationRouteResponse::try_from(buf)?,
+            )),
             MessageType::SetEncryptionKey => Ok(Self::SetEncryptionKeyResponse(
                 SetEncryptionKeyResponse::try_from(buf)?,
             )),
@@ -320,6 +414,9 @@ inner_enum!(MessageVariant, EncryptionResetResponse);
 inner_enum!(MessageVariant, EnableResponse);
 inner_enum!(MessageVariant, EnablePayoutResponse);
 inner_enum!(MessageVariant, EventAckResponse);
+inner_enum!(MessageVariant, GetAllLevelsResponse);
+inner_enum!(MessageVariant, GetDenominationLevelResponse);
+inner_enum!(MessageVariant, GetDenominationRouteResponse);
 inner_enum!(MessageVariant, GetBarcodeDataResponse);
 inner_enum!(MessageVariant, GetBarcodeInhibitResponse);
 inner_enum!(MessageVariant, GetBarcodeReaderConfigurationResponse);
@@ -328,10 +425,18 @@ inner_enum!(MessageVariant, SetBarcodeReaderConfigurationResponse);
 inner_enum!(MessageVariant, HoldResponse);
 inner_enum!(MessageVariant, HostProtocolVersionResponse);
 inner_enum!(MessageVariant, LastRejectCodeResponse);
+inner_enum!(MessageVariant, PayoutByDenominationResponse);
+inner_enum!(MessageVariant, PayoutAmountResponse);
+inner_enum!(MessageVariant, HaltPayoutResponse);
+inner_enum!(MessageVariant, FloatAmountResponse);
+inner_enum!(MessageVariant, GetMinimumPayoutResponse);
+inner_enum!(MessageVariant, FloatByDenominationResponse);
 inner_enum!(MessageVariant, PollResponse);
 inner_enum!(MessageVariant, PollWithAckResponse);
 inner_enum!(MessageVariant, RejectResponse);
 inner_enum!(MessageVariant, SerialNumberResponse);
+inner_enum!(MessageVariant, SetDenominationLevelResponse);
+inner_enum!(MessageVariant, SetDenominationRouteResponse);
 inner_enum!(MessageVariant, SetEncryptionKeyResponse);
 inner_enum!(MessageVariant, SetGeneratorResponse);
 inner_enum!(MessageVariant, SetModulusResponse);
@@ -358,6 +463,13 @@ impl fmt::Display for MessageVariant {
             Self::EnablePayoutResponse(msg) => write!(f, "EnablePayoutResponse({msg})"),
             Self::EncryptionResetResponse(msg) => write!(f, "EncryptionResetResponse({msg})"),
             Self::EventAckResponse(msg) => write!(f, "EventAckResponse({msg})"),
+            Self::GetAllLevelsResponse(msg) => write!(f, "GetAllLevelsResponse({msg})"),
+            Self::GetDenominationLevelResponse(msg) => {
+                write!(f, "GetDenominationLevelResponse({msg})")
+            }
+            Self::GetDenominationRouteResponse(msg) => {
+                write!(f, "GetDenominationRouteResponse({msg})")
+            }
             Self::GetBarcodeDataResponse(msg) => write!(f, "GetBarcodeDataResponse({msg})"),
             Self::GetBarcodeInhibitResponse(msg) => write!(f, "GetBarcodeInhibitResponse({msg})"),
             Self::GetBarcodeReaderConfigurationResponse(msg) => {
@@ -372,10 +484,26 @@ impl fmt::Display for MessageVariant {
                 write!(f, "HostProtocolVersionResponse({msg})")
             }
             Self::LastRejectCodeResponse(msg) => write!(f, "LastRejectCodeResponse({msg})"),
+            Self::PayoutByDenominationResponse(msg) => {
+                write!(f, "PayoutByDenominationResponse({msg})")
+            }
+            Self::PayoutAmountResponse(msg) => write!(f, "PayoutAmountResponse({msg})"),
+            Self::HaltPayoutResponse(msg) => write!(f, "HaltPayoutResponse({msg})"),
+            Self::FloatAmountResponse(msg) => write!(f, "FloatAmountResponse({msg})"),
+            Self::GetMinimumPayoutResponse(msg) => write!(f, "GetMinimumPayoutResponse({msg})"),
+            Self::FloatByDenominationResponse(msg) => {
+                write!(f, "FloatByDenominationResponse({msg})")
+            }
             Self::PollResponse(msg) => write!(f, "PollResponse({msg})"),
             Self::PollWithAckResponse(msg) => write!(f, "PollWithAckResponse({msg})"),
             Self::RejectResponse(msg) => write!(f, "RejectResponse({msg})"),
             Self::SerialNumberResponse(msg) => write!(f, "SerialNumberResponse({msg})"),
+            Self::SetDenominationLevelResponse(msg) => {
+                write!(f, "SetDenominationLevelResponse({msg})")
+            }
+            Self::SetDenominationRouteResponse(msg) => {
+                write!(f, "SetDenominationRouteResponse({msg})")
+            }
             Self::SetEncryptionKeyResponse(msg) => write!(f, "SetEncryptionKeyResponse({msg})"),
             Self::SetGeneratorResponse(msg) => write!(f, "SetGeneratorResponse({msg})"),
             Self::SetModulusResponse(msg) => write!(f, "SetModulusResponse({msg})"),
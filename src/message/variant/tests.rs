@@ -196,6 +196,51 @@ fn test_variant_event_ack_destructure() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_variant_get_all_levels_destructure() -> Result<()> {
+    let mut exp_msg = GetAllLevelsResponse::new();
+    exp_msg.calculate_checksum();
+
+    let exp_msg_type = exp_msg.message_type();
+    let var = MessageVariant::from_buf(exp_msg.buf(), exp_msg_type)?;
+
+    assert!(var.is_get_all_levels_response());
+    assert_eq!(var.as_get_all_levels_response()?, &exp_msg);
+    assert_eq!(var.into_get_all_levels_response()?, exp_msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_variant_get_denomination_level_destructure() -> Result<()> {
+    let mut exp_msg = GetDenominationLevelResponse::new();
+    exp_msg.calculate_checksum();
+
+    let exp_msg_type = exp_msg.message_type();
+    let var = MessageVariant::from_buf(exp_msg.buf(), exp_msg_type)?;
+
+    assert!(var.is_get_denomination_level_response());
+    assert_eq!(var.as_get_denomination_level_response()?, &exp_msg);
+    assert_eq!(var.into_get_denomination_level_response()?, exp_msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_variant_get_denomination_route_destructure() -> Result<()> {
+    let mut exp_msg = GetDenominationRouteResponse::new();
+    exp_msg.calculate_checksum();
+
+    let exp_msg_type = exp_msg.message_type();
+    let var = MessageVariant::from_buf(exp_msg.buf(), exp_msg_type)?;
+
+    assert!(var.is_get_denomination_route_response());
+    assert_eq!(var.as_get_denomination_route_response()?, &exp_msg);
+    assert_eq!(var.into_get_denomination_route_response()?, exp_msg);
+
+    Ok(())
+}
+
 #[test]
 fn test_variant_get_barcode_data_destructure() -> Result<()> {
     let mut exp_msg = GetBarcodeDataResponse::new();
@@ -328,6 +373,96 @@ fn test_variant_last_reject_code_destructure() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_variant_payout_by_denomination_destructure() -> Result<()> {
+    let mut exp_msg = PayoutByDenominationResponse::new();
+    exp_msg.calculate_checksum();
+
+    let exp_msg_type = exp_msg.message_type();
+    let var = MessageVariant::from_buf(exp_msg.buf(), exp_msg_type)?;
+
+    assert!(var.is_payout_by_denomination_response());
+    assert_eq!(var.as_payout_by_denomination_response()?, &exp_msg);
+    assert_eq!(var.into_payout_by_denomination_response()?, exp_msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_variant_payout_amount_destructure() -> Result<()> {
+    let mut exp_msg = PayoutAmountResponse::new();
+    exp_msg.calculate_checksum();
+
+    let exp_msg_type = exp_msg.message_type();
+    let var = MessageVariant::from_buf(exp_msg.buf(), exp_msg_type)?;
+
+    assert!(var.is_payout_amount_response());
+    assert_eq!(var.as_payout_amount_response()?, &exp_msg);
+    assert_eq!(var.into_payout_amount_response()?, exp_msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_variant_halt_payout_destructure() -> Result<()> {
+    let mut exp_msg = HaltPayoutResponse::new();
+    exp_msg.calculate_checksum();
+
+    let exp_msg_type = exp_msg.message_type();
+    let var = MessageVariant::from_buf(exp_msg.buf(), exp_msg_type)?;
+
+    assert!(var.is_halt_payout_response());
+    assert_eq!(var.as_halt_payout_response()?, &exp_msg);
+    assert_eq!(var.into_halt_payout_response()?, exp_msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_variant_float_amount_destructure() -> Result<()> {
+    let mut exp_msg = FloatAmountResponse::new();
+    exp_msg.calculate_checksum();
+
+    let exp_msg_type = exp_msg.message_type();
+    let var = MessageVariant::from_buf(exp_msg.buf(), exp_msg_type)?;
+
+    assert!(var.is_float_amount_response());
+    assert_eq!(var.as_float_amount_response()?, &exp_msg);
+    assert_eq!(var.into_float_amount_response()?, exp_msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_variant_get_minimum_payout_destructure() -> Result<()> {
+    let mut exp_msg = GetMinimumPayoutResponse::new();
+    exp_msg.calculate_checksum();
+
+    let exp_msg_type = exp_msg.message_type();
+    let var = MessageVariant::from_buf(exp_msg.buf(), exp_msg_type)?;
+
+    assert!(var.is_get_minimum_payout_response());
+    assert_eq!(var.as_get_minimum_payout_response()?, &exp_msg);
+    assert_eq!(var.into_get_minimum_payout_response()?, exp_msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_variant_float_by_denomination_destructure() -> Result<()> {
+    let mut exp_msg = FloatByDenominationResponse::new();
+    exp_msg.calculate_checksum();
+
+    let exp_msg_type = exp_msg.message_type();
+    let var = MessageVariant::from_buf(exp_msg.buf(), exp_msg_type)?;
+
+    assert!(var.is_float_by_denomination_response());
+    assert_eq!(var.as_float_by_denomination_response()?, &exp_msg);
+    assert_eq!(var.into_float_by_denomination_response()?, exp_msg);
+
+    Ok(())
+}
+
 #[test]
 fn test_variant_poll_destructure() -> Result<()> {
     let mut exp_msg = PollResponse::new();
@@ -388,6 +523,36 @@ fn test_variant_serial_number_destructure() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_variant_set_denomination_level_destructure() -> Result<()> {
+    let mut exp_msg = SetDenominationLevelResponse::new();
+    exp_msg.calculate_checksum();
+
+    let exp_msg_type = exp_msg.message_type();
+    let var = MessageVariant::from_buf(exp_msg.buf(), exp_msg_type)?;
+
+    assert!(var.is_set_denomination_level_response());
+    assert_eq!(var.as_set_denomination_level_response()?, &exp_msg);
+    assert_eq!(var.into_set_denomination_level_response()?, exp_msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_variant_set_denomination_route_destructure() -> Result<()> {
+    let mut exp_msg = SetDenominationRouteResponse::new();
+    exp_msg.calculate_checksum();
+
+    let exp_msg_type = exp_msg.message_type();
+    let var = MessageVariant::from_buf(exp_msg.buf(), exp_msg_type)?;
+
+    assert!(var.is_set_denomination_route_response());
+    assert_eq!(var.as_set_denomination_route_response()?, &exp_msg);
+    assert_eq!(var.into_set_denomination_route_response()?, exp_msg);
+
+    Ok(())
+}
+
 #[test]
 fn test_variant_set_encryption_key_destructure() -> Result<()> {
     let mut exp_msg = SetEncryptionKeyResponse::new();
@@ -0,0 +1,7 @@
+//! Dispense a monetary value to the customer.
+
+mod command;
+mod response;
+
+pub use command::*;
+pub use response::*;
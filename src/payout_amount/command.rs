@@ -0,0 +1,154 @@
+use crate::{
+    impl_command_ops, impl_default, impl_message_from_buf, impl_message_ops, len, std::fmt,
+    CommandOps, CountryCode, MessageOps, MessageType, PayoutOption,
+};
+
+mod index {
+    pub const AMOUNT: usize = 4;
+    pub const AMOUNT_END: usize = 8;
+    pub const CURRENCY: usize = 8;
+    pub const CURRENCY_END: usize = 11;
+    pub const PAYOUT_OPTION: usize = 11;
+}
+
+/// PayoutAmount - Command (0x33)
+///
+/// A command to set the monetary value to be paid by the payout unit.
+///
+/// The payout value is sent as a 4 byte little endian integer of the full penny amount, followed
+/// by the 3 byte ASCII country code of the currency to pay.
+///
+/// The host also adds an option byte to the end of the command array ([`PayoutOption`]).
+/// This will allow a pre-test of the ability to payout the requested amount before actual payout
+/// executes.
+///
+/// ***Uses the command format for Protocol Version 6 or above.***
+///
+/// For request failure, the device responds with
+/// [CommandCannotBeProcessed](crate::ResponseStatus::CommandCannotBeProcessed).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PayoutAmountCommand {
+    buf: [u8; len::PAYOUT_AMOUNT_COMMAND],
+}
+
+impl PayoutAmountCommand {
+    /// Creates a new [PayoutAmountCommand] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::PAYOUT_AMOUNT_COMMAND],
+        };
+
+        msg.init();
+        msg.set_command(MessageType::PayoutAmount);
+        msg.set_payout_option(PayoutOption::default());
+
+        msg
+    }
+
+    /// Gets the amount to payout.
+    pub fn amount(&self) -> u32 {
+        u32::from_le_bytes(
+            self.buf[index::AMOUNT..index::AMOUNT_END]
+                .try_into()
+                .unwrap_or([0; 4]),
+        )
+    }
+
+    /// Sets the amount to payout.
+    pub fn set_amount(&mut self, amount: u32) {
+        self.buf[index::AMOUNT..index::AMOUNT_END].copy_from_slice(amount.to_le_bytes().as_ref());
+    }
+
+    /// Builder function that sets the amount to payout.
+    pub fn with_amount(mut self, amount: u32) -> Self {
+        self.set_amount(amount);
+        self
+    }
+
+    /// Gets the [CountryCode] of the currency to payout.
+    pub fn currency(&self) -> CountryCode {
+        CountryCode::from(&self.buf[index::CURRENCY..index::CURRENCY_END])
+    }
+
+    /// Sets the [CountryCode] of the currency to payout.
+    pub fn set_currency(&mut self, currency: CountryCode) {
+        self.buf[index::CURRENCY..index::CURRENCY_END]
+            .copy_from_slice(<&str>::from(currency).as_bytes());
+    }
+
+    /// Builder function that sets the [CountryCode] of the currency to payout.
+    pub fn with_currency(mut self, currency: CountryCode) -> Self {
+        self.set_currency(currency);
+        self
+    }
+
+    /// Gets the [PayoutOption].
+    pub fn payout_option(&self) -> PayoutOption {
+        self.buf[index::PAYOUT_OPTION].into()
+    }
+
+    /// Sets the [PayoutOption].
+    pub fn set_payout_option(&mut self, option: PayoutOption) {
+        self.buf[index::PAYOUT_OPTION] = option.into();
+    }
+
+    /// Builder function that sets the [PayoutOption].
+    pub fn with_payout_option(mut self, option: PayoutOption) -> Self {
+        self.set_payout_option(option);
+        self
+    }
+}
+
+impl_default!(PayoutAmountCommand);
+impl_message_from_buf!(PayoutAmountCommand);
+impl_message_ops!(PayoutAmountCommand);
+impl_command_ops!(PayoutAmountCommand);
+
+impl fmt::Display for PayoutAmountCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stx = self.stx();
+        let seqid = self.sequence_id();
+        let len = self.data_len();
+        let cmd = self.command();
+        let amount = self.amount();
+        let currency = self.currency();
+        let option = self.payout_option();
+        let crc = self.checksum();
+
+        write!(f, "STX: 0x{stx:02x} | SEQID: {seqid} | LEN: 0x{len:02x} | Command: {cmd} | Amount: {amount} | Currency: {currency} | Payout option: {option} | CRC-16: 0x{crc:04x}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Result;
+
+    #[test]
+    fn test_payout_amount_command() -> Result<()> {
+        // Payout EUR 5.00 in protocol version 6, from the SSP Protocol Manual
+        let exp_buf = [
+            0x7f, 0x80, 0x09, 0x33, 0xf4, 0x01, 0x00, 0x00, 0x45, 0x55, 0x52, 0x58, 0xc3, 0xee,
+        ];
+
+        let mut cmd = PayoutAmountCommand::new()
+            .with_amount(500)
+            .with_currency(CountryCode::EUR)
+            .with_payout_option(PayoutOption::PayoutAmount);
+        cmd.set_sequence_id(crate::SequenceId::from(0x80));
+        cmd.calculate_checksum();
+
+        assert_eq!(cmd.buf(), exp_buf.as_ref());
+        assert_eq!(PayoutAmountCommand::try_from(exp_buf)?, cmd);
+
+        assert_eq!(cmd.amount(), 500);
+        assert_eq!(cmd.currency(), CountryCode::EUR);
+        assert_eq!(cmd.payout_option(), PayoutOption::PayoutAmount);
+
+        cmd.set_payout_option(PayoutOption::TestPayoutAmount);
+        assert_eq!(cmd.payout_option(), PayoutOption::TestPayoutAmount);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{
+    impl_default, impl_message_from_buf, impl_message_ops, impl_response_display,
+    impl_response_ops, len, MessageOps, MessageType,
+};
+
+/// PayoutAmount - Response (0x33)
+///
+/// Represents a response to an [PayoutAmountCommand](crate::PayoutAmountCommand) message.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PayoutAmountResponse {
+    buf: [u8; len::PAYOUT_AMOUNT_RESPONSE],
+}
+
+impl PayoutAmountResponse {
+    /// Creates a new [PayoutAmountResponse] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::PAYOUT_AMOUNT_RESPONSE],
+        };
+
+        msg.init();
+
+        msg
+    }
+}
+
+impl_default!(PayoutAmountResponse);
+impl_message_from_buf!(PayoutAmountResponse);
+impl_message_ops!(PayoutAmountResponse, MessageType::PayoutAmount);
+impl_response_ops!(PayoutAmountResponse);
+impl_response_display!(PayoutAmountResponse);
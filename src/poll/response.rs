@@ -1,7 +1,7 @@
 use crate::{
     impl_default, impl_message_from_buf, impl_response_ops, impl_var_message_ops, len,
-    message::index, std::fmt, MessageOps, MessageType, ResponseOps, ResponseStatus,
-    ResponseStatusList, Vec,
+    message::index, std::fmt, MessageOps, MessageType, PollEventIter, ProtocolVersion, ResponseOps,
+    ResponseStatus, ResponseStatusList, Vec,
 };
 
 /// Poll - Response (0x7F)
@@ -35,6 +35,19 @@ impl PollResponse {
             .collect::<Vec<_>>()
             .into()
     }
+
+    /// Gets an iterator over the typed events since the last [PollCommand](crate::PollCommand).
+    ///
+    /// Event lengths depend on the [ProtocolVersion] negotiated with the device.
+    pub fn events(&self, protocol: ProtocolVersion) -> PollEventIter<'_> {
+        let events_start = index::DATA + 1;
+        let events_end = len::HEADER + self.data_len();
+
+        PollEventIter::new(
+            self.buf.get(events_start..events_end).unwrap_or_default(),
+            protocol,
+        )
+    }
 }
 
 impl_default!(PollResponse);
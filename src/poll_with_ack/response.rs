@@ -1,7 +1,7 @@
 use crate::{
     impl_default, impl_message_from_buf, impl_response_ops, impl_var_message_ops, len,
-    message::index, std::fmt, MessageOps, MessageType, ResponseOps, ResponseStatus,
-    ResponseStatusList, Vec,
+    message::index, std::fmt, MessageOps, MessageType, PollEventIter, ProtocolVersion, ResponseOps,
+    ResponseStatus, ResponseStatusList, Vec,
 };
 
 /// PollWithAck - Response (0x56)
@@ -35,6 +35,25 @@ impl PollWithAckResponse {
             .collect::<Vec<_>>()
             .into()
     }
+
+    /// Gets an iterator over the typed events since the last [PollWithAckCommand](crate::PollWithAckCommand).
+    ///
+    /// Events requiring an ACK are repeated by the device until an
+    /// [EventAckCommand](crate::EventAckCommand) is received. See [EventAckTracker](crate::EventAckTracker)
+    /// for filtering repeated events.
+    ///
+    /// Event lengths depend on the [ProtocolVersion] negotiated with the device.
+    pub fn events(&self, protocol: ProtocolVersion) -> PollEventIter<'_> {
+        PollEventIter::new(self.event_data(), protocol)
+    }
+
+    /// Gets the raw event data, excluding the leading [ResponseStatus].
+    pub fn event_data(&self) -> &[u8] {
+        let events_start = index::DATA + 1;
+        let events_end = len::HEADER + self.data_len();
+
+        self.buf.get(events_start..events_end).unwrap_or_default()
+    }
 }
 
 impl_default!(PollWithAckResponse);
@@ -69,6 +69,19 @@ impl Generator {
         }
     }
 
+    /// Generates a new random number from the CSPRNG.
+    pub fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    /// Tests whether the `candidate` is (probably) prime.
+    ///
+    /// Runs trial division by small primes, then the Fermat and Miller-Rabin probabilistic tests,
+    /// with random bases from the CSPRNG.
+    pub fn is_probable_prime(&mut self, candidate: u64) -> bool {
+        Self::is_prime(candidate, &mut self.rng)
+    }
+
     /// Sets the CSPRNG seed to a new value.
     pub fn set_seed(&mut self, seed: Seed) {
         self.seed = seed;
@@ -76,6 +89,13 @@ impl Generator {
     }
 
     fn is_prime(candidate: u64, rng: &mut ChaCha20Rng) -> bool {
+        let largest_small = SMALL_PRIMES[SMALL_PRIMES.len() - 1];
+
+        // small candidates leave no room for the random test bases
+        if candidate <= u64::from(largest_small) {
+            return SMALL_PRIMES.binary_search(&(candidate as u32)).is_ok();
+        }
+
         candidate != ZERO
             && (Self::is_odd(candidate) || candidate == TWO)
             && Self::div_small_primes(candidate)
@@ -187,3 +207,25 @@ impl Generator {
         (d, s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_probable_prime() {
+        let mut gen = Generator::from_seed([0x5a; 32]);
+
+        for prime in [2, 3, 5, 17863, 0x7fcc_9ee3, 0x7f1c_7181, 0xffff_ffff_ffff_ffc5] {
+            assert!(gen.is_probable_prime(prime), "{prime} is prime");
+        }
+
+        // 3_215_031_751 = 151 * 751 * 28_351 is a strong pseudoprime to bases 2, 3, 5 and 7
+        for composite in [0, 1, 4, 17865, 0x7fcc_9ee1, 4_294_967_297, 3_215_031_751] {
+            assert!(!gen.is_probable_prime(composite), "{composite} is composite");
+        }
+
+        let prime = gen.new_prime();
+        assert!(gen.is_probable_prime(prime));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    impl_command_ops, impl_default, impl_message_from_buf, impl_message_ops, len, std::fmt,
+    ChannelValue, CommandOps, CountryCode, MessageOps, MessageType,
+};
+
+mod index {
+    pub const COUNT: usize = 4;
+    pub const COUNT_END: usize = 6;
+    pub const VALUE: usize = 6;
+    pub const VALUE_END: usize = 10;
+    pub const CURRENCY: usize = 10;
+    pub const CURRENCY_END: usize = 13;
+}
+
+/// SetDenominationLevel - Command (0x34)
+///
+/// A command to increment the level of a denomination stored in the payout device.
+///
+/// The number to add is sent as a 2 byte little endian integer, the value of the denomination as
+/// a 4 byte little endian integer, and the country code as a 3 byte ASCII array.
+///
+/// The level of a denomination can be set to zero by sending a zero count for that value.
+///
+/// ***Uses the command format for Protocol Version 6 or above.***
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SetDenominationLevelCommand {
+    buf: [u8; len::SET_DENOMINATION_LEVEL_COMMAND],
+}
+
+impl SetDenominationLevelCommand {
+    /// Creates a new [SetDenominationLevelCommand] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::SET_DENOMINATION_LEVEL_COMMAND],
+        };
+
+        msg.init();
+        msg.set_command(MessageType::SetDenominationLevel);
+
+        msg
+    }
+
+    /// Gets the number of notes to add to the level.
+    pub fn count(&self) -> u16 {
+        u16::from_le_bytes(
+            self.buf[index::COUNT..index::COUNT_END]
+                .try_into()
+                .unwrap_or([0; 2]),
+        )
+    }
+
+    /// Sets the number of notes to add to the level.
+    ///
+    /// A zero count clears the level.
+    pub fn set_count(&mut self, count: u16) {
+        self.buf[index::COUNT..index::COUNT_END].copy_from_slice(count.to_le_bytes().as_ref());
+    }
+
+    /// Builder function that sets the number of notes to add to the level.
+    pub fn with_count(mut self, count: u16) -> Self {
+        self.set_count(count);
+        self
+    }
+
+    /// Gets the denomination [ChannelValue].
+    pub fn value(&self) -> ChannelValue {
+        self.buf[index::VALUE..index::VALUE_END].into()
+    }
+
+    /// Sets the denomination [ChannelValue].
+    pub fn set_value(&mut self, value: ChannelValue) {
+        self.buf[index::VALUE..index::VALUE_END]
+            .copy_from_slice(value.as_inner().to_le_bytes().as_ref());
+    }
+
+    /// Builder function that sets the denomination [ChannelValue].
+    pub fn with_value(mut self, value: ChannelValue) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Gets the denomination [CountryCode].
+    pub fn currency(&self) -> CountryCode {
+        CountryCode::from(&self.buf[index::CURRENCY..index::CURRENCY_END])
+    }
+
+    /// Sets the denomination [CountryCode].
+    pub fn set_currency(&mut self, currency: CountryCode) {
+        self.buf[index::CURRENCY..index::CURRENCY_END]
+            .copy_from_slice(<&str>::from(currency).as_bytes());
+    }
+
+    /// Builder function that sets the denomination [CountryCode].
+    pub fn with_currency(mut self, currency: CountryCode) -> Self {
+        self.set_currency(currency);
+        self
+    }
+}
+
+impl_default!(SetDenominationLevelCommand);
+impl_message_from_buf!(SetDenominationLevelCommand);
+impl_message_ops!(SetDenominationLevelCommand);
+impl_command_ops!(SetDenominationLevelCommand);
+
+impl fmt::Display for SetDenominationLevelCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stx = self.stx();
+        let seqid = self.sequence_id();
+        let len = self.data_len();
+        let cmd = self.command();
+        let count = self.count();
+        let value = self.value();
+        let currency = self.currency();
+        let crc = self.checksum();
+
+        write!(f, "STX: 0x{stx:02x} | SEQID: {seqid} | LEN: 0x{len:02x} | Command: {cmd} | Count: {count} | Value: {value} | Currency: {currency} | CRC-16: 0x{crc:04x}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Result, SequenceId};
+
+    #[test]
+    fn test_set_denomination_level_command() -> Result<()> {
+        // Increase the level of EUR 1.00 by 12, from the SSP Protocol Manual
+        let exp_buf = [
+            0x7f, 0x80, 0x0a, 0x34, 0x0c, 0x00, 0x64, 0x00, 0x00, 0x00, 0x45, 0x55, 0x52, 0xc7,
+            0x28,
+        ];
+
+        let mut cmd = SetDenominationLevelCommand::new()
+            .with_count(12)
+            .with_value(ChannelValue::from(100))
+            .with_currency(CountryCode::EUR);
+        cmd.set_sequence_id(SequenceId::from(0x80));
+        cmd.calculate_checksum();
+
+        assert_eq!(cmd.buf(), exp_buf.as_ref());
+        assert_eq!(SetDenominationLevelCommand::try_from(exp_buf)?, cmd);
+
+        Ok(())
+    }
+}
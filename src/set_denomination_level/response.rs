@@ -0,0 +1,35 @@
+use crate::{
+    impl_default, impl_message_from_buf, impl_message_ops, impl_response_display,
+    impl_response_ops, len, MessageOps, MessageType,
+};
+
+/// SetDenominationLevel - Response (0x34)
+///
+/// Represents a response to an [SetDenominationLevelCommand](crate::SetDenominationLevelCommand) message.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SetDenominationLevelResponse {
+    buf: [u8; len::SET_DENOMINATION_LEVEL_RESPONSE],
+}
+
+impl SetDenominationLevelResponse {
+    /// Creates a new [SetDenominationLevelResponse] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::SET_DENOMINATION_LEVEL_RESPONSE],
+        };
+
+        msg.init();
+
+        msg
+    }
+}
+
+impl_default!(SetDenominationLevelResponse);
+impl_message_from_buf!(SetDenominationLevelResponse);
+impl_message_ops!(
+    SetDenominationLevelResponse,
+    MessageType::SetDenominationLevel
+);
+impl_response_ops!(SetDenominationLevelResponse);
+impl_response_display!(SetDenominationLevelResponse);
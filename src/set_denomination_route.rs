@@ -0,0 +1,7 @@
+//! Route a denomination to the payout device, or the cashbox.
+
+mod command;
+mod response;
+
+pub use command::*;
+pub use response::*;
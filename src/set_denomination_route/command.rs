@@ -0,0 +1,139 @@
+use crate::{
+    impl_command_ops, impl_default, impl_message_from_buf, impl_message_ops, len, std::fmt,
+    ChannelValue, CommandOps, CountryCode, DenominationRoute, MessageOps, MessageType,
+};
+
+mod index {
+    pub const ROUTE: usize = 4;
+    pub const VALUE: usize = 5;
+    pub const VALUE_END: usize = 9;
+    pub const CURRENCY: usize = 9;
+    pub const CURRENCY_END: usize = 12;
+}
+
+/// SetDenominationRoute - Command (0x3B)
+///
+/// A command to configure a denomination to be either routed to the cashbox on detection, or
+/// stored to be made available for later possible payout.
+///
+/// The [DenominationRoute] is sent as a single byte, followed by the value of the denomination as
+/// a 4 byte little endian integer, and the 3 byte ASCII country code.
+///
+/// ***Uses the command format for Protocol Version 6 or above.***
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SetDenominationRouteCommand {
+    buf: [u8; len::SET_DENOMINATION_ROUTE_COMMAND],
+}
+
+impl SetDenominationRouteCommand {
+    /// Creates a new [SetDenominationRouteCommand] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::SET_DENOMINATION_ROUTE_COMMAND],
+        };
+
+        msg.init();
+        msg.set_command(MessageType::SetDenominationRoute);
+
+        msg
+    }
+
+    /// Gets the [DenominationRoute].
+    pub fn route(&self) -> DenominationRoute {
+        self.buf[index::ROUTE].into()
+    }
+
+    /// Sets the [DenominationRoute].
+    pub fn set_route(&mut self, route: DenominationRoute) {
+        self.buf[index::ROUTE] = route.into();
+    }
+
+    /// Builder function that sets the [DenominationRoute].
+    pub fn with_route(mut self, route: DenominationRoute) -> Self {
+        self.set_route(route);
+        self
+    }
+
+    /// Gets the denomination [ChannelValue].
+    pub fn value(&self) -> ChannelValue {
+        self.buf[index::VALUE..index::VALUE_END].into()
+    }
+
+    /// Sets the denomination [ChannelValue].
+    pub fn set_value(&mut self, value: ChannelValue) {
+        self.buf[index::VALUE..index::VALUE_END]
+            .copy_from_slice(value.as_inner().to_le_bytes().as_ref());
+    }
+
+    /// Builder function that sets the denomination [ChannelValue].
+    pub fn with_value(mut self, value: ChannelValue) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Gets the denomination [CountryCode].
+    pub fn currency(&self) -> CountryCode {
+        CountryCode::from(&self.buf[index::CURRENCY..index::CURRENCY_END])
+    }
+
+    /// Sets the denomination [CountryCode].
+    pub fn set_currency(&mut self, currency: CountryCode) {
+        self.buf[index::CURRENCY..index::CURRENCY_END]
+            .copy_from_slice(<&str>::from(currency).as_bytes());
+    }
+
+    /// Builder function that sets the denomination [CountryCode].
+    pub fn with_currency(mut self, currency: CountryCode) -> Self {
+        self.set_currency(currency);
+        self
+    }
+}
+
+impl_default!(SetDenominationRouteCommand);
+impl_message_from_buf!(SetDenominationRouteCommand);
+impl_message_ops!(SetDenominationRouteCommand);
+impl_command_ops!(SetDenominationRouteCommand);
+
+impl fmt::Display for SetDenominationRouteCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stx = self.stx();
+        let seqid = self.sequence_id();
+        let len = self.data_len();
+        let cmd = self.command();
+        let route = self.route();
+        let value = self.value();
+        let currency = self.currency();
+        let crc = self.checksum();
+
+        write!(f, "STX: 0x{stx:02x} | SEQID: {seqid} | LEN: 0x{len:02x} | Command: {cmd} | Route: {route} | Value: {value} | Currency: {currency} | CRC-16: 0x{crc:04x}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Result, SequenceId};
+
+    #[test]
+    fn test_set_denomination_route_command() -> Result<()> {
+        // Route EUR 0.10 to be stored for payout, from the SSP Protocol Manual
+        let exp_buf = [
+            0x7f, 0x80, 0x09, 0x3b, 0x00, 0x0a, 0x00, 0x00, 0x00, 0x45, 0x55, 0x52, 0x08, 0x43,
+        ];
+
+        let mut cmd = SetDenominationRouteCommand::new()
+            .with_route(DenominationRoute::Payout)
+            .with_value(ChannelValue::from(10))
+            .with_currency(CountryCode::EUR);
+        cmd.set_sequence_id(SequenceId::from(0x80));
+        cmd.calculate_checksum();
+
+        assert_eq!(cmd.buf(), exp_buf.as_ref());
+
+        cmd.set_route(DenominationRoute::Cashbox);
+        assert_eq!(cmd.route(), DenominationRoute::Cashbox);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{
+    impl_default, impl_message_from_buf, impl_message_ops, impl_response_display,
+    impl_response_ops, len, MessageOps, MessageType,
+};
+
+/// SetDenominationRoute - Response (0x3B)
+///
+/// Represents a response to an [SetDenominationRouteCommand](crate::SetDenominationRouteCommand) message.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SetDenominationRouteResponse {
+    buf: [u8; len::SET_DENOMINATION_ROUTE_RESPONSE],
+}
+
+impl SetDenominationRouteResponse {
+    /// Creates a new [SetDenominationRouteResponse] message.
+    pub fn new() -> Self {
+        let mut msg = Self {
+            buf: [0u8; len::SET_DENOMINATION_ROUTE_RESPONSE],
+        };
+
+        msg.init();
+
+        msg
+    }
+}
+
+impl_default!(SetDenominationRouteResponse);
+impl_message_from_buf!(SetDenominationRouteResponse);
+impl_message_ops!(
+    SetDenominationRouteResponse,
+    MessageType::SetDenominationRoute
+);
+impl_response_ops!(SetDenominationRouteResponse);
+impl_response_display!(SetDenominationRouteResponse);
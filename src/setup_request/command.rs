@@ -1,5 +1,5 @@
 use crate::{
-    impl_command_display, impl_command_ops, impl_default, impl_message_ops,
+    impl_command_display, impl_command_ops, impl_default, impl_message_from_buf, impl_message_ops,
     len::SETUP_REQUEST_COMMAND, CommandOps, MessageOps, MessageType,
 };
 
@@ -28,6 +28,7 @@ impl SetupRequestCommand {
 
 impl_default!(SetupRequestCommand);
 impl_command_display!(SetupRequestCommand);
+impl_message_from_buf!(SetupRequestCommand);
 impl_message_ops!(SetupRequestCommand);
 impl_command_ops!(SetupRequestCommand);
 
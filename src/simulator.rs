@@ -0,0 +1,1123 @@
+//! Software model of an NV200 banknote validator, for testing host code without hardware.
+//!
+//! The [Simulator] parses incoming command frames with the same `*Command` types used by the
+//! host, and answers with `*Response` frames, encrypting them when the command was encrypted.
+//!
+//! Scripted hooks inject notes, jams, fraud attempts and cashbox removal, which are reported to
+//! the host in the following poll replies:
+//!
+//! ```rust
+//! # fn main() -> ssp::Result<()> {
+//! use ssp::simulator::{Simulator, SimulatorTransport};
+//! use ssp::{Device, Method};
+//!
+//! let transport = SimulatorTransport::new(Simulator::new());
+//! let simulator = transport.simulator();
+//!
+//! let mut device = Device::new(transport);
+//! device.sync()?;
+//! device.enable()?;
+//!
+//! simulator.lock().unwrap().insert_note(1);
+//!
+//! let event = device.poll()?.events(device.protocol_version()).next().unwrap()?;
+//! assert_eq!(event.method(), Method::Read);
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::{
+    crc::crc16, len, message::index, ChannelValueDataResponse, DisableResponse, DisplayOffResponse,
+    DisplayOnResponse, EnableResponse, EncryptedCommand, EncryptedResponse, Error, EsspSession,
+    EventAckResponse, EventPayload, HoldResponse, HostProtocolVersionCommand,
+    HostProtocolVersionResponse, KeyExchangeResponder, LastRejectCode, LastRejectCodeResponse,
+    MessageOps, MessageType, MessageVariant, PollResponse, PollWithAckResponse, ProtocolVersion,
+    RejectResponse, RequestKeyExchangeCommand, ResponseOps, ResponseStatus, Result, SequenceId,
+    SerialNumber, SerialNumberResponse, SetGeneratorCommand, SetInhibitsCommand,
+    SetInhibitsResponse, SetModulusCommand, SetupRequestResponse, SyncResponse, UnitDataResponse,
+    WrappedEncryptedMessage, STX,
+};
+
+#[cfg(unix)]
+mod pty;
+mod transport;
+
+#[cfg(unix)]
+pub use pty::*;
+pub use transport::*;
+
+/// Default channel values of the simulated device, in whole currency units.
+pub const DEFAULT_CHANNELS: [u32; 7] = [5, 10, 20, 50, 100, 200, 500];
+/// Default country code of the simulated device.
+pub const DEFAULT_COUNTRY: [u8; 3] = *b"EUR";
+/// Default serial number of the simulated device.
+pub const DEFAULT_SERIAL_NUMBER: u32 = 0x0102_0304;
+
+// NV200 unit type
+const UNIT_TYPE: u8 = 0x00;
+const FIRMWARE_VERSION: [u8; 4] = *b"0400";
+// channel values are in whole units, and the real value multiplier converts them to cents
+const VALUE_MULTIPLIER: [u8; 3] = [0, 0, 1];
+const REAL_VALUE_MULTIPLIER: [u8; 3] = [0, 0, 100];
+const SECURITY_LEVEL: u8 = 2;
+
+/// Progress of a banknote through the simulated note path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NoteState {
+    Idle,
+    /// Note held in escrow, reported by a `Read` event with its channel.
+    Escrow(u8),
+    /// Note credited, and moving into the stacker.
+    Stacking(u8),
+    /// Note rejected by the host, to be returned to the user.
+    Reject,
+    /// Note being returned to the user.
+    Rejecting,
+}
+
+/// Software model of an NV200 banknote validator.
+///
+/// Handles one un-stuffed command frame at a time with [Simulator::handle_frame], and returns
+/// the reply frame. Plug it into a host [Device](crate::Device) with a [SimulatorTransport], or
+/// serve it over a byte stream with [serve].
+///
+/// The model keeps the device state the host can observe:
+///
+/// - enabled/disabled, and the channel inhibits
+/// - the note in escrow, and the notes stored in the cashbox
+/// - the eSSP key negotiated with `SetGenerator`, `SetModulus` and `RequestKeyExchange`
+/// - the [SequenceFlag](crate::SequenceFlag) of the last command, so re-transmitted commands get
+///   the previous reply instead of being executed twice
+pub struct Simulator {
+    address: u8,
+    protocol: ProtocolVersion,
+    channels: Vec<u32>,
+    country: [u8; 3],
+    serial_number: u32,
+    enabled: bool,
+    inhibits: Vec<u8>,
+    note: NoteState,
+    held: bool,
+    inserted: VecDeque<u8>,
+    events: Vec<u8>,
+    unacked: Option<Vec<u8>>,
+    stored: Vec<u32>,
+    cashbox_present: bool,
+    jammed: bool,
+    last_reject: LastRejectCode,
+    key_exchange: KeyExchangeResponder,
+    session: Option<EsspSession>,
+    last_reply: Option<(u8, Vec<u8>)>,
+}
+
+impl Simulator {
+    /// Creates a new [Simulator] with the default channels, as if just powered up.
+    pub fn new() -> Self {
+        Self::with_channels(DEFAULT_CHANNELS.as_ref())
+    }
+
+    /// Creates a new [Simulator] with the provided channel values, in whole currency units.
+    ///
+    /// At most [MAX_CHANNELS](crate::MAX_CHANNELS) are used.
+    pub fn with_channels(channels: &[u32]) -> Self {
+        let channels: Vec<u32> = channels.iter().copied().take(crate::MAX_CHANNELS).collect();
+        let num_channels = channels.len();
+
+        Self {
+            address: 0,
+            protocol: ProtocolVersion::Eight,
+            channels,
+            country: DEFAULT_COUNTRY,
+            serial_number: DEFAULT_SERIAL_NUMBER,
+            enabled: false,
+            inhibits: Vec::new(),
+            note: NoteState::Idle,
+            held: false,
+            inserted: VecDeque::new(),
+            events: Vec::new(),
+            unacked: None,
+            stored: vec![0; num_channels],
+            cashbox_present: true,
+            jammed: false,
+            last_reject: LastRejectCode::NoteAccepted,
+            key_exchange: KeyExchangeResponder::from_entropy(),
+            session: None,
+            last_reply: None,
+        }
+    }
+
+    /// Builder function that sets the device address.
+    pub fn with_address(mut self, address: u8) -> Self {
+        self.address = address & 0x7f;
+        self
+    }
+
+    /// Builder function that sets the device country code, e.g. `*b"USD"`.
+    pub fn with_country(mut self, country: [u8; 3]) -> Self {
+        self.country = country;
+        self
+    }
+
+    /// Builder function that sets the device serial number.
+    pub fn with_serial_number(mut self, serial_number: u32) -> Self {
+        self.serial_number = serial_number;
+        self
+    }
+
+    /// Gets the device address.
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Gets the [ProtocolVersion] set by the host.
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol
+    }
+
+    /// Gets the channel values, in whole currency units.
+    pub fn channels(&self) -> &[u32] {
+        self.channels.as_ref()
+    }
+
+    /// Gets whether the host has enabled the device.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Gets whether the host has enabled the (one-indexed) `channel`.
+    pub fn is_channel_enabled(&self, channel: u8) -> bool {
+        let Some(idx) = (channel as usize).checked_sub(1) else {
+            return false;
+        };
+
+        idx < self.channels.len()
+            && self
+                .inhibits
+                .get(idx / 8)
+                .is_some_and(|bits| bits & (1 << (idx % 8)) != 0)
+    }
+
+    /// Gets the channel of the note held in escrow, if any.
+    pub fn escrow(&self) -> Option<u8> {
+        match self.note {
+            NoteState::Escrow(channel) => Some(channel),
+            _ => None,
+        }
+    }
+
+    /// Gets the number of notes stored in the cashbox for each channel.
+    pub fn stored_notes(&self) -> &[u32] {
+        self.stored.as_ref()
+    }
+
+    /// Gets the total value of notes stored in the cashbox, in whole currency units.
+    pub fn stored_value(&self) -> u64 {
+        self.stored
+            .iter()
+            .zip(self.channels.iter())
+            .map(|(&count, &value)| u64::from(count) * u64::from(value))
+            .sum()
+    }
+
+    /// Gets whether an eSSP encryption key has been negotiated.
+    pub fn is_encrypted(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Gets the [LastRejectCode] reported for the last rejected note.
+    pub fn last_reject_code(&self) -> LastRejectCode {
+        self.last_reject
+    }
+
+    /// Inserts a note for the (one-indexed) `channel` into the bezel.
+    ///
+    /// The note is read on the next poll, and held in escrow. The poll after that stacks the
+    /// note, unless the host sends a `Hold` or `Reject` command in between.
+    ///
+    /// Notes for unknown or inhibited channels, and notes inserted while the device cannot accept
+    /// them, are rejected. Notes inserted while another is in the note path wait their turn.
+    pub fn insert_note(&mut self, channel: u8) {
+        self.inserted.push_back(channel);
+    }
+
+    /// Jams the note path.
+    ///
+    /// A note in escrow or stacking is lost in the jam. An `UnsafeJam` event is reported on every
+    /// poll until [Simulator::clear_jam] is called.
+    pub fn jam(&mut self) {
+        self.jammed = true;
+
+        if self.note != NoteState::Idle {
+            self.note = NoteState::Idle;
+            self.held = false;
+        }
+    }
+
+    /// Clears a jam caused by [Simulator::jam].
+    pub fn clear_jam(&mut self) {
+        self.jammed = false;
+    }
+
+    /// Reports an attempt to defraud the device with a note of the (one-indexed) `channel`.
+    pub fn fraud_attempt(&mut self, channel: u8) {
+        self.push_event(ResponseStatus::FraudAttempt, &[channel]);
+        self.last_reject = LastRejectCode::FraudChannelReject;
+    }
+
+    /// Removes the cashbox, along with the stored notes.
+    ///
+    /// A `CashboxRemoved` event is reported on every poll, and notes are rejected, until
+    /// [Simulator::replace_cashbox] is called.
+    ///
+    /// Returns the number of notes removed for each channel.
+    pub fn remove_cashbox(&mut self) -> Vec<u32> {
+        self.cashbox_present = false;
+
+        let removed = self.stored.clone();
+        self.stored.iter_mut().for_each(|count| *count = 0);
+
+        removed
+    }
+
+    /// Replaces an empty cashbox, reported with a `CashboxReplaced` event.
+    pub fn replace_cashbox(&mut self) {
+        if !self.cashbox_present {
+            self.cashbox_present = true;
+            self.push_event(ResponseStatus::CashboxReplaced, &[]);
+        }
+    }
+
+    /// Powers the device off and on.
+    ///
+    /// Clears the host configuration, and the negotiated encryption key. A `DeviceReset` event is
+    /// reported on the next poll. Stored notes are kept.
+    pub fn reset(&mut self) {
+        let stored = core::mem::take(&mut self.stored);
+
+        *self = Self::with_channels(self.channels.as_ref())
+            .with_address(self.address)
+            .with_country(self.country)
+            .with_serial_number(self.serial_number);
+
+        self.stored = stored;
+        self.push_event(ResponseStatus::DeviceReset, &[]);
+    }
+
+    /// Handles an un-stuffed command frame, and returns the un-stuffed reply frame.
+    ///
+    /// Returns `None` for frames a device does not answer: corrupt frames, frames for another
+    /// address, and encrypted commands that fail decryption or have an unexpected count.
+    pub fn handle_frame(&mut self, frame: &[u8]) -> Option<Vec<u8>> {
+        let data = match frame_data(frame) {
+            Ok(data) => data,
+            Err(err) => {
+                log::warn!("Simulator dropping invalid frame: {err}");
+                return None;
+            }
+        };
+
+        let seq_id = frame[index::SEQ_ID];
+        if SequenceId::from(seq_id).id() != self.address {
+            return None;
+        }
+
+        // a repeated sequence flag means the host lost the last reply
+        let is_sync = data.first() == Some(&MessageType::Synchronisation.into());
+        match self.last_reply.as_ref() {
+            Some((last_seq, reply)) if *last_seq == seq_id && !is_sync => {
+                log::debug!("Simulator repeating reply for sequence ID: 0x{seq_id:02x}");
+                return Some(reply.clone());
+            }
+            _ => (),
+        }
+
+        let reply = if data.first() == Some(&MessageType::Encrypted.into()) {
+            self.handle_encrypted(frame)?
+        } else {
+            let res = self.execute(frame);
+            build_frame(seq_id, res.as_response().data())
+        };
+
+        self.last_reply = Some((seq_id, reply.clone()));
+
+        Some(reply)
+    }
+
+    fn handle_encrypted(&mut self, frame: &[u8]) -> Option<Vec<u8>> {
+        let session = self.session.as_mut()?;
+        let seq_id = frame[index::SEQ_ID];
+
+        let mut wrapped = WrappedEncryptedMessage::try_from(frame).ok()?;
+        // decryption expects the encrypted data to be stuffed
+        wrapped.stuff_encrypted_data().ok()?;
+
+        let cmd = match EncryptedCommand::decrypt_verified(session, wrapped) {
+            Ok(cmd) => cmd,
+            Err(err) => {
+                log::warn!("Simulator dropping encrypted command: {err}");
+                return None;
+            }
+        };
+
+        let res = self.execute(build_frame(seq_id, cmd.message_data()).as_ref());
+
+        let mut enc_res = EncryptedResponse::new();
+        enc_res.set_message_data(res.as_response()).ok()?;
+
+        let session = self.session.as_mut()?;
+        let mut wrapped = match enc_res.encrypt(session) {
+            Ok(wrapped) => wrapped,
+            Err(err) => {
+                log::warn!("Simulator dropping encrypted response: {err}");
+                return None;
+            }
+        };
+        // the transport handles byte stuffing for the entire frame
+        wrapped.unstuff_encrypted_data().ok()?;
+        wrapped.set_sequence_id(seq_id.into());
+
+        Some(wrapped.as_bytes().into())
+    }
+
+    fn execute(&mut self, frame: &[u8]) -> MessageVariant {
+        let msg_type = MessageType::from(frame[index::COMMAND]);
+
+        log::debug!("Simulator received {msg_type} command");
+
+        let res = match msg_type {
+            MessageType::Synchronisation => {
+                Ok(ok(MessageVariant::SyncResponse(SyncResponse::new())))
+            }
+            MessageType::Reset => {
+                self.reset();
+                Ok(status_reply(ResponseStatus::Ok))
+            }
+            MessageType::HostProtocolVersion => self.host_protocol_version(frame),
+            MessageType::SetupRequest => self.setup_request(),
+            MessageType::UnitData => self.unit_data(),
+            MessageType::ChannelValueData => self.channel_value_data(),
+            MessageType::SerialNumber => {
+                let mut res = SerialNumberResponse::new();
+                res.set_serial_number(SerialNumber::from(self.serial_number.to_be_bytes()));
+                Ok(ok(MessageVariant::SerialNumberResponse(res)))
+            }
+            MessageType::LastRejectCode => {
+                let mut res = LastRejectCodeResponse::new();
+                res.set_reject_code(self.last_reject);
+                Ok(ok(MessageVariant::LastRejectCodeResponse(res)))
+            }
+            MessageType::SetInhibits => self.set_inhibits(frame),
+            MessageType::Enable => {
+                self.enabled = true;
+                Ok(ok(MessageVariant::EnableResponse(EnableResponse::new())))
+            }
+            MessageType::Disable => {
+                self.enabled = false;
+                Ok(ok(MessageVariant::DisableResponse(DisableResponse::new())))
+            }
+            MessageType::DisplayOn => Ok(ok(MessageVariant::DisplayOnResponse(
+                DisplayOnResponse::new(),
+            ))),
+            MessageType::DisplayOff => Ok(ok(MessageVariant::DisplayOffResponse(
+                DisplayOffResponse::new(),
+            ))),
+            MessageType::Hold => self.hold(),
+            MessageType::Reject => {
+                if matches!(self.note, NoteState::Escrow(_)) {
+                    self.reject_note(NoteState::Reject, LastRejectCode::HostRejectedNote);
+                }
+                Ok(ok(MessageVariant::RejectResponse(RejectResponse::new())))
+            }
+            MessageType::Poll => Ok(self.poll()),
+            MessageType::PollWithAck => Ok(self.poll_with_ack()),
+            MessageType::EventAck => {
+                self.unacked = None;
+                Ok(ok(
+                    MessageVariant::EventAckResponse(EventAckResponse::new()),
+                ))
+            }
+            MessageType::SetGenerator => self.set_generator(frame),
+            MessageType::SetModulus => self.set_modulus(frame),
+            MessageType::RequestKeyExchange => self.request_key_exchange(frame),
+            _ => Err(ResponseStatus::CommandNotKnown),
+        };
+
+        res.unwrap_or_else(|status| {
+            log::debug!("Simulator replying to {msg_type} command with status: {status}");
+            status_reply(status)
+        })
+    }
+
+    fn host_protocol_version(
+        &mut self,
+        frame: &[u8],
+    ) -> core::result::Result<MessageVariant, ResponseStatus> {
+        let cmd = HostProtocolVersionCommand::try_from(frame)
+            .map_err(|_| ResponseStatus::WrongNumberParameters)?;
+
+        let version = cmd.version().to_u8();
+
+        if (ProtocolVersion::One.to_u8()..=ProtocolVersion::Eight.to_u8()).contains(&version) {
+            self.protocol = cmd.version();
+            Ok(ok(MessageVariant::HostProtocolVersionResponse(
+                HostProtocolVersionResponse::new(),
+            )))
+        } else {
+            Err(ResponseStatus::Fail)
+        }
+    }
+
+    fn setup_request(&self) -> core::result::Result<MessageVariant, ResponseStatus> {
+        let protocol = self.protocol.to_u8();
+        let num_channels = self.channels.len();
+
+        let mut data = vec![ResponseStatus::Ok.to_u8(), UNIT_TYPE];
+        data.extend_from_slice(FIRMWARE_VERSION.as_ref());
+        data.extend_from_slice(self.country.as_ref());
+        data.extend_from_slice(VALUE_MULTIPLIER.as_ref());
+        data.push(num_channels as u8);
+        data.extend(self.channels.iter().map(|&v| v.min(u8::MAX.into()) as u8));
+        data.extend(self.channels.iter().map(|_| SECURITY_LEVEL));
+        data.extend_from_slice(REAL_VALUE_MULTIPLIER.as_ref());
+        data.push(protocol);
+
+        if protocol >= ProtocolVersion::Six.to_u8() {
+            for _ in 0..num_channels {
+                data.extend_from_slice(self.country.as_ref());
+            }
+            for value in self.channels.iter() {
+                data.extend_from_slice(value.to_le_bytes().as_ref());
+            }
+        }
+
+        let mut res = SetupRequestResponse::new();
+        res.set_data(data.as_ref())
+            .map_err(|_| ResponseStatus::CommandCannotBeProcessed)?;
+
+        Ok(MessageVariant::SetupRequestResponse(res))
+    }
+
+    fn unit_data(&self) -> core::result::Result<MessageVariant, ResponseStatus> {
+        let mut data = vec![ResponseStatus::Ok.to_u8(), UNIT_TYPE];
+        data.extend_from_slice(FIRMWARE_VERSION.as_ref());
+        data.extend_from_slice(self.country.as_ref());
+        data.extend_from_slice(VALUE_MULTIPLIER.as_ref());
+        data.push(self.protocol.to_u8());
+
+        let mut res = UnitDataResponse::new();
+        res.set_data(data.as_ref())
+            .map_err(|_| ResponseStatus::CommandCannotBeProcessed)?;
+
+        Ok(MessageVariant::UnitDataResponse(res))
+    }
+
+    fn channel_value_data(&self) -> core::result::Result<MessageVariant, ResponseStatus> {
+        let mut data = vec![ResponseStatus::Ok.to_u8(), self.channels.len() as u8];
+        data.extend(self.channels.iter().map(|&v| v.min(u8::MAX.into()) as u8));
+
+        let mut res = ChannelValueDataResponse::new();
+        res.set_data(data.as_ref())
+            .map_err(|_| ResponseStatus::CommandCannotBeProcessed)?;
+
+        Ok(MessageVariant::ChannelValueDataResponse(res))
+    }
+
+    fn set_inhibits(
+        &mut self,
+        frame: &[u8],
+    ) -> core::result::Result<MessageVariant, ResponseStatus> {
+        let cmd = SetInhibitsCommand::try_from(frame)
+            .map_err(|_| ResponseStatus::WrongNumberParameters)?;
+
+        // skip the command byte
+        self.inhibits = cmd.data()[1..].into();
+
+        Ok(ok(MessageVariant::SetInhibitsResponse(
+            SetInhibitsResponse::new(),
+        )))
+    }
+
+    fn hold(&mut self) -> core::result::Result<MessageVariant, ResponseStatus> {
+        if matches!(self.note, NoteState::Escrow(_)) {
+            self.held = true;
+            Ok(ok(MessageVariant::HoldResponse(HoldResponse::new())))
+        } else {
+            Err(ResponseStatus::CommandCannotBeProcessed)
+        }
+    }
+
+    fn set_generator(
+        &mut self,
+        frame: &[u8],
+    ) -> core::result::Result<MessageVariant, ResponseStatus> {
+        let cmd = SetGeneratorCommand::try_from(frame)
+            .map_err(|_| ResponseStatus::WrongNumberParameters)?;
+
+        self.key_exchange
+            .set_generator(&cmd)
+            .map(MessageVariant::SetGeneratorResponse)
+            .map_err(key_exchange_status)
+    }
+
+    fn set_modulus(
+        &mut self,
+        frame: &[u8],
+    ) -> core::result::Result<MessageVariant, ResponseStatus> {
+        let cmd = SetModulusCommand::try_from(frame)
+            .map_err(|_| ResponseStatus::WrongNumberParameters)?;
+
+        self.key_exchange
+            .set_modulus(&cmd)
+            .map(MessageVariant::SetModulusResponse)
+            .map_err(key_exchange_status)
+    }
+
+    fn request_key_exchange(
+        &mut self,
+        frame: &[u8],
+    ) -> core::result::Result<MessageVariant, ResponseStatus> {
+        let cmd = RequestKeyExchangeCommand::try_from(frame)
+            .map_err(|_| ResponseStatus::WrongNumberParameters)?;
+
+        let res = self
+            .key_exchange
+            .request_key_exchange(&cmd)
+            .map_err(key_exchange_status)?;
+
+        self.session = self.key_exchange.session();
+
+        Ok(MessageVariant::RequestKeyExchangeResponse(res))
+    }
+
+    fn poll(&mut self) -> MessageVariant {
+        let events = self.next_events();
+        let events = self.split_events(events);
+
+        MessageVariant::PollResponse(poll_reply(PollResponse::new(), events.as_ref()))
+    }
+
+    fn poll_with_ack(&mut self) -> MessageVariant {
+        // un-ACKed events are repeated, and the note path waits for the ACK
+        let events = match self.unacked.as_ref() {
+            Some(events) => events.clone(),
+            None => {
+                let events = self.next_events();
+                let events = self.split_events(events);
+                if !events.is_empty() {
+                    self.unacked = Some(events.clone());
+                }
+                events
+            }
+        };
+
+        MessageVariant::PollWithAckResponse(poll_reply(PollWithAckResponse::new(), events.as_ref()))
+    }
+
+    /// Advances the note path, and collects the events reported by a poll.
+    fn next_events(&mut self) -> Vec<u8> {
+        let mut events = core::mem::take(&mut self.events);
+
+        match self.note {
+            NoteState::Idle => {
+                if let Some(channel) = self.inserted.pop_front() {
+                    self.read_note(channel, &mut events);
+                }
+            }
+            NoteState::Escrow(channel) if self.held => {
+                self.held = false;
+                events.extend_from_slice(&[ResponseStatus::Read.to_u8(), channel]);
+            }
+            NoteState::Escrow(channel) => {
+                events.extend_from_slice(&[
+                    ResponseStatus::NoteCredit.to_u8(),
+                    channel,
+                    ResponseStatus::Stacking.to_u8(),
+                ]);
+                self.note = NoteState::Stacking(channel);
+            }
+            NoteState::Stacking(channel) => {
+                events.push(ResponseStatus::Stacked.to_u8());
+                if let Some(count) = self.stored.get_mut(channel as usize - 1) {
+                    *count = count.saturating_add(1);
+                }
+                self.note = NoteState::Idle;
+            }
+            NoteState::Reject => {
+                events.push(ResponseStatus::Rejecting.to_u8());
+                self.note = NoteState::Rejecting;
+            }
+            NoteState::Rejecting => {
+                events.push(ResponseStatus::Rejected.to_u8());
+                self.note = NoteState::Idle;
+            }
+        }
+
+        if self.jammed {
+            events.push(ResponseStatus::UnsafeJam.to_u8());
+        }
+        if !self.cashbox_present {
+            events.push(ResponseStatus::CashboxRemoved.to_u8());
+        }
+        if !self.enabled {
+            events.push(ResponseStatus::Disabled.to_u8());
+        }
+
+        events
+    }
+
+    /// Splits the `events` at the last event boundary that fits in a poll reply.
+    ///
+    /// Events that do not fit are kept for the next poll.
+    fn split_events(&mut self, mut events: Vec<u8>) -> Vec<u8> {
+        // the reply status takes the first byte of the data field
+        let max_len = if self.session.is_some() {
+            len::MAX_ENCRYPTED_DATA - 1
+        } else {
+            len::MAX_DATA - 1
+        };
+
+        let mut split = 0;
+
+        while split < events.len() {
+            match EventPayload::poll_event_len(&events[split..], self.protocol) {
+                Ok(event_len) if split + event_len > events.len() => {
+                    log::warn!("Simulator dropping truncated poll event");
+                    events.truncate(split);
+                }
+                Ok(event_len) if split + event_len <= max_len => split += event_len,
+                // an event that never fits would block the events after it
+                Ok(event_len) if split == 0 => {
+                    log::warn!("Simulator dropping poll event too long for a reply: {event_len}");
+                    events.drain(..event_len);
+                }
+                Ok(_) => break,
+                Err(err) => {
+                    log::warn!("Simulator dropping invalid poll events: {err}");
+                    events.truncate(split);
+                }
+            }
+        }
+
+        // the remaining events are reported before any newer events
+        let mut rest = events.split_off(split);
+        rest.append(&mut self.events);
+        self.events = rest;
+
+        events
+    }
+
+    fn read_note(&mut self, channel: u8, events: &mut Vec<u8>) {
+        let reject = if !self.enabled || self.jammed || !self.cashbox_present {
+            Some(LastRejectCode::ValidatorDisabled)
+        } else if channel == 0 || channel as usize > self.channels.len() {
+            Some(LastRejectCode::InvalidNote)
+        } else if !self.is_channel_enabled(channel) {
+            Some(LastRejectCode::ChannelInhibited)
+        } else {
+            None
+        };
+
+        match reject {
+            Some(code) => {
+                events.extend_from_slice(&[
+                    ResponseStatus::Read.to_u8(),
+                    0,
+                    ResponseStatus::Rejecting.to_u8(),
+                ]);
+                self.reject_note(NoteState::Rejecting, code);
+            }
+            None => {
+                events.extend_from_slice(&[ResponseStatus::Read.to_u8(), channel]);
+                self.note = NoteState::Escrow(channel);
+                self.last_reject = LastRejectCode::NoteAccepted;
+            }
+        }
+    }
+
+    fn reject_note(&mut self, state: NoteState, code: LastRejectCode) {
+        self.note = state;
+        self.held = false;
+        self.last_reject = code;
+    }
+
+    fn push_event(&mut self, status: ResponseStatus, data: &[u8]) {
+        self.events.push(status.to_u8());
+        self.events.extend_from_slice(data);
+    }
+}
+
+impl Default for Simulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn ok(mut res: MessageVariant) -> MessageVariant {
+    res.as_response_mut()
+        .set_response_status(ResponseStatus::Ok);
+    res
+}
+
+/// Creates a reply with only a [ResponseStatus].
+///
+/// Every status-only reply has the same layout as a [SyncResponse].
+fn status_reply(status: ResponseStatus) -> MessageVariant {
+    let mut res = SyncResponse::new();
+    res.set_response_status(status);
+    MessageVariant::SyncResponse(res)
+}
+
+/// Creates a poll reply with the `events`, already split to fit in the reply.
+fn poll_reply<M: ResponseOps>(mut res: M, events: &[u8]) -> M {
+    res.set_data_len(1 + events.len() as u8);
+    res.set_response_status(ResponseStatus::Ok);
+    res.buf_mut()[index::DATA + 1..index::DATA + 1 + events.len()].copy_from_slice(events);
+
+    res
+}
+
+/// Gets the data field of an un-stuffed frame, after checking the length and CRC-16.
+fn frame_data(frame: &[u8]) -> Result<&[u8]> {
+    let frame_len = frame.len();
+
+    if frame_len < len::METADATA {
+        return Err(crate::Error::InvalidLength((frame_len, len::METADATA)));
+    }
+
+    if frame[index::STX] != STX {
+        return Err(crate::Error::InvalidSTX(frame[index::STX]));
+    }
+
+    let exp_len = len::METADATA + frame[index::LEN] as usize;
+    if frame_len != exp_len {
+        return Err(crate::Error::InvalidLength((frame_len, exp_len)));
+    }
+
+    let crc = u16::from_le_bytes([frame[frame_len - 2], frame[frame_len - 1]]);
+    let exp_crc = crc16(frame[index::SEQ_ID..frame_len - 2].as_ref());
+    if crc != exp_crc {
+        return Err(crate::Error::Crc((crc, exp_crc)));
+    }
+
+    Ok(frame[index::DATA..frame_len - 2].as_ref())
+}
+
+/// Gets the [ResponseStatus] of a rejected key exchange command.
+fn key_exchange_status(err: Error) -> ResponseStatus {
+    match err {
+        Error::Status(status) => status,
+        _ => ResponseStatus::CommandCannotBeProcessed,
+    }
+}
+
+/// Builds an un-stuffed frame with the `data` field.
+fn build_frame(seq_id: u8, data: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(len::METADATA + data.len());
+
+    frame.extend_from_slice(&[STX, seq_id, data.len() as u8]);
+    frame.extend_from_slice(data);
+
+    let crc = crc16(frame[index::SEQ_ID..].as_ref());
+    frame.extend_from_slice(crc.to_le_bytes().as_ref());
+
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Device, Method, ResyncPolicy, SequenceCount, SequenceFlag};
+
+    fn poll_events(device: &mut Device<SimulatorTransport>) -> Result<Vec<Method>> {
+        let protocol = device.protocol_version();
+
+        device
+            .poll()?
+            .events(protocol)
+            .map(|event| event.map(|e| e.method()))
+            .collect()
+    }
+
+    #[test]
+    fn test_simulator_encrypted_session() -> Result<()> {
+        let transport = SimulatorTransport::new(Simulator::new());
+        let simulator = transport.simulator();
+
+        let mut device = Device::new(transport).with_retries(0);
+        let setup = device.connect()?;
+
+        assert!(device.is_encrypted());
+        assert_eq!(setup.protocol_version()?, ProtocolVersion::Eight);
+        assert_eq!(setup.num_channels(), DEFAULT_CHANNELS.len());
+
+        {
+            let sim = simulator.lock().unwrap();
+            assert!(sim.is_encrypted());
+            assert!(sim.is_enabled());
+            assert!((1..=7).all(|c| sim.is_channel_enabled(c)));
+        }
+
+        simulator.lock().unwrap().insert_note(3);
+
+        assert_eq!(poll_events(&mut device)?, [Method::Read]);
+        assert_eq!(simulator.lock().unwrap().escrow(), Some(3));
+        assert_eq!(
+            poll_events(&mut device)?,
+            [Method::NoteCredit, Method::Stacking]
+        );
+        assert_eq!(poll_events(&mut device)?, [Method::Stacked]);
+
+        assert_eq!(simulator.lock().unwrap().stored_notes()[2], 1);
+        assert_eq!(simulator.lock().unwrap().stored_value(), 20);
+
+        // every command after the key exchange was encrypted
+        let sim_count = simulator
+            .lock()
+            .unwrap()
+            .session
+            .as_ref()
+            .map(EsspSession::count);
+
+        assert_eq!(sim_count, Some(SequenceCount::from(12)));
+        assert_eq!(device.session().map(EsspSession::count), sim_count);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulator_initialize() -> Result<()> {
+        let transport = SimulatorTransport::new(Simulator::new());
+        let simulator = transport.simulator();
+
+        let mut device = Device::new(transport).with_retries(0);
+        device.initialize()?;
+
+        {
+            let sim = simulator.lock().unwrap();
+            assert!(sim.is_encrypted());
+            assert!(!sim.is_enabled());
+            assert!((1..=7).all(|c| sim.is_channel_enabled(c)));
+        }
+
+        device.enable()?;
+        assert!(simulator.lock().unwrap().is_enabled());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulator_split_poll_events() -> Result<()> {
+        let transport = SimulatorTransport::new(Simulator::new());
+        let simulator = transport.simulator();
+
+        let mut device = Device::new(transport).with_retries(0);
+        device.connect()?;
+
+        // more events than fit in one encrypted reply, each with a data byte
+        for _ in 0..150 {
+            simulator.lock().unwrap().fraud_attempt(1);
+        }
+
+        let mut events = Vec::new();
+        for _ in 0..3 {
+            events.extend(poll_events(&mut device)?);
+        }
+
+        assert_eq!(events.len(), 150);
+        assert!(events.iter().all(|&m| m == Method::FraudAttempt));
+        assert_eq!(poll_events(&mut device)?, []);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulator_multiple_encrypted_devices() -> Result<()> {
+        let payout_transport = SimulatorTransport::new(Simulator::new());
+        let hopper_transport = SimulatorTransport::new(Simulator::new().with_address(0x10));
+        let hopper = hopper_transport.simulator();
+
+        let mut payout = Device::new(payout_transport).with_retries(0);
+        let mut hopper_device = Device::new(hopper_transport)
+            .with_address(0x10)
+            .with_retries(0);
+
+        payout.connect()?;
+        hopper_device.connect()?;
+
+        // each device keeps its own session, so interleaved commands stay in sync
+        hopper.lock().unwrap().insert_note(1);
+
+        assert_eq!(poll_events(&mut payout)?, []);
+        assert_eq!(poll_events(&mut hopper_device)?, [Method::Read]);
+        assert_eq!(poll_events(&mut payout)?, []);
+        assert_eq!(
+            poll_events(&mut hopper_device)?,
+            [Method::NoteCredit, Method::Stacking]
+        );
+
+        let payout_session = payout.session().unwrap();
+        let hopper_session = hopper_device.session().unwrap();
+
+        assert_ne!(payout_session.key(), hopper_session.key());
+        assert_eq!(payout_session.count(), hopper_session.count());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulator_resync_policy() -> Result<()> {
+        let transport = SimulatorTransport::new(Simulator::new());
+
+        let mut device = Device::new(transport)
+            .with_retries(0)
+            .with_resync_policy(ResyncPolicy::Renegotiate);
+
+        device.connect()?;
+        poll_events(&mut device)?;
+
+        let old_key = *device.session().unwrap().key();
+
+        // re-use the last sequence flag, so the simulator replays its last encrypted reply
+        let mut sequence_id = device.sequence_id();
+        sequence_id.toggle_flag();
+        device.engine_mut().set_sequence_id(sequence_id);
+
+        assert!(matches!(device.poll(), Err(Error::InvalidSequenceCount(_))));
+
+        // the replay triggered a new key exchange, and the new session is in sync
+        assert!(device.is_encrypted());
+        assert_ne!(device.session().unwrap().key(), &old_key);
+        assert_eq!(poll_events(&mut device)?, []);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulator_resync_negotiator() -> Result<()> {
+        use crate::{primes::Generator, KeyNegotiator, RandomKey};
+
+        // seeded negotiators need no system entropy, e.g. on no_std targets
+        let negotiator = || {
+            KeyNegotiator::new(
+                Generator::from_seed([0x5a; 32]),
+                RandomKey::from_seed([0xa5; 32]),
+            )
+        };
+
+        let transport = SimulatorTransport::new(Simulator::new());
+
+        let mut device = Device::new(transport)
+            .with_retries(0)
+            .with_resync_policy(ResyncPolicy::Renegotiate)
+            .with_resync_negotiator(negotiator());
+
+        device.connect_with_negotiator(&mut negotiator())?;
+
+        let mut sequence_id = device.sequence_id();
+        sequence_id.toggle_flag();
+        device.engine_mut().set_sequence_id(sequence_id);
+
+        let old_count = device.session().unwrap().count().as_inner();
+
+        assert!(matches!(device.poll(), Err(Error::InvalidSequenceCount(_))));
+
+        // the new session restarts the sequence count, and stays in sync
+        assert!(device.session().unwrap().count().as_inner() < old_count);
+        assert_eq!(poll_events(&mut device)?, []);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulator_scripted_events() -> Result<()> {
+        let transport = SimulatorTransport::new(Simulator::new());
+        let simulator = transport.simulator();
+
+        let mut device = Device::new(transport).with_retries(0);
+        device.sync()?;
+
+        assert_eq!(poll_events(&mut device)?, [Method::Disabled]);
+
+        device.set_inhibits(
+            [0b0000_0001, 0]
+                .into_iter()
+                .map(crate::EnableBitfield::from)
+                .collect::<crate::Vec<_>>()
+                .into(),
+        )?;
+        device.enable()?;
+
+        // inhibited channel
+        simulator.lock().unwrap().insert_note(2);
+        assert_eq!(poll_events(&mut device)?, [Method::Read, Method::Rejecting]);
+        assert_eq!(poll_events(&mut device)?, [Method::Rejected]);
+        assert_eq!(
+            simulator.lock().unwrap().last_reject_code(),
+            LastRejectCode::ChannelInhibited
+        );
+
+        // host rejects the note in escrow
+        simulator.lock().unwrap().insert_note(1);
+        assert_eq!(poll_events(&mut device)?, [Method::Read]);
+        device.reject()?;
+        assert_eq!(poll_events(&mut device)?, [Method::Rejecting]);
+        assert_eq!(poll_events(&mut device)?, [Method::Rejected]);
+
+        {
+            let mut sim = simulator.lock().unwrap();
+            sim.fraud_attempt(1);
+            sim.jam();
+            sim.remove_cashbox();
+        }
+        assert_eq!(
+            poll_events(&mut device)?,
+            [
+                Method::FraudAttempt,
+                Method::UnsafeJam,
+                Method::CashboxRemoved
+            ]
+        );
+
+        {
+            let mut sim = simulator.lock().unwrap();
+            sim.clear_jam();
+            sim.replace_cashbox();
+        }
+        assert_eq!(poll_events(&mut device)?, [Method::CashboxReplaced]);
+        assert!(poll_events(&mut device)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulator_retransmit() {
+        let mut sim = Simulator::new();
+        sim.enabled = true;
+        sim.inhibits = vec![0xff, 0xff];
+        sim.insert_note(1);
+
+        let mut seq_id = SequenceId::new();
+        seq_id.set_flag(SequenceFlag::Set);
+
+        let mut poll = crate::PollCommand::new();
+        poll.set_sequence_id(seq_id);
+
+        let reply = sim.handle_frame(poll.as_bytes()).unwrap();
+
+        // the lost reply is repeated, without advancing the note path
+        assert_eq!(sim.handle_frame(poll.as_bytes()), Some(reply));
+        assert_eq!(sim.escrow(), Some(1));
+
+        // corrupt frames, and frames for other devices, are not answered
+        let mut frame = poll.as_bytes().to_vec();
+        frame[index::LEN + 1] ^= 0xff;
+        assert_eq!(sim.handle_frame(frame.as_ref()), None);
+
+        let mut other = crate::PollCommand::new();
+        other.set_sequence_id(SequenceId::from(0x10));
+        assert_eq!(sim.handle_frame(other.as_bytes()), None);
+    }
+}
@@ -0,0 +1,173 @@
+//! Pseudo-terminal harness serving a [Simulator] on a tty path.
+
+use alloc::string::String;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use serialport::{SerialPort, TTYPort};
+
+use crate::{Device, Error, Result, SerialLink};
+
+use super::{serve, Simulator};
+
+/// Serves a [Simulator] on one end of a pseudo-terminal pair.
+///
+/// Host code opens the other end by its path, e.g. `/dev/pts/3`, like any serial device. This
+/// lets unmodified host applications talk to the simulated device.
+///
+/// The simulator is served from a background thread, which is stopped when the harness is
+/// dropped.
+///
+/// ```rust,no_run
+/// # fn main() -> ssp::Result<()> {
+/// use ssp::simulator::{PtyHarness, Simulator};
+///
+/// let harness = PtyHarness::start(Simulator::new())?;
+///
+/// let mut device = harness.open_device()?;
+/// device.connect()?;
+///
+/// harness.simulator().lock().unwrap().insert_note(1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct PtyHarness {
+    path: String,
+    simulator: Arc<Mutex<Simulator>>,
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<Result<()>>>,
+    // held open, so the device end does not hang up when a host closes the path
+    _tty: TTYPort,
+}
+
+impl PtyHarness {
+    /// Creates a pseudo-terminal pair, and starts serving the `simulator` in the background.
+    pub fn start(simulator: Simulator) -> Result<Self> {
+        Self::start_shared(Arc::new(Mutex::new(simulator)))
+    }
+
+    /// Creates a pseudo-terminal pair, and starts serving a shared `simulator` in the background.
+    pub fn start_shared(simulator: Arc<Mutex<Simulator>>) -> Result<Self> {
+        let (mut device_end, tty) = TTYPort::pair()?;
+
+        let path = tty
+            .name()
+            .ok_or(Error::Io("pseudo-terminal has no path".into()))?;
+
+        let running = Arc::new(AtomicBool::new(true));
+
+        let handle = {
+            let simulator = Arc::clone(&simulator);
+            let running = Arc::clone(&running);
+
+            thread::Builder::new()
+                .name("ssp-simulator".into())
+                .spawn(move || {
+                    let res = serve(&simulator, &mut device_end, &running);
+                    if let Err(err) = res.as_ref() {
+                        log::error!("Simulator stopped serving pseudo-terminal: {err}");
+                    }
+                    res
+                })?
+        };
+
+        log::info!("Serving simulator on: {path}");
+
+        Ok(Self {
+            path,
+            simulator,
+            running,
+            handle: Some(handle),
+            _tty: tty,
+        })
+    }
+
+    /// Gets the tty path host code opens to talk to the [Simulator].
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// Gets a shared handle to the [Simulator].
+    pub fn simulator(&self) -> Arc<Mutex<Simulator>> {
+        Arc::clone(&self.simulator)
+    }
+
+    /// Gets whether the [Simulator] is still being served.
+    pub fn is_running(&self) -> bool {
+        self.handle.as_ref().is_some_and(|h| !h.is_finished())
+    }
+
+    /// Opens the tty path with a [SerialLink], and creates a host [Device] talking to the
+    /// [Simulator].
+    pub fn open_device(&self) -> Result<Device<SerialLink>> {
+        Ok(Device::new(SerialLink::open(self.path())?))
+    }
+
+    /// Blocks until the [Simulator] stops being served, e.g. after a pseudo-terminal error.
+    pub fn wait(mut self) -> Result<()> {
+        self.join()
+    }
+
+    /// Stops serving the [Simulator].
+    pub fn stop(mut self) -> Result<()> {
+        self.running.store(false, Ordering::Relaxed);
+        self.join()
+    }
+
+    fn join(&mut self) -> Result<()> {
+        match self.handle.take() {
+            Some(handle) => handle
+                .join()
+                .map_err(|_| Error::Io("simulator thread panicked".into()))?,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for PtyHarness {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+
+        if let Err(err) = self.join() {
+            log::warn!("Error stopping simulator harness: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Method, ProtocolVersion};
+
+    #[test]
+    fn test_pty_harness() -> Result<()> {
+        let harness = PtyHarness::start(Simulator::new())?;
+        assert!(harness.path().starts_with("/dev/"));
+        assert!(harness.is_running());
+
+        let mut device = harness.open_device()?;
+        let setup = device.connect()?;
+
+        assert_eq!(setup.protocol_version()?, ProtocolVersion::Eight);
+        assert!(harness.simulator().lock().unwrap().is_encrypted());
+
+        harness.simulator().lock().unwrap().insert_note(2);
+
+        let protocol = device.protocol_version();
+        let events = device
+            .poll()?
+            .events(protocol)
+            .map(|event| event.map(|e| e.method()))
+            .collect::<Result<Vec<Method>>>()?;
+
+        assert_eq!(events, [Method::Read]);
+        assert_eq!(harness.simulator().lock().unwrap().escrow(), Some(2));
+
+        // the path can be re-opened after the host disconnects
+        drop(device);
+        harness.open_device()?.sync()?;
+
+        harness.stop()
+    }
+}
@@ -0,0 +1,149 @@
+//! Transports connecting a [Simulator] to host code.
+
+use alloc::vec::Vec;
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::{
+    len, read_frame, transport::DEFAULT_TIMEOUT_MS, Error, FrameDecoder, FrameEncoder, Result,
+    Transport,
+};
+
+use super::Simulator;
+
+/// In-memory [Transport] that delivers frames directly to a [Simulator].
+///
+/// The simulator is shared, so tests can drive the scripted hooks while a
+/// [Device](crate::Device) owns the transport.
+pub struct SimulatorTransport {
+    simulator: Arc<Mutex<Simulator>>,
+    reply: Option<Vec<u8>>,
+    timeout: Duration,
+}
+
+impl SimulatorTransport {
+    /// Creates a new [SimulatorTransport] connected to the `simulator`.
+    pub fn new(simulator: Simulator) -> Self {
+        Self::from_shared(Arc::new(Mutex::new(simulator)))
+    }
+
+    /// Creates a new [SimulatorTransport] connected to a shared `simulator`.
+    pub fn from_shared(simulator: Arc<Mutex<Simulator>>) -> Self {
+        Self {
+            simulator,
+            reply: None,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+        }
+    }
+
+    /// Gets a shared handle to the [Simulator].
+    pub fn simulator(&self) -> Arc<Mutex<Simulator>> {
+        Arc::clone(&self.simulator)
+    }
+}
+
+impl Transport for SimulatorTransport {
+    fn send_frame(&mut self, frame: &[u8]) -> Result<()> {
+        let mut simulator = self
+            .simulator
+            .lock()
+            .map_err(|err| Error::Io(format!("simulator lock poisoned: {err}")))?;
+
+        self.reply = simulator.handle_frame(frame);
+
+        Ok(())
+    }
+
+    fn receive_frame(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let reply = self
+            .reply
+            .take()
+            .ok_or(Error::Timeout("simulator did not reply".into()))?;
+
+        let (buf_len, reply_len) = (buf.len(), reply.len());
+        if buf_len < reply_len {
+            return Err(Error::InvalidLength((buf_len, reply_len)));
+        }
+
+        buf[..reply_len].copy_from_slice(reply.as_ref());
+
+        Ok(reply_len)
+    }
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> Result<()> {
+        self.timeout = timeout;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl crate::transport::AsyncTransport for SimulatorTransport {
+    async fn send_frame(&mut self, frame: &[u8]) -> Result<()> {
+        Transport::send_frame(self, frame)
+    }
+
+    async fn receive_frame(&mut self, buf: &mut [u8]) -> Result<usize> {
+        Transport::receive_frame(self, buf)
+    }
+
+    fn timeout(&self) -> Duration {
+        Transport::timeout(self)
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> Result<()> {
+        Transport::set_timeout(self, timeout)
+    }
+}
+
+/// Time between checks of the `running` flag while [serve] waits for a command frame.
+const SERVE_CHECK_MS: u64 = 100;
+
+/// Serves a [Simulator] over a byte stream, e.g. a serial port or pseudo-terminal.
+///
+/// Reads stuffed command frames from the `stream`, and writes the stuffed replies back, until
+/// `running` is cleared, or the stream is closed or returns an error.
+///
+/// Partially received frames are kept while checking the `running` flag, so the `stream` may
+/// return read timeouts.
+pub fn serve<S: Read + Write + ?Sized>(
+    simulator: &Mutex<Simulator>,
+    stream: &mut S,
+    running: &AtomicBool,
+) -> Result<()> {
+    let mut decoder = FrameDecoder::new();
+    let mut encoder = FrameEncoder::new();
+    let mut buf = [0u8; len::MAX_MESSAGE];
+
+    while running.load(Ordering::Relaxed) {
+        let frame_len = match read_frame(
+            stream,
+            &mut decoder,
+            buf.as_mut(),
+            Duration::from_millis(SERVE_CHECK_MS),
+        ) {
+            Ok(frame_len) => frame_len,
+            Err(Error::Timeout(_)) => continue,
+            // corrupt frames are dropped, and the host re-transmits
+            Err(Error::Crc(_)) | Err(Error::InvalidLength(_)) => continue,
+            Err(err) => return Err(err),
+        };
+
+        let reply = simulator
+            .lock()
+            .map_err(|err| Error::Io(format!("simulator lock poisoned: {err}")))?
+            .handle_frame(&buf[..frame_len]);
+
+        if let Some(reply) = reply {
+            stream.write_all(encoder.encode(reply.as_ref())?)?;
+            stream.flush()?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+//! Transport layer for sending and receiving SSP messages.
+
+use crate::{
+    len, std::time::Duration, CommandOps, MessageOps, MessageType, MessageVariant, Result,
+};
+
+#[cfg(feature = "async")]
+mod async_engine;
+#[cfg(feature = "async")]
+mod async_serial;
+mod engine;
+#[cfg(test)]
+pub(crate) mod mock;
+#[cfg(feature = "std")]
+mod serial;
+
+#[cfg(feature = "async")]
+pub use async_engine::*;
+#[cfg(feature = "async")]
+pub use async_serial::*;
+pub use engine::*;
+
+#[cfg(feature = "std")]
+pub use serial::*;
+
+/// Default time to wait for a device response.
+///
+/// From the SSP Implementation Guide, the host should wait at least one second for a reply
+/// before re-transmitting a packet.
+pub const DEFAULT_TIMEOUT_MS: u64 = 1_000;
+
+/// Generic transport for framed SSP messages.
+///
+/// Implementors only need to move complete, un-stuffed frames (`STX` through `CRC_H`) to and from
+/// the device. Byte stuffing, framing, and CRC validation are the responsibility of the
+/// implementation.
+pub trait Transport {
+    /// Writes a complete message frame to the device.
+    ///
+    /// The `frame` is the un-stuffed message buffer, including the `STX` byte and CRC-16 checksum.
+    fn send_frame(&mut self, frame: &[u8]) -> Result<()>;
+
+    /// Reads a complete message frame from the device into `buf`.
+    ///
+    /// On success, `buf` contains the un-stuffed message buffer (including `STX` and CRC-16), and
+    /// the frame length is returned.
+    fn receive_frame(&mut self, buf: &mut [u8]) -> Result<usize>;
+
+    /// Gets the timeout for reading a response from the device.
+    fn timeout(&self) -> Duration;
+
+    /// Sets the timeout for reading a response from the device.
+    fn set_timeout(&mut self, timeout: Duration) -> Result<()>;
+
+    /// Sends a message to the device.
+    ///
+    /// Calculates the CRC-16 checksum before transmission.
+    fn send(&mut self, message: &mut dyn MessageOps) -> Result<()> {
+        self.send_frame(message.as_bytes())
+    }
+
+    /// Receives a response message from the device.
+    ///
+    /// The `command` type is needed to infer the type of response message.
+    fn receive(&mut self, command: MessageType) -> Result<MessageVariant> {
+        let mut buf = [0u8; len::MAX_MESSAGE];
+
+        let frame_len = self.receive_frame(&mut buf)?;
+
+        MessageVariant::from_buf(buf[..frame_len].as_ref(), command)
+    }
+
+    /// Sends a command to the device, and waits for the response.
+    fn transact(&mut self, command: &mut dyn CommandOps) -> Result<MessageVariant> {
+        self.send(command)?;
+        self.receive(command.command())
+    }
+}
+
+/// Generic asynchronous transport for framed SSP messages.
+///
+/// The asynchronous counterpart of [Transport], with the same framing responsibilities. Futures
+/// returned by the transport must be [Send], so drivers can run on a multi-threaded runtime.
+///
+/// Implementations should keep partially received frames between calls, so a cancelled
+/// [receive_frame](Self::receive_frame) does not lose data.
+#[cfg(feature = "async")]
+pub trait AsyncTransport: Send {
+    /// Writes a complete message frame to the device.
+    ///
+    /// The `frame` is the un-stuffed message buffer, including the `STX` byte and CRC-16 checksum.
+    fn send_frame(&mut self, frame: &[u8])
+        -> impl core::future::Future<Output = Result<()>> + Send;
+
+    /// Reads a complete message frame from the device into `buf`.
+    ///
+    /// On success, `buf` contains the un-stuffed message buffer (including `STX` and CRC-16), and
+    /// the frame length is returned.
+    fn receive_frame(
+        &mut self,
+        buf: &mut [u8],
+    ) -> impl core::future::Future<Output = Result<usize>> + Send;
+
+    /// Gets the timeout for reading a response from the device.
+    fn timeout(&self) -> Duration;
+
+    /// Sets the timeout for reading a response from the device.
+    fn set_timeout(&mut self, timeout: Duration) -> Result<()>;
+}
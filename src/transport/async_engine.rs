@@ -0,0 +1,229 @@
+//! Asynchronous request/response engine handling retransmission and sequence flags.
+
+use alloc::vec::Vec;
+use core::future::Future;
+
+use crate::{
+    CommandOps, MessageOps, MessageType, MessageVariant, Result, SequenceFlag, SequenceId,
+};
+
+use super::engine::{check_reply, retries_exhausted};
+use super::{AsyncTransport, DEFAULT_RETRIES};
+
+/// Message frame encoded with the [SequenceId] of the next request.
+pub(crate) struct EncodedMessage {
+    msg_type: MessageType,
+    frame: Vec<u8>,
+}
+
+/// Asynchronous request/response engine implementing SSP packet sequencing.
+///
+/// The asynchronous counterpart of [RequestEngine](super::RequestEngine), with the same
+/// retransmission and [SequenceFlag] handling.
+///
+/// Cancelling a request before it completes leaves the [SequenceFlag] unchanged, so the next
+/// request is sent with the same flag. If the device already executed the cancelled command, it
+/// repeats its last reply instead of executing the next command. Resetting the flag with
+/// [sync](crate::AsyncDevice::sync) is not enough for encrypted sessions, which also need a new
+/// key, see [AsyncDevice](crate::AsyncDevice#cancellation).
+pub struct AsyncRequestEngine<T: AsyncTransport> {
+    transport: T,
+    sequence_id: SequenceId,
+    retries: usize,
+}
+
+impl<T: AsyncTransport> AsyncRequestEngine<T> {
+    /// Creates a new [AsyncRequestEngine] communicating over the provided [AsyncTransport].
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            sequence_id: SequenceId::new(),
+            retries: DEFAULT_RETRIES,
+        }
+    }
+
+    /// Builder function that sets the number of retransmissions.
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Gets the number of retransmissions attempted before giving up on a request.
+    pub fn retries(&self) -> usize {
+        self.retries
+    }
+
+    /// Sets the number of retransmissions attempted before giving up on a request.
+    pub fn set_retries(&mut self, retries: usize) {
+        self.retries = retries;
+    }
+
+    /// Gets the [SequenceId] used for the next request.
+    pub fn sequence_id(&self) -> SequenceId {
+        self.sequence_id
+    }
+
+    /// Sets the [SequenceId] used for the next request.
+    pub fn set_sequence_id(&mut self, sequence_id: SequenceId) {
+        self.sequence_id = sequence_id;
+    }
+
+    /// Sets the [SequenceFlag] used for the next request.
+    pub fn set_sequence_flag(&mut self, flag: SequenceFlag) {
+        self.sequence_id.set_flag(flag);
+    }
+
+    /// Gets a reference to the [AsyncTransport].
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Gets a mutable reference to the [AsyncTransport].
+    pub fn transport_mut(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    /// Consumes the [AsyncRequestEngine], returning the [AsyncTransport].
+    pub fn into_transport(self) -> T {
+        self.transport
+    }
+
+    /// Sends a message to the device, and reads the reply frame into `buf`.
+    ///
+    /// Sets the [SequenceId] of the message before sending. Timeouts, invalid frames, and replies
+    /// with a different [SequenceId] cause the message to be re-transmitted, up to the configured
+    /// number of retries.
+    ///
+    /// The message is encoded before the returned future is first polled, so the future does not
+    /// borrow the `message`.
+    ///
+    /// Returns the length of the reply frame on success, or [Error::Timeout](crate::Error::Timeout) if the
+    /// retries run out.
+    pub fn request_frame<'a>(
+        &'a mut self,
+        message: &mut dyn MessageOps,
+        buf: &'a mut [u8],
+    ) -> impl Future<Output = Result<usize>> + Send + 'a {
+        let frame = self.encode(message);
+
+        self.request_encoded(frame, buf)
+    }
+
+    /// Sends a command to the device, and parses the reply.
+    pub fn request<'a>(
+        &'a mut self,
+        command: &mut dyn CommandOps,
+    ) -> impl Future<Output = Result<MessageVariant>> + Send + 'a {
+        let msg_type = command.command();
+        let frame = self.encode(command);
+
+        async move {
+            let mut buf = [0u8; crate::len::MAX_MESSAGE];
+
+            let frame_len = self.request_encoded(frame, &mut buf).await?;
+
+            MessageVariant::from_buf(buf[..frame_len].as_ref(), msg_type)
+        }
+    }
+
+    /// Sets the [SequenceId] of the message, and encodes it for [request_encoded].
+    pub(crate) fn encode(&self, message: &mut dyn MessageOps) -> EncodedMessage {
+        message.set_sequence_id(self.sequence_id);
+
+        EncodedMessage {
+            msg_type: message.message_type(),
+            frame: message.as_bytes().into(),
+        }
+    }
+
+    /// Sends an encoded message to the device, and reads the reply frame into `buf`.
+    pub(crate) async fn request_encoded(
+        &mut self,
+        message: EncodedMessage,
+        buf: &mut [u8],
+    ) -> Result<usize> {
+        let EncodedMessage { msg_type, frame } = message;
+
+        for attempt in 0..=self.retries {
+            if attempt != 0 {
+                log::debug!("Re-transmitting {msg_type} message, attempt: {attempt}");
+            }
+
+            self.transport.send_frame(frame.as_ref()).await?;
+
+            let res = self.transport.receive_frame(buf).await;
+
+            if let Some(frame_len) = check_reply(res, buf, self.sequence_id, msg_type)? {
+                // only a valid reply advances the sequence flag
+                self.sequence_id.toggle_flag();
+
+                return Ok(frame_len);
+            }
+        }
+
+        Err(retries_exhausted(msg_type, self.retries, self.sequence_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        message::index, transport::mock::MockTransport, Error, ResponseStatus, SyncCommand,
+        SyncResponse,
+    };
+
+    #[tokio::test]
+    async fn test_async_request_retransmit() -> Result<()> {
+        let mut transport = MockTransport::new();
+        transport.push_error(Error::Timeout("dropped reply".into()));
+        transport.push_reply(&mut SyncResponse::new(), ResponseStatus::Ok);
+
+        let mut engine = AsyncRequestEngine::new(transport).with_retries(1);
+        engine.set_sequence_flag(SequenceFlag::Set);
+
+        let res = engine.request(&mut SyncCommand::new()).await?;
+
+        assert!(res.is_sync_response());
+        assert_eq!(engine.sequence_id().flag(), SequenceFlag::Unset);
+
+        let sent = engine.transport().sent();
+        assert_eq!(sent.len(), 2);
+        assert!(sent.iter().all(|f| f[index::SEQ_ID] == 0x80));
+
+        assert!(matches!(
+            engine.request(&mut SyncCommand::new()).await,
+            Err(Error::Timeout(_))
+        ));
+        assert_eq!(engine.sequence_id().flag(), SequenceFlag::Unset);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_async_request_sequence_id_mismatch() -> Result<()> {
+        let mut transport = MockTransport::new();
+        // stale reply to the previous request, with the other sequence flag
+        transport.push_reply_with_sequence_id(
+            &mut SyncResponse::new(),
+            ResponseStatus::Ok,
+            SequenceId::from(0x00),
+        );
+        transport.push_reply(&mut SyncResponse::new(), ResponseStatus::Ok);
+
+        let mut engine = AsyncRequestEngine::new(transport).with_retries(1);
+        engine.set_sequence_flag(SequenceFlag::Set);
+
+        assert!(engine
+            .request(&mut SyncCommand::new())
+            .await?
+            .is_sync_response());
+        assert_eq!(engine.sequence_id().flag(), SequenceFlag::Unset);
+
+        let sent = engine.transport().sent();
+        assert_eq!(sent.len(), 2);
+        assert!(sent.iter().all(|f| f[index::SEQ_ID] == 0x80));
+
+        Ok(())
+    }
+}
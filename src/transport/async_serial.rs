@@ -0,0 +1,176 @@
+//! Asynchronous serial port [AsyncTransport] for communicating with SSP devices.
+
+use std::io;
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio_serial::{DataBits, FlowControl, Parity, SerialPortBuilderExt, SerialStream, StopBits};
+
+use crate::{Error, FrameDecoder, FrameEncoder, Result};
+
+use super::{AsyncTransport, DEFAULT_TIMEOUT_MS, SERIAL_BAUD_RATE};
+
+/// Asynchronous [AsyncTransport] over a serial port, using the `tokio` runtime.
+///
+/// The port is configured for SSP communication: 9600 baud, eight data bits, no parity, and two
+/// stop bits (8N2).
+///
+/// Partially received frames are kept by the decoder, so cancelling a read does not lose data.
+pub struct AsyncSerialLink {
+    port: SerialStream,
+    timeout: Duration,
+    encoder: FrameEncoder,
+    decoder: FrameDecoder,
+}
+
+impl AsyncSerialLink {
+    /// Opens the serial port at `path`, and configures it for SSP communication.
+    ///
+    /// Must be called from within a `tokio` runtime.
+    pub fn open(path: &str) -> Result<Self> {
+        let port = tokio_serial::new(path, SERIAL_BAUD_RATE)
+            .data_bits(DataBits::Eight)
+            .parity(Parity::None)
+            .stop_bits(StopBits::Two)
+            .flow_control(FlowControl::None)
+            .open_native_async()?;
+
+        Ok(Self::from_port(port))
+    }
+
+    /// Creates an [AsyncSerialLink] from an already opened serial port.
+    ///
+    /// The caller is responsible for configuring the port settings.
+    pub fn from_port(port: SerialStream) -> Self {
+        Self {
+            port,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+            encoder: FrameEncoder::new(),
+            decoder: FrameDecoder::new(),
+        }
+    }
+
+    /// Gets a reference to the underlying serial port.
+    pub fn port(&self) -> &SerialStream {
+        &self.port
+    }
+
+    /// Gets a mutable reference to the underlying serial port.
+    pub fn port_mut(&mut self) -> &mut SerialStream {
+        &mut self.port
+    }
+}
+
+impl AsyncTransport for AsyncSerialLink {
+    async fn send_frame(&mut self, frame: &[u8]) -> Result<()> {
+        let stuffed = self.encoder.encode(frame)?;
+
+        log::trace!("Sending frame: {stuffed:x?}");
+
+        self.port.write_all(stuffed).await?;
+        self.port.flush().await?;
+
+        Ok(())
+    }
+
+    async fn receive_frame(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let frame_len =
+            read_frame_async(&mut self.port, &mut self.decoder, buf, self.timeout).await?;
+
+        log::trace!("Received frame: {:x?}", &buf[..frame_len]);
+
+        Ok(frame_len)
+    }
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> Result<()> {
+        self.timeout = timeout;
+        Ok(())
+    }
+}
+
+/// Reads a complete message frame from an asynchronous byte stream.
+///
+/// The asynchronous counterpart of [read_frame](super::read_frame), with the same decoding and
+/// resynchronization behavior.
+///
+/// Bytes are pushed to the `decoder` as they are read, so a cancelled read resumes from the same
+/// point when called again with the same `decoder`.
+pub async fn read_frame_async<R: AsyncRead + Unpin + ?Sized>(
+    reader: &mut R,
+    decoder: &mut FrameDecoder,
+    buf: &mut [u8],
+    timeout: Duration,
+) -> Result<usize> {
+    tokio::time::timeout(timeout, async {
+        loop {
+            while let Some(res) = decoder.pop_frame() {
+                match res {
+                    Ok(frame) => {
+                        let (buf_len, frame_len) = (buf.len(), frame.len());
+
+                        if buf_len < frame_len {
+                            return Err(Error::InvalidLength((buf_len, frame_len)));
+                        }
+
+                        buf[..frame_len].copy_from_slice(frame.as_bytes());
+
+                        return Ok(frame_len);
+                    }
+                    Err(Error::Resync(discarded)) => {
+                        log::warn!("Resynchronized frame, discarded {discarded} byte(s)");
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+
+            match reader.read_u8().await {
+                Ok(byte) => {
+                    decoder.push_bytes(&[byte]);
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+    })
+    .await
+    .map_err(|_| Error::Timeout("reading message frame".into()))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{len, MessageOps, SyncCommand};
+
+    #[tokio::test]
+    async fn test_read_frame_async() -> Result<()> {
+        let mut msg = SyncCommand::new();
+        let exp = msg.as_bytes().to_vec();
+
+        let (mut tx, mut rx) = tokio::io::duplex(len::MAX_MESSAGE);
+
+        // leading noise is discarded, and a frame split across writes is reassembled
+        let (head, tail) = exp.split_at(3);
+        tx.write_all(&[0x00, 0x11]).await?;
+        tx.write_all(head).await?;
+
+        let mut decoder = FrameDecoder::new();
+        let mut buf = [0u8; len::MAX_MESSAGE];
+        let timeout = Duration::from_millis(10);
+
+        assert!(matches!(
+            read_frame_async(&mut rx, &mut decoder, buf.as_mut(), timeout).await,
+            Err(Error::Timeout(_))
+        ));
+
+        tx.write_all(tail).await?;
+
+        let frame_len = read_frame_async(&mut rx, &mut decoder, buf.as_mut(), timeout).await?;
+        assert_eq!(buf[..frame_len].as_ref(), exp.as_slice());
+
+        Ok(())
+    }
+}
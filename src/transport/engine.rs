@@ -0,0 +1,258 @@
+//! Request/response engine handling retransmission and sequence flags.
+
+use crate::{
+    message::index, CommandOps, Error, MessageOps, MessageType, MessageVariant, Result,
+    SequenceFlag, SequenceId,
+};
+
+use super::Transport;
+
+/// Default number of retransmissions before assuming the device has crashed.
+///
+/// From the SSP Protocol Manual:
+///
+/// ```no_build,no_run
+/// After twenty retries, the master will assume that the slave has crashed.
+/// ```
+pub const DEFAULT_RETRIES: usize = 20;
+
+/// Request/response engine implementing SSP packet sequencing.
+///
+/// If a reply times out, fails validation, or carries a different [SequenceId], the engine
+/// re-transmits the same frame with the same [SequenceFlag]. The device either executes the
+/// command (if it never received it), or repeats its last reply (if the reply was lost).
+///
+/// The [SequenceFlag] is only toggled after a valid reply.
+pub struct RequestEngine<T: Transport> {
+    transport: T,
+    sequence_id: SequenceId,
+    retries: usize,
+}
+
+impl<T: Transport> RequestEngine<T> {
+    /// Creates a new [RequestEngine] communicating over the provided [Transport].
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            sequence_id: SequenceId::new(),
+            retries: DEFAULT_RETRIES,
+        }
+    }
+
+    /// Builder function that sets the number of retransmissions.
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Gets the number of retransmissions attempted before giving up on a request.
+    pub fn retries(&self) -> usize {
+        self.retries
+    }
+
+    /// Sets the number of retransmissions attempted before giving up on a request.
+    pub fn set_retries(&mut self, retries: usize) {
+        self.retries = retries;
+    }
+
+    /// Gets the [SequenceId] used for the next request.
+    pub fn sequence_id(&self) -> SequenceId {
+        self.sequence_id
+    }
+
+    /// Sets the [SequenceId] used for the next request.
+    pub fn set_sequence_id(&mut self, sequence_id: SequenceId) {
+        self.sequence_id = sequence_id;
+    }
+
+    /// Sets the [SequenceFlag] used for the next request.
+    pub fn set_sequence_flag(&mut self, flag: SequenceFlag) {
+        self.sequence_id.set_flag(flag);
+    }
+
+    /// Gets a reference to the [Transport].
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Gets a mutable reference to the [Transport].
+    pub fn transport_mut(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    /// Consumes the [RequestEngine], returning the [Transport].
+    pub fn into_transport(self) -> T {
+        self.transport
+    }
+
+    /// Sends a message to the device, and reads the reply frame into `buf`.
+    ///
+    /// Sets the [SequenceId] of the message before sending. Timeouts, invalid frames, and replies
+    /// with a different [SequenceId] cause the message to be re-transmitted, up to the configured
+    /// number of retries.
+    ///
+    /// Returns the length of the reply frame on success, or [Error::Timeout] if the retries run
+    /// out.
+    pub fn request_frame(&mut self, message: &mut dyn MessageOps, buf: &mut [u8]) -> Result<usize> {
+        let msg_type = message.message_type();
+
+        message.set_sequence_id(self.sequence_id);
+
+        for attempt in 0..=self.retries {
+            if attempt != 0 {
+                log::debug!("Re-transmitting {msg_type} message, attempt: {attempt}");
+            }
+
+            self.transport.send(message)?;
+
+            let res = self.transport.receive_frame(buf);
+
+            if let Some(frame_len) = check_reply(res, buf, self.sequence_id, msg_type)? {
+                // only a valid reply advances the sequence flag
+                self.sequence_id.toggle_flag();
+
+                return Ok(frame_len);
+            }
+        }
+
+        Err(retries_exhausted(msg_type, self.retries, self.sequence_id))
+    }
+
+    /// Sends a command to the device, and parses the reply.
+    pub fn request(&mut self, command: &mut dyn CommandOps) -> Result<MessageVariant> {
+        let mut buf = [0u8; crate::len::MAX_MESSAGE];
+
+        let frame_len = self.request_frame(command, &mut buf)?;
+
+        MessageVariant::from_buf(buf[..frame_len].as_ref(), command.command())
+    }
+}
+
+/// Checks a reply received for the request with the [SequenceId].
+///
+/// Returns the frame length if the reply is valid, or `None` if the request should be
+/// re-transmitted: the reply timed out, failed validation, or has a different [SequenceId]. A
+/// mismatched [SequenceId] means a stale reply to an earlier request, so accepting it would
+/// answer the current command with the wrong reply, e.g. losing a credit.
+///
+/// Shared by the blocking and asynchronous engines.
+pub(crate) fn check_reply(
+    res: Result<usize>,
+    buf: &[u8],
+    sequence_id: SequenceId,
+    msg_type: MessageType,
+) -> Result<Option<usize>> {
+    match res {
+        Ok(frame_len) => {
+            let seq_id = SequenceId::from(buf[index::SEQ_ID]);
+
+            if seq_id == sequence_id {
+                Ok(Some(frame_len))
+            } else {
+                log::warn!(
+                    "Discarding reply to {msg_type} message, sequence ID mismatch, have: {seq_id}, expected: {sequence_id}"
+                );
+                Ok(None)
+            }
+        }
+        Err(err @ (Error::Timeout(_) | Error::Crc(_) | Error::Resync(_))) => {
+            log::warn!("Failed to receive reply to {msg_type} message: {err}");
+            Ok(None)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Creates the error returned when no valid reply is received within the `retries`.
+pub(crate) fn retries_exhausted(
+    msg_type: MessageType,
+    retries: usize,
+    sequence_id: SequenceId,
+) -> Error {
+    let attempts = retries + 1;
+
+    Error::Timeout(format!(
+        "no valid reply to {msg_type} message after {attempts} attempt(s), sequence ID: {sequence_id}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{transport::mock::MockTransport, ResponseStatus, SyncCommand, SyncResponse};
+
+    #[test]
+    fn test_request_retransmit() -> Result<()> {
+        let mut transport = MockTransport::new();
+        transport.push_error(Error::Timeout("dropped reply".into()));
+        transport.push_error(Error::Crc((0x1234, 0x4321)));
+        transport.push_reply(&mut SyncResponse::new(), ResponseStatus::Ok);
+
+        let mut engine = RequestEngine::new(transport).with_retries(2);
+        engine.set_sequence_flag(SequenceFlag::Set);
+
+        let res = engine.request(&mut SyncCommand::new())?;
+
+        assert!(res.is_sync_response());
+        assert_eq!(engine.sequence_id().flag(), SequenceFlag::Unset);
+
+        // every attempt re-uses the same sequence flag
+        let sent = engine.transport().sent();
+        assert_eq!(sent.len(), 3);
+        assert!(sent.iter().all(|f| f[index::SEQ_ID] == 0x80));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_request_retries_exhausted() {
+        let mut transport = MockTransport::new();
+        transport.push_error(Error::Timeout("dropped reply".into()));
+        transport.push_error(Error::Timeout("dropped reply".into()));
+
+        let mut engine = RequestEngine::new(transport).with_retries(1);
+
+        assert!(matches!(
+            engine.request(&mut SyncCommand::new()),
+            Err(Error::Timeout(_))
+        ));
+        assert_eq!(engine.sequence_id().flag(), SequenceFlag::Unset);
+        assert_eq!(engine.transport().sent().len(), 2);
+    }
+
+    #[test]
+    fn test_request_sequence_id_mismatch() -> Result<()> {
+        let mut transport = MockTransport::new();
+        // stale reply to the previous request, with the other sequence flag
+        transport.push_reply_with_sequence_id(
+            &mut SyncResponse::new(),
+            ResponseStatus::Ok,
+            SequenceId::from(0x00),
+        );
+        transport.push_reply(&mut SyncResponse::new(), ResponseStatus::Ok);
+
+        let mut engine = RequestEngine::new(transport).with_retries(1);
+        engine.set_sequence_flag(SequenceFlag::Set);
+
+        assert!(engine.request(&mut SyncCommand::new())?.is_sync_response());
+        assert_eq!(engine.sequence_id().flag(), SequenceFlag::Unset);
+
+        let sent = engine.transport().sent();
+        assert_eq!(sent.len(), 2);
+        assert!(sent.iter().all(|f| f[index::SEQ_ID] == 0x80));
+
+        // only stale replies, the retries run out without toggling the flag
+        engine.transport_mut().push_reply_with_sequence_id(
+            &mut SyncResponse::new(),
+            ResponseStatus::Ok,
+            SequenceId::from(0x80),
+        );
+
+        assert!(matches!(
+            engine.with_retries(0).request(&mut SyncCommand::new()),
+            Err(Error::Timeout(_))
+        ));
+
+        Ok(())
+    }
+}
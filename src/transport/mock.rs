@@ -0,0 +1,112 @@
+//! Mock [Transport] used for testing host-side logic.
+
+use alloc::vec::Vec;
+
+use crate::{
+    crc::crc16, message::index, std::time::Duration, Error, ResponseOps, ResponseStatus, Result,
+    SequenceId,
+};
+
+use super::{Transport, DEFAULT_TIMEOUT_MS};
+
+/// Mock [Transport] that records sent frames, and replies with queued frames.
+///
+/// Reply frames are given the `SEQID` of the last sent frame, so they match the request, unless
+/// queued with [push_reply_with_sequence_id](Self::push_reply_with_sequence_id).
+#[derive(Default)]
+pub struct MockTransport {
+    sent: Vec<Vec<u8>>,
+    replies: Vec<Result<(Vec<u8>, Option<SequenceId>)>>,
+}
+
+impl MockTransport {
+    /// Creates a new [MockTransport].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the frames sent over the transport.
+    pub fn sent(&self) -> &[Vec<u8>] {
+        self.sent.as_ref()
+    }
+
+    /// Queues a reply message with the provided [ResponseStatus].
+    pub fn push_reply(&mut self, msg: &mut dyn ResponseOps, status: ResponseStatus) {
+        msg.set_response_status(status);
+        self.replies.push(Ok((msg.as_bytes().to_vec(), None)));
+    }
+
+    /// Queues a reply message with the provided [ResponseStatus] and [SequenceId], e.g. a stale
+    /// reply to an earlier request.
+    pub fn push_reply_with_sequence_id(
+        &mut self,
+        msg: &mut dyn ResponseOps,
+        status: ResponseStatus,
+        sequence_id: SequenceId,
+    ) {
+        msg.set_response_status(status);
+        self.replies
+            .push(Ok((msg.as_bytes().to_vec(), Some(sequence_id))));
+    }
+
+    /// Queues an error returned when receiving a reply.
+    pub fn push_error(&mut self, err: Error) {
+        self.replies.push(Err(err));
+    }
+}
+
+impl Transport for MockTransport {
+    fn send_frame(&mut self, frame: &[u8]) -> Result<()> {
+        self.sent.push(frame.to_vec());
+        Ok(())
+    }
+
+    fn receive_frame(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.replies.is_empty() {
+            return Err(Error::Timeout("no queued reply".into()));
+        }
+
+        let (mut reply, sequence_id) = self.replies.remove(0)?;
+        let reply_len = reply.len();
+
+        match (sequence_id, self.sent.last()) {
+            (Some(seq_id), _) => reply[index::SEQ_ID] = seq_id.into(),
+            (None, Some(last)) => reply[index::SEQ_ID] = last[index::SEQ_ID],
+            (None, None) => (),
+        }
+
+        let crc = crc16(reply[index::SEQ_ID..reply_len - 2].as_ref());
+        reply[reply_len - 2..].copy_from_slice(crc.to_le_bytes().as_ref());
+
+        buf[..reply_len].copy_from_slice(reply.as_ref());
+
+        Ok(reply_len)
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(DEFAULT_TIMEOUT_MS)
+    }
+
+    fn set_timeout(&mut self, _timeout: Duration) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl super::AsyncTransport for MockTransport {
+    async fn send_frame(&mut self, frame: &[u8]) -> Result<()> {
+        Transport::send_frame(self, frame)
+    }
+
+    async fn receive_frame(&mut self, buf: &mut [u8]) -> Result<usize> {
+        Transport::receive_frame(self, buf)
+    }
+
+    fn timeout(&self) -> Duration {
+        Transport::timeout(self)
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> Result<()> {
+        Transport::set_timeout(self, timeout)
+    }
+}
@@ -0,0 +1,289 @@
+//! Serial port [Transport] for communicating with SSP devices.
+
+use alloc::string::String;
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant};
+
+use serialport::{DataBits, FlowControl, Parity, SerialPort, StopBits};
+
+use crate::{Error, FrameDecoder, FrameEncoder, Result};
+
+use super::{Transport, DEFAULT_TIMEOUT_MS};
+
+/// Baud rate used by SSP devices.
+pub const SERIAL_BAUD_RATE: u32 = 9600;
+
+// longest a single port read blocks, so reads stop close to the frame deadline
+const PORT_READ_TIMEOUT_MS: u64 = 10;
+
+/// Blocking [Transport] over a serial port.
+///
+/// The port is configured for SSP communication: 9600 baud, eight data bits, no parity, and two
+/// stop bits (8N2).
+pub struct SerialLink {
+    port: Box<dyn SerialPort>,
+    timeout: Duration,
+    encoder: FrameEncoder,
+    decoder: FrameDecoder,
+}
+
+impl SerialLink {
+    /// Opens the serial port at `path`, and configures it for SSP communication.
+    pub fn open(path: &str) -> Result<Self> {
+        let timeout = Duration::from_millis(DEFAULT_TIMEOUT_MS);
+
+        let port = serialport::new(path, SERIAL_BAUD_RATE)
+            .data_bits(DataBits::Eight)
+            .parity(Parity::None)
+            .stop_bits(StopBits::Two)
+            .flow_control(FlowControl::None)
+            .timeout(port_read_timeout(timeout))
+            .open()?;
+
+        Ok(Self::with_timeout(port, timeout))
+    }
+
+    /// Creates a [SerialLink] from an already opened serial port.
+    ///
+    /// The caller is responsible for configuring the port settings. The port timeout is used as
+    /// the frame timeout, see [set_timeout](Transport::set_timeout) to change it. A port without
+    /// a timeout uses [DEFAULT_TIMEOUT_MS], since every frame read would fail immediately.
+    ///
+    /// The port timeout is then replaced with a short per-read timeout, bounded by the frame
+    /// timeout.
+    pub fn from_port(mut port: Box<dyn SerialPort>) -> Self {
+        let timeout = match port.timeout() {
+            timeout if timeout.is_zero() => Duration::from_millis(DEFAULT_TIMEOUT_MS),
+            timeout => timeout,
+        };
+
+        if let Err(err) = port.set_timeout(port_read_timeout(timeout)) {
+            log::warn!("Error setting the serial port read timeout: {err}");
+        }
+
+        Self::with_timeout(port, timeout)
+    }
+
+    fn with_timeout(port: Box<dyn SerialPort>, timeout: Duration) -> Self {
+        Self {
+            port,
+            timeout,
+            encoder: FrameEncoder::new(),
+            decoder: FrameDecoder::new(),
+        }
+    }
+
+    /// Gets the name of the underlying serial port, if available.
+    pub fn name(&self) -> Option<String> {
+        self.port.name()
+    }
+
+    /// Gets a reference to the underlying serial port.
+    pub fn port(&self) -> &dyn SerialPort {
+        self.port.as_ref()
+    }
+
+    /// Gets a mutable reference to the underlying serial port.
+    pub fn port_mut(&mut self) -> &mut dyn SerialPort {
+        self.port.as_mut()
+    }
+}
+
+impl Transport for SerialLink {
+    fn send_frame(&mut self, frame: &[u8]) -> Result<()> {
+        let stuffed = self.encoder.encode(frame)?;
+
+        log::trace!("Sending frame: {stuffed:x?}");
+
+        self.port.write_all(stuffed)?;
+        self.port.flush()?;
+
+        Ok(())
+    }
+
+    fn receive_frame(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let frame_len = read_frame(&mut self.port, &mut self.decoder, buf, self.timeout)?;
+
+        log::trace!("Received frame: {:x?}", &buf[..frame_len]);
+
+        Ok(frame_len)
+    }
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> Result<()> {
+        // a single read must not block past the frame deadline
+        self.port.set_timeout(port_read_timeout(timeout))?;
+        self.timeout = timeout;
+
+        Ok(())
+    }
+}
+
+/// Gets the timeout for a single port read, bounded by the frame `timeout`.
+fn port_read_timeout(timeout: Duration) -> Duration {
+    timeout.min(Duration::from_millis(PORT_READ_TIMEOUT_MS))
+}
+
+/// Reads a complete message frame from a byte stream.
+///
+/// Bytes are pushed through the `decoder` until it produces a frame. Resynchronization errors are
+/// logged and skipped, any other decoding error is returned.
+///
+/// Bytes are read one at a time, so no data following the frame is consumed from the `reader`.
+///
+/// On success, `buf` contains the un-stuffed frame with a valid CRC-16 checksum, and the frame
+/// length is returned.
+pub fn read_frame<R: Read + ?Sized>(
+    reader: &mut R,
+    decoder: &mut FrameDecoder,
+    buf: &mut [u8],
+    timeout: Duration,
+) -> Result<usize> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        while let Some(res) = decoder.pop_frame() {
+            match res {
+                Ok(frame) => {
+                    let (buf_len, frame_len) = (buf.len(), frame.len());
+
+                    if buf_len < frame_len {
+                        return Err(Error::InvalidLength((buf_len, frame_len)));
+                    }
+
+                    buf[..frame_len].copy_from_slice(frame.as_bytes());
+
+                    return Ok(frame_len);
+                }
+                Err(Error::Resync(discarded)) => {
+                    log::warn!("Resynchronized frame, discarded {discarded} byte(s)");
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        let byte = read_byte(reader, deadline)?;
+        decoder.push_bytes(&[byte]);
+    }
+}
+
+fn read_byte<R: Read + ?Sized>(reader: &mut R, deadline: Instant) -> Result<u8> {
+    let mut byte = [0u8];
+
+    loop {
+        if Instant::now() >= deadline {
+            return Err(Error::Timeout("reading message frame".into()));
+        }
+
+        match reader.read(byte.as_mut()) {
+            Ok(1) => return Ok(byte[0]),
+            Ok(_) => {
+                return Err(Error::Io(format!(
+                    "{}",
+                    io::Error::from(io::ErrorKind::UnexpectedEof)
+                )))
+            }
+            Err(err) => match err.kind() {
+                io::ErrorKind::TimedOut
+                | io::ErrorKind::WouldBlock
+                | io::ErrorKind::Interrupted => continue,
+                _ => return Err(err.into()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crc::crc16, len, stuff_frame, MessageOps, SyncCommand, STX};
+
+    fn timeout() -> Duration {
+        Duration::from_millis(DEFAULT_TIMEOUT_MS)
+    }
+
+    #[test]
+    fn test_read_frame() -> Result<()> {
+        let mut msg = SyncCommand::new();
+        let exp = msg.as_bytes().to_vec();
+
+        // leading noise is discarded
+        let mut stream = [0x00, 0x11, 0x22].to_vec();
+        stream.extend_from_slice(exp.as_ref());
+
+        let mut buf = [0u8; len::MAX_MESSAGE];
+        let frame_len = read_frame(
+            &mut stream.as_slice(),
+            &mut FrameDecoder::new(),
+            buf.as_mut(),
+            timeout(),
+        )?;
+
+        assert_eq!(buf[..frame_len].as_ref(), exp.as_slice());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_stuffed_frame() -> Result<()> {
+        // SEQID, LEN, DATA (0x7f 0xaa), CRC
+        let mut exp = [STX, 0x80, 0x02, 0x7f, 0xaa, 0x00, 0x00];
+        let crc = crc16(exp[1..5].as_ref()).to_le_bytes();
+        exp[5..].copy_from_slice(crc.as_ref());
+
+        let mut stuffed = [0u8; len::MAX_STUFFED_MESSAGE];
+        let stuffed_len = stuff_frame(exp.as_ref(), stuffed.as_mut())?;
+
+        // a partial frame interrupted by a new STX is dropped
+        let mut stream = [STX, 0x80, 0x04, 0x01].to_vec();
+        stream.extend_from_slice(stuffed[..stuffed_len].as_ref());
+
+        let mut buf = [0u8; len::MAX_MESSAGE];
+        let frame_len = read_frame(
+            &mut stream.as_slice(),
+            &mut FrameDecoder::new(),
+            buf.as_mut(),
+            timeout(),
+        )?;
+
+        assert_eq!(buf[..frame_len].as_ref(), exp.as_ref());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_frame_bad_crc() {
+        let mut msg = SyncCommand::new();
+        let mut stream = msg.as_bytes().to_vec();
+        let last = stream.len() - 1;
+        stream[last] ^= 0xff;
+
+        let mut buf = [0u8; len::MAX_MESSAGE];
+
+        assert!(matches!(
+            read_frame(
+                &mut stream.as_slice(),
+                &mut FrameDecoder::new(),
+                buf.as_mut(),
+                timeout(),
+            ),
+            Err(Error::Crc(_))
+        ));
+    }
+
+    #[test]
+    fn test_port_read_timeout() {
+        assert_eq!(
+            port_read_timeout(timeout()),
+            Duration::from_millis(PORT_READ_TIMEOUT_MS)
+        );
+        assert_eq!(
+            port_read_timeout(Duration::from_millis(2)),
+            Duration::from_millis(2)
+        );
+        assert_eq!(port_read_timeout(Duration::ZERO), Duration::ZERO);
+    }
+}
@@ -6,10 +6,14 @@ pub(crate) mod barcode;
 pub(crate) mod bezel;
 pub(crate) mod channel_value;
 pub(crate) mod country_code;
+pub(crate) mod currency_value;
+pub(crate) mod denomination_level;
+pub(crate) mod denomination_route;
 pub(crate) mod device_status;
 pub(crate) mod encryption;
 pub(crate) mod events;
 pub(crate) mod firmware;
+pub(crate) mod incomplete_value;
 pub(crate) mod inhibit;
 pub(crate) mod last_reject_code;
 pub(crate) mod message_type;
@@ -24,10 +28,14 @@ pub use barcode::*;
 pub use bezel::*;
 pub use channel_value::*;
 pub use country_code::*;
+pub use currency_value::*;
+pub use denomination_level::*;
+pub use denomination_route::*;
 pub use device_status::*;
 pub use encryption::*;
 pub use events::*;
 pub use firmware::*;
+pub use incomplete_value::*;
 pub use inhibit::*;
 pub use last_reject_code::*;
 pub use message_type::*;
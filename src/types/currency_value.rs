@@ -0,0 +1,186 @@
+use super::{ChannelValue, CountryCode, ProtocolVersion};
+use crate::{len::CURRENCY_VALUE_BLOCK, std::fmt, Error, Result};
+
+mod list;
+pub use list::*;
+
+/// Convenience alias for a serialized [CurrencyValue] array.
+pub type CurrencyValueBlock = [u8; CURRENCY_VALUE_BLOCK];
+
+/// Length of the single value event data used before [ProtocolVersion::Six].
+pub(crate) const SINGLE_VALUE_LEN: usize = 4;
+
+/// Gets whether event data for the [ProtocolVersion] is a single value, instead of a
+/// multi-currency array.
+pub(crate) const fn is_single_value(protocol: ProtocolVersion) -> bool {
+    protocol.to_u8() < ProtocolVersion::Six.to_u8()
+}
+
+/// Represents a value in a specific currency, as reported in payout event data.
+///
+/// Used by events like [DispensingEvent](crate::DispensingEvent) and
+/// [FloatedEvent](crate::FloatedEvent).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct CurrencyValue {
+    value: ChannelValue,
+    currency: CountryCode,
+}
+
+impl CurrencyValue {
+    /// Creates a new [CurrencyValue].
+    pub const fn new() -> Self {
+        Self {
+            value: ChannelValue::from_inner(0),
+            currency: CountryCode::new(),
+        }
+    }
+
+    /// Creates a new [CurrencyValue] with the provided parameters.
+    pub const fn create(value: ChannelValue, currency: CountryCode) -> Self {
+        Self { value, currency }
+    }
+
+    /// Gets the [ChannelValue].
+    pub const fn value(&self) -> ChannelValue {
+        self.value
+    }
+
+    /// Sets the [ChannelValue].
+    pub fn set_value(&mut self, val: ChannelValue) {
+        self.value = val;
+    }
+
+    /// Builder function that sets the [ChannelValue].
+    pub fn with_value(mut self, val: ChannelValue) -> Self {
+        self.set_value(val);
+        self
+    }
+
+    /// Gets the [CountryCode].
+    pub const fn currency(&self) -> CountryCode {
+        self.currency
+    }
+
+    /// Sets the [CountryCode].
+    pub fn set_currency(&mut self, val: CountryCode) {
+        self.currency = val;
+    }
+
+    /// Builder function that sets the [CountryCode].
+    pub fn with_currency(mut self, val: CountryCode) -> Self {
+        self.set_currency(val);
+        self
+    }
+
+    /// Serializes the [CurrencyValue] to a mutable buffer.
+    ///
+    /// Parameters:
+    ///
+    /// - `buf`: mutable reference to a byte buffer
+    ///
+    /// Returns:
+    ///
+    /// - `Ok(())`
+    /// - [`Error::InvalidLength`] if `buf` length is less than [`CURRENCY_VALUE_BLOCK`]
+    pub fn to_buffer(&self, buf: &mut [u8]) -> Result<()> {
+        let buf_len = buf.len();
+        if buf_len < CURRENCY_VALUE_BLOCK {
+            Err(Error::InvalidLength((buf_len, CURRENCY_VALUE_BLOCK)))
+        } else {
+            buf[..4].copy_from_slice(self.value().as_inner().to_le_bytes().as_ref());
+            buf[4..CURRENCY_VALUE_BLOCK].copy_from_slice(<&str>::from(self.currency()).as_bytes());
+
+            Ok(())
+        }
+    }
+
+    /// Deserializes the [CurrencyValue] from a buffer.
+    ///
+    /// Parameters:
+    ///
+    /// - `buf`: reference to a byte buffer
+    ///
+    /// Returns:
+    ///
+    /// - Ok([`CurrencyValue`])
+    /// - [`Error::InvalidLength`] if `buf` length is less than [`CURRENCY_VALUE_BLOCK`]
+    pub fn from_buffer(buf: &[u8]) -> Result<Self> {
+        let len = buf.len();
+
+        if len < CURRENCY_VALUE_BLOCK {
+            Err(Error::InvalidLength((len, CURRENCY_VALUE_BLOCK)))
+        } else {
+            let value = ChannelValue::from(&buf[..4]);
+            let currency = CountryCode::from(&buf[4..CURRENCY_VALUE_BLOCK]);
+
+            Ok(Self::create(value, currency))
+        }
+    }
+}
+
+impl From<CurrencyValue> for CurrencyValueBlock {
+    fn from(val: CurrencyValue) -> Self {
+        let mut res = [0u8; CURRENCY_VALUE_BLOCK];
+
+        // buffer is a valid length, so `to_buffer` will never fail.
+        val.to_buffer(res.as_mut()).ok();
+
+        res
+    }
+}
+
+impl From<&CurrencyValueBlock> for CurrencyValue {
+    fn from(val: &CurrencyValueBlock) -> Self {
+        // the unwrap branch should never happen, since the buffer length is valid.
+        Self::from_buffer(val.as_ref()).unwrap_or(Self::new())
+    }
+}
+
+impl TryFrom<&[u8]> for CurrencyValue {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        Self::from_buffer(val)
+    }
+}
+
+impl fmt::Display for CurrencyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""value":{},"#, self.value())?;
+        write!(f, r#""currency":{}"#, self.currency())?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_currency_value() -> Result<()> {
+        // EUR 15.30 paid up to a halt, from the SSP Protocol Manual
+        let buf: CurrencyValueBlock = [0xfa, 0x05, 0x00, 0x00, 0x45, 0x55, 0x52];
+        let exp_value = CurrencyValue::create(ChannelValue::from(1530), CountryCode::EUR);
+
+        assert_eq!(CurrencyValue::try_from(buf.as_ref())?, exp_value);
+        assert_eq!(CurrencyValueBlock::from(exp_value), buf);
+
+        let mut data = [0u8; 1 + CURRENCY_VALUE_BLOCK];
+        data[0] = 1;
+        data[1..].copy_from_slice(buf.as_ref());
+
+        assert_eq!(
+            CurrencyValueList::event_data_len(data.as_ref(), ProtocolVersion::Six)?,
+            data.len()
+        );
+        assert_eq!(
+            CurrencyValueList::from_event_data(data.as_ref())?,
+            CurrencyValueList::from([exp_value])
+        );
+        assert!(CurrencyValueList::from_event_data(data[..5].as_ref()).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+use super::{is_single_value, CurrencyValue, SINGLE_VALUE_LEN};
+use crate::{
+    arrays::{deserialize_vec, serialize_vec},
+    len::{CURRENCY_VALUE_BLOCK, MAX_DATA},
+    std::{cmp, fmt, slice},
+    ChannelValue, Error, ProtocolVersion, Result,
+};
+
+/// Maximum number of [CurrencyValue]s in a single poll event.
+///
+/// Limited by the response data length, minus the status and number of countries bytes.
+pub const MAX_CURRENCY_VALUES: usize = (MAX_DATA - 2) / CURRENCY_VALUE_BLOCK;
+
+/// Convenience alias for a [CurrencyValue] vector.
+pub type CurrencyValueVec = heapless::Vec<CurrencyValue, MAX_CURRENCY_VALUES>;
+
+/// Container for a list of [CurrencyValue]s.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct CurrencyValueList {
+    #[serde(serialize_with = "serialize_vec", deserialize_with = "deserialize_vec")]
+    values: CurrencyValueVec,
+}
+
+impl CurrencyValueList {
+    /// Creates a new empty list.
+    pub const fn new() -> Self {
+        Self {
+            values: CurrencyValueVec::new(),
+        }
+    }
+
+    /// Creates a new [CurrencyValueList] from the provided [CurrencyValue]s.
+    pub const fn create(values: CurrencyValueVec) -> Self {
+        Self { values }
+    }
+
+    /// Gets an iterator over the list.
+    pub fn iter(&self) -> slice::Iter<'_, CurrencyValue> {
+        self.values.iter()
+    }
+
+    /// Gets a mutable iterator over the list.
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, CurrencyValue> {
+        self.values.iter_mut()
+    }
+
+    /// Gets the list length.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Gets the list capacity.
+    pub fn capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
+    /// Gets whether the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Get the list as a reference to its inner container type.
+    pub fn as_inner(&self) -> &CurrencyValueVec {
+        &self.values
+    }
+
+    /// Get the list as a mutable reference to its inner container type.
+    pub fn as_inner_mut(&mut self) -> &mut CurrencyValueVec {
+        &mut self.values
+    }
+
+    /// Converts the list into its inner container type.
+    pub fn into_inner(self) -> CurrencyValueVec {
+        self.values
+    }
+
+    /// Gets the length of the event data at the start of `data`, not including the event status
+    /// byte.
+    ///
+    /// Before [ProtocolVersion::Six], event data is a single 4 byte value. Later versions send the
+    /// number of countries, followed by a [CurrencyValue] block for each country.
+    pub fn event_data_len(data: &[u8], protocol: ProtocolVersion) -> Result<usize> {
+        if is_single_value(protocol) {
+            Ok(SINGLE_VALUE_LEN)
+        } else {
+            let num = data.first().ok_or(Error::InvalidLength((0, 1)))?;
+            Ok(1 + (*num as usize * CURRENCY_VALUE_BLOCK))
+        }
+    }
+
+    /// Parses the list from event data, not including the event status byte.
+    ///
+    /// Single value event data (before [ProtocolVersion::Six]) does not include a country code,
+    /// so the value is returned with the default [CountryCode](crate::CountryCode).
+    pub fn from_event_data(data: &[u8]) -> Result<Self> {
+        let data_len = data.len();
+
+        if data_len == SINGLE_VALUE_LEN {
+            let mut values = CurrencyValueVec::new();
+            // list is empty, so `push` will not fail
+            values
+                .push(CurrencyValue::new().with_value(ChannelValue::from(data)))
+                .ok();
+
+            return Ok(values.into());
+        }
+
+        let num = data
+            .first()
+            .map(|&n| n as usize)
+            .ok_or(Error::InvalidLength((0, 1)))?;
+        let end = 1 + (num * CURRENCY_VALUE_BLOCK);
+
+        if num > MAX_CURRENCY_VALUES {
+            Err(Error::InvalidLength((num, MAX_CURRENCY_VALUES)))
+        } else if data_len != end {
+            Err(Error::InvalidLength((data_len, end)))
+        } else {
+            let mut values = CurrencyValueVec::new();
+
+            for block in data[1..end].chunks_exact(CURRENCY_VALUE_BLOCK) {
+                // `push` only fails if `heapless::Vec` is full
+                // since we checked valid range above, `push` will not fail
+                values.push(CurrencyValue::try_from(block)?).ok();
+            }
+
+            Ok(values.into())
+        }
+    }
+}
+
+impl AsRef<[CurrencyValue]> for CurrencyValueList {
+    fn as_ref(&self) -> &[CurrencyValue] {
+        self.values.as_slice()
+    }
+}
+
+impl AsMut<[CurrencyValue]> for CurrencyValueList {
+    fn as_mut(&mut self) -> &mut [CurrencyValue] {
+        self.values.as_mut()
+    }
+}
+
+impl From<CurrencyValueVec> for CurrencyValueList {
+    fn from(val: CurrencyValueVec) -> Self {
+        Self { values: val }
+    }
+}
+
+impl From<&[CurrencyValue]> for CurrencyValueList {
+    fn from(val: &[CurrencyValue]) -> Self {
+        let len = cmp::min(val.len(), MAX_CURRENCY_VALUES);
+        let mut values = CurrencyValueVec::new();
+
+        // the above call to `min` ensures the length is in the valid range
+        values.extend_from_slice(&val[..len]).ok();
+
+        Self { values }
+    }
+}
+
+impl<const N: usize> From<[CurrencyValue; N]> for CurrencyValueList {
+    fn from(val: [CurrencyValue; N]) -> Self {
+        val.as_ref().into()
+    }
+}
+
+impl<const N: usize> From<&[CurrencyValue; N]> for CurrencyValueList {
+    fn from(val: &[CurrencyValue; N]) -> Self {
+        val.as_ref().into()
+    }
+}
+
+impl fmt::Display for CurrencyValueList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""values":["#)?;
+
+        for (i, value) in self.iter().enumerate() {
+            write!(f, "{value}")?;
+
+            if i < self.len() - 1 {
+                write!(f, ",")?;
+            }
+        }
+
+        write!(f, "]}}")
+    }
+}
+
+impl Default for CurrencyValueList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,187 @@
+use super::{ChannelValue, CountryCode};
+use crate::{len::LEVEL_BLOCK, std::fmt, Error, Result};
+
+mod list;
+pub use list::*;
+
+/// Convenience alias for a serialized [DenominationLevel] array.
+pub type LevelBlock = [u8; LEVEL_BLOCK];
+
+/// Represents the stored level of a denomination in a payout device.
+///
+/// Returned in a [GetAllLevelsResponse](crate::GetAllLevelsResponse) message.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct DenominationLevel {
+    count: u16,
+    value: ChannelValue,
+    currency: CountryCode,
+}
+
+impl DenominationLevel {
+    /// Creates a new [DenominationLevel].
+    pub const fn new() -> Self {
+        Self {
+            count: 0,
+            value: ChannelValue::from_inner(0),
+            currency: CountryCode::new(),
+        }
+    }
+
+    /// Creates a new [DenominationLevel] with the provided parameters.
+    pub const fn create(count: u16, value: ChannelValue, currency: CountryCode) -> Self {
+        Self {
+            count,
+            value,
+            currency,
+        }
+    }
+
+    /// Gets the number of notes stored.
+    pub const fn count(&self) -> u16 {
+        self.count
+    }
+
+    /// Sets the number of notes stored.
+    pub fn set_count(&mut self, val: u16) {
+        self.count = val;
+    }
+
+    /// Builder function that sets the number of notes stored.
+    pub fn with_count(mut self, val: u16) -> Self {
+        self.set_count(val);
+        self
+    }
+
+    /// Gets the denomination [ChannelValue].
+    pub const fn value(&self) -> ChannelValue {
+        self.value
+    }
+
+    /// Sets the denomination [ChannelValue].
+    pub fn set_value(&mut self, val: ChannelValue) {
+        self.value = val;
+    }
+
+    /// Builder function that sets the denomination [ChannelValue].
+    pub fn with_value(mut self, val: ChannelValue) -> Self {
+        self.set_value(val);
+        self
+    }
+
+    /// Gets the denomination [CountryCode].
+    pub const fn currency(&self) -> CountryCode {
+        self.currency
+    }
+
+    /// Sets the denomination [CountryCode].
+    pub fn set_currency(&mut self, val: CountryCode) {
+        self.currency = val;
+    }
+
+    /// Builder function that sets the denomination [CountryCode].
+    pub fn with_currency(mut self, val: CountryCode) -> Self {
+        self.set_currency(val);
+        self
+    }
+
+    /// Serializes the [DenominationLevel] to a mutable buffer.
+    ///
+    /// Parameters:
+    ///
+    /// - `buf`: mutable reference to a byte buffer
+    ///
+    /// Returns:
+    ///
+    /// - `Ok(())`
+    /// - [`Error::InvalidLength`] if `buf` length is less than [`LEVEL_BLOCK`]
+    pub fn to_buffer(&self, buf: &mut [u8]) -> Result<()> {
+        let buf_len = buf.len();
+        if buf_len < LEVEL_BLOCK {
+            Err(Error::InvalidLength((buf_len, LEVEL_BLOCK)))
+        } else {
+            buf[..2].copy_from_slice(self.count().to_le_bytes().as_ref());
+            buf[2..6].copy_from_slice(self.value().as_inner().to_le_bytes().as_ref());
+            buf[6..LEVEL_BLOCK].copy_from_slice(<&str>::from(self.currency()).as_bytes());
+
+            Ok(())
+        }
+    }
+
+    /// Deserializes the [DenominationLevel] from a buffer.
+    ///
+    /// Parameters:
+    ///
+    /// - `buf`: reference to a byte buffer
+    ///
+    /// Returns:
+    ///
+    /// - Ok([`DenominationLevel`])
+    /// - [`Error::InvalidLength`] if `buf` length is less than [`LEVEL_BLOCK`]
+    pub fn from_buffer(buf: &[u8]) -> Result<Self> {
+        let len = buf.len();
+
+        if len < LEVEL_BLOCK {
+            Err(Error::InvalidLength((len, LEVEL_BLOCK)))
+        } else {
+            let count = u16::from_le_bytes(buf[..2].try_into().unwrap_or([0; 2]));
+            let value = ChannelValue::from(&buf[2..6]);
+            let currency = CountryCode::from(&buf[6..LEVEL_BLOCK]);
+
+            Ok(Self::create(count, value, currency))
+        }
+    }
+}
+
+impl From<DenominationLevel> for LevelBlock {
+    fn from(val: DenominationLevel) -> Self {
+        let mut res = [0u8; LEVEL_BLOCK];
+
+        // buffer is a valid length, so `to_buffer` will never fail.
+        val.to_buffer(res.as_mut()).ok();
+
+        res
+    }
+}
+
+impl From<&LevelBlock> for DenominationLevel {
+    fn from(val: &LevelBlock) -> Self {
+        // the unwrap branch should never happen, since the buffer length is valid.
+        Self::from_buffer(val.as_ref()).unwrap_or(Self::new())
+    }
+}
+
+impl TryFrom<&[u8]> for DenominationLevel {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        Self::from_buffer(val)
+    }
+}
+
+impl fmt::Display for DenominationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""count":{},"#, self.count())?;
+        write!(f, r#""value":{},"#, self.value())?;
+        write!(f, r#""currency":{}"#, self.currency())?;
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_denomination_level() -> Result<()> {
+        // 5 notes of EUR 5.00, from the SSP Implementation Guide
+        let buf: LevelBlock = [0x05, 0x00, 0xf4, 0x01, 0x00, 0x00, 0x45, 0x55, 0x52];
+        let exp_level = DenominationLevel::create(5, ChannelValue::from(500), CountryCode::EUR);
+
+        assert_eq!(DenominationLevel::try_from(buf.as_ref())?, exp_level);
+        assert_eq!(LevelBlock::from(exp_level), buf);
+
+        Ok(())
+    }
+}
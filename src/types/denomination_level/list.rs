@@ -0,0 +1,142 @@
+use super::DenominationLevel;
+use crate::{
+    arrays::{deserialize_vec, serialize_vec},
+    len::{LEVEL_BLOCK, MAX_DATA},
+    std::{cmp, fmt, slice},
+};
+
+/// Maximum number of [DenominationLevel]s in a single
+/// [GetAllLevelsResponse](crate::GetAllLevelsResponse) message.
+///
+/// Limited by the response data length, minus the status and number of denominations bytes.
+pub const MAX_LEVELS: usize = (MAX_DATA - 2) / LEVEL_BLOCK;
+
+/// Convenience alias for a [DenominationLevel] vector.
+pub type LevelVec = heapless::Vec<DenominationLevel, MAX_LEVELS>;
+
+/// Container for a list of [DenominationLevel]s.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct DenominationLevelList {
+    #[serde(serialize_with = "serialize_vec", deserialize_with = "deserialize_vec")]
+    levels: LevelVec,
+}
+
+impl DenominationLevelList {
+    /// Creates a new empty list.
+    pub const fn new() -> Self {
+        Self {
+            levels: LevelVec::new(),
+        }
+    }
+
+    /// Creates a new [DenominationLevelList] from the provided [DenominationLevel]s.
+    pub const fn create(levels: LevelVec) -> Self {
+        Self { levels }
+    }
+
+    /// Gets an iterator over the list.
+    pub fn iter(&self) -> slice::Iter<'_, DenominationLevel> {
+        self.levels.iter()
+    }
+
+    /// Gets a mutable iterator over the list.
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, DenominationLevel> {
+        self.levels.iter_mut()
+    }
+
+    /// Gets the list length.
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Gets the list capacity.
+    pub fn capacity(&self) -> usize {
+        self.levels.capacity()
+    }
+
+    /// Gets whether the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    /// Get the list as a reference to its inner container type.
+    pub fn as_inner(&self) -> &LevelVec {
+        &self.levels
+    }
+
+    /// Get the list as a mutable reference to its inner container type.
+    pub fn as_inner_mut(&mut self) -> &mut LevelVec {
+        &mut self.levels
+    }
+
+    /// Converts the list into its inner container type.
+    pub fn into_inner(self) -> LevelVec {
+        self.levels
+    }
+}
+
+impl AsRef<[DenominationLevel]> for DenominationLevelList {
+    fn as_ref(&self) -> &[DenominationLevel] {
+        self.levels.as_slice()
+    }
+}
+
+impl AsMut<[DenominationLevel]> for DenominationLevelList {
+    fn as_mut(&mut self) -> &mut [DenominationLevel] {
+        self.levels.as_mut()
+    }
+}
+
+impl From<LevelVec> for DenominationLevelList {
+    fn from(val: LevelVec) -> Self {
+        Self { levels: val }
+    }
+}
+
+impl From<&[DenominationLevel]> for DenominationLevelList {
+    fn from(val: &[DenominationLevel]) -> Self {
+        let len = cmp::min(val.len(), MAX_LEVELS);
+        let mut levels = LevelVec::new();
+
+        // the above call to `min` ensures the length is in the valid range
+        levels.extend_from_slice(&val[..len]).ok();
+
+        Self { levels }
+    }
+}
+
+impl<const N: usize> From<[DenominationLevel; N]> for DenominationLevelList {
+    fn from(val: [DenominationLevel; N]) -> Self {
+        val.as_ref().into()
+    }
+}
+
+impl<const N: usize> From<&[DenominationLevel; N]> for DenominationLevelList {
+    fn from(val: &[DenominationLevel; N]) -> Self {
+        val.as_ref().into()
+    }
+}
+
+impl fmt::Display for DenominationLevelList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""levels":["#)?;
+
+        for (i, level) in self.iter().enumerate() {
+            write!(f, "{level}")?;
+
+            if i < self.len() - 1 {
+                write!(f, ",")?;
+            }
+        }
+
+        write!(f, "]}}")
+    }
+}
+
+impl Default for DenominationLevelList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
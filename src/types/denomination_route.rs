@@ -0,0 +1,55 @@
+use crate::std::fmt;
+
+/// Route for a denomination accepted by a device with an attached payout module.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DenominationRoute {
+    /// Store the denomination, making it available for later payout.
+    #[default]
+    Payout = 0x00,
+    /// Route the denomination to the cashbox on detection.
+    Cashbox = 0x01,
+}
+
+impl From<u8> for DenominationRoute {
+    fn from(val: u8) -> Self {
+        match val {
+            0x00 => Self::Payout,
+            0x01 => Self::Cashbox,
+            _ => Self::Payout,
+        }
+    }
+}
+
+impl From<DenominationRoute> for u8 {
+    fn from(val: DenominationRoute) -> Self {
+        val as u8
+    }
+}
+
+impl From<&DenominationRoute> for u8 {
+    fn from(val: &DenominationRoute) -> Self {
+        (*val).into()
+    }
+}
+
+impl From<DenominationRoute> for &'static str {
+    fn from(val: DenominationRoute) -> Self {
+        match val {
+            DenominationRoute::Payout => "Payout",
+            DenominationRoute::Cashbox => "Cashbox",
+        }
+    }
+}
+
+impl From<&DenominationRoute> for &'static str {
+    fn from(val: &DenominationRoute) -> Self {
+        (*val).into()
+    }
+}
+
+impl fmt::Display for DenominationRoute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, <&'static str>::from(self))
+    }
+}
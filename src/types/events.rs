@@ -1,51 +1,95 @@
 //! Event types for polling responses.
 
-use crate::{std::fmt, Error};
+use crate::{std::fmt, Error, ProtocolVersion, ResponseStatus, Result};
 
 mod cashbox_removed;
 mod cashbox_replaced;
+mod channel_disable;
+mod device_full;
 mod disable;
 mod disabled;
 mod dispense;
+mod dispensed;
+mod dispensing;
+mod emptied;
+mod emptying;
 mod enable;
+mod floated;
+mod floating;
 mod fraud_attempt;
+mod halted;
+mod incomplete_float;
+mod incomplete_payout;
+mod iter;
+mod jammed;
 mod method;
 mod note_cleared_from_front;
 mod note_cleared_into_cashbox;
 mod note_credit;
+mod note_held_in_bezel;
+mod note_path_open;
+mod note_stored_in_payout;
+mod note_transferred_to_stacker;
+mod payout_out_of_service;
 mod read;
 mod reject;
 mod rejected;
 mod rejecting;
 mod reset;
+mod set_inhibits;
+mod smart_emptied;
+mod smart_emptying;
 mod stack;
 mod stacked;
 mod stacker_full;
 mod stacking;
 mod status;
+mod timeout;
 mod unsafe_jam;
 
 pub use cashbox_removed::*;
 pub use cashbox_replaced::*;
+pub use channel_disable::*;
+pub use device_full::*;
 pub use disable::*;
 pub use disabled::*;
 pub use dispense::*;
+pub use dispensed::*;
+pub use dispensing::*;
+pub use emptied::*;
+pub use emptying::*;
 pub use enable::*;
+pub use floated::*;
+pub use floating::*;
 pub use fraud_attempt::*;
+pub use halted::*;
+pub use incomplete_float::*;
+pub use incomplete_payout::*;
+pub use iter::*;
+pub use jammed::*;
 pub use method::*;
 pub use note_cleared_from_front::*;
 pub use note_cleared_into_cashbox::*;
 pub use note_credit::*;
+pub use note_held_in_bezel::*;
+pub use note_path_open::*;
+pub use note_stored_in_payout::*;
+pub use note_transferred_to_stacker::*;
+pub use payout_out_of_service::*;
 pub use read::*;
 pub use reject::*;
 pub use rejected::*;
 pub use rejecting::*;
 pub use reset::*;
+pub use set_inhibits::*;
+pub use smart_emptied::*;
+pub use smart_emptying::*;
 pub use stack::*;
 pub use stacked::*;
 pub use stacker_full::*;
 pub use stacking::*;
 pub use status::*;
+pub use timeout::*;
 pub use unsafe_jam::*;
 
 /// JSON-RPC payloads for request parameters and response results.
@@ -57,6 +101,7 @@ pub enum EventPayload {
     DispenseEvent(DispenseEvent),
     EnableEvent(EnableEvent),
     RejectEvent(RejectEvent),
+    SetInhibitsEvent(SetInhibitsEvent),
     StackEvent(StackEvent),
     StatusEvent(StatusEvent),
     // Response event payloads
@@ -76,6 +121,28 @@ pub enum EventPayload {
     StackerFullEvent(StackerFullEvent),
     StackingEvent(StackingEvent),
     UnsafeJamEvent(UnsafeJamEvent),
+    // Payout response event payloads
+    DispensingEvent(DispensingEvent),
+    DispensedEvent(DispensedEvent),
+    JammedEvent(JammedEvent),
+    HaltedEvent(HaltedEvent),
+    FloatingEvent(FloatingEvent),
+    FloatedEvent(FloatedEvent),
+    TimeoutEvent(TimeoutEvent),
+    IncompletePayoutEvent(IncompletePayoutEvent),
+    IncompleteFloatEvent(IncompleteFloatEvent),
+    // Storage response event payloads
+    EmptyingEvent(EmptyingEvent),
+    EmptiedEvent(EmptiedEvent),
+    SmartEmptyingEvent(SmartEmptyingEvent),
+    SmartEmptiedEvent(SmartEmptiedEvent),
+    NoteStoredInPayoutEvent(NoteStoredInPayoutEvent),
+    NoteTransferredToStackerEvent(NoteTransferredToStackerEvent),
+    NoteHeldInBezelEvent(NoteHeldInBezelEvent),
+    DeviceFullEvent(DeviceFullEvent),
+    PayoutOutOfServiceEvent(PayoutOutOfServiceEvent),
+    NotePathOpenEvent(NotePathOpenEvent),
+    ChannelDisableEvent(ChannelDisableEvent),
 }
 
 impl EventPayload {
@@ -86,6 +153,7 @@ impl EventPayload {
             Self::DispenseEvent(_) => DispenseEvent::method(),
             Self::EnableEvent(_) => EnableEvent::method(),
             Self::RejectEvent(_) => RejectEvent::method(),
+            Self::SetInhibitsEvent(_) => SetInhibitsEvent::method(),
             Self::StackEvent(_) => StackEvent::method(),
             Self::StatusEvent(_) => StatusEvent::method(),
             Self::CashboxRemovedEvent(_) => CashboxRemovedEvent::method(),
@@ -103,6 +171,132 @@ impl EventPayload {
             Self::StackerFullEvent(_) => StackerFullEvent::method(),
             Self::StackingEvent(_) => StackingEvent::method(),
             Self::UnsafeJamEvent(_) => UnsafeJamEvent::method(),
+            Self::DispensingEvent(_) => DispensingEvent::method(),
+            Self::DispensedEvent(_) => DispensedEvent::method(),
+            Self::JammedEvent(_) => JammedEvent::method(),
+            Self::HaltedEvent(_) => HaltedEvent::method(),
+            Self::FloatingEvent(_) => FloatingEvent::method(),
+            Self::FloatedEvent(_) => FloatedEvent::method(),
+            Self::TimeoutEvent(_) => TimeoutEvent::method(),
+            Self::IncompletePayoutEvent(_) => IncompletePayoutEvent::method(),
+            Self::IncompleteFloatEvent(_) => IncompleteFloatEvent::method(),
+            Self::EmptyingEvent(_) => EmptyingEvent::method(),
+            Self::EmptiedEvent(_) => EmptiedEvent::method(),
+            Self::SmartEmptyingEvent(_) => SmartEmptyingEvent::method(),
+            Self::SmartEmptiedEvent(_) => SmartEmptiedEvent::method(),
+            Self::NoteStoredInPayoutEvent(_) => NoteStoredInPayoutEvent::method(),
+            Self::NoteTransferredToStackerEvent(_) => NoteTransferredToStackerEvent::method(),
+            Self::NoteHeldInBezelEvent(_) => NoteHeldInBezelEvent::method(),
+            Self::DeviceFullEvent(_) => DeviceFullEvent::method(),
+            Self::PayoutOutOfServiceEvent(_) => PayoutOutOfServiceEvent::method(),
+            Self::NotePathOpenEvent(_) => NotePathOpenEvent::method(),
+            Self::ChannelDisableEvent(_) => ChannelDisableEvent::method(),
+        }
+    }
+
+    /// Gets the length of the poll event at the start of `data`, including the status byte.
+    ///
+    /// Some events change length depending on the [ProtocolVersion] negotiated with the device.
+    pub fn poll_event_len(data: &[u8], protocol: ProtocolVersion) -> Result<usize> {
+        let status = data
+            .first()
+            .map(|&s| ResponseStatus::from(s))
+            .ok_or(Error::InvalidLength((0, 1)))?;
+
+        match status {
+            ResponseStatus::CashboxRemoved => Ok(CashboxRemovedEvent::len()),
+            ResponseStatus::CashboxReplaced => Ok(CashboxReplacedEvent::len()),
+            ResponseStatus::Disabled => Ok(DisabledEvent::len()),
+            ResponseStatus::FraudAttempt => Ok(FraudAttemptEvent::len()),
+            ResponseStatus::NoteClearedFromFront => Ok(NoteClearedFromFrontEvent::len()),
+            ResponseStatus::NoteClearedIntoCashbox => Ok(NoteClearedIntoCashboxEvent::len()),
+            ResponseStatus::NoteCredit => Ok(NoteCreditEvent::len()),
+            ResponseStatus::Read => Ok(ReadEvent::len()),
+            ResponseStatus::Rejected => Ok(RejectedEvent::len()),
+            ResponseStatus::Rejecting => Ok(RejectingEvent::len()),
+            ResponseStatus::DeviceReset => Ok(ResetEvent::len()),
+            ResponseStatus::Stacked => Ok(StackedEvent::len()),
+            ResponseStatus::StackerFull => Ok(StackerFullEvent::len()),
+            ResponseStatus::Stacking => Ok(StackingEvent::len()),
+            ResponseStatus::UnsafeJam => Ok(UnsafeJamEvent::len()),
+            ResponseStatus::Dispensing => DispensingEvent::len(data, protocol),
+            ResponseStatus::Dispensed => DispensedEvent::len(data, protocol),
+            ResponseStatus::Jammed => JammedEvent::len(data, protocol),
+            ResponseStatus::Halted => HaltedEvent::len(data, protocol),
+            ResponseStatus::Floating => FloatingEvent::len(data, protocol),
+            ResponseStatus::Floated => FloatedEvent::len(data, protocol),
+            ResponseStatus::Timeout => TimeoutEvent::len(data, protocol),
+            ResponseStatus::IncompletePayout => IncompletePayoutEvent::len(data, protocol),
+            ResponseStatus::IncompleteFloat => IncompleteFloatEvent::len(data, protocol),
+            ResponseStatus::Emptying => Ok(EmptyingEvent::len()),
+            ResponseStatus::Emptied => Ok(EmptiedEvent::len()),
+            ResponseStatus::SmartEmptying => SmartEmptyingEvent::len(data, protocol),
+            ResponseStatus::SmartEmptied => SmartEmptiedEvent::len(data, protocol),
+            ResponseStatus::NoteStoredInPayout => Ok(NoteStoredInPayoutEvent::len()),
+            ResponseStatus::NoteTransferredToStacker => Ok(NoteTransferredToStackerEvent::len()),
+            ResponseStatus::NoteHeldInBezel => Ok(NoteHeldInBezelEvent::len()),
+            ResponseStatus::DeviceFull => Ok(DeviceFullEvent::len()),
+            ResponseStatus::PayoutOutOfService => Ok(PayoutOutOfServiceEvent::len()),
+            ResponseStatus::NotePathOpen => Ok(NotePathOpenEvent::len()),
+            ResponseStatus::ChannelDisable => Ok(ChannelDisableEvent::len()),
+            status => Err(Error::Event(format!("unknown poll event: {status}"))),
+        }
+    }
+
+    /// Parses the poll event at the start of `data` into an [EventPayload].
+    pub fn from_poll_event(data: &[u8], protocol: ProtocolVersion) -> Result<Self> {
+        let event_len = Self::poll_event_len(data, protocol)?;
+        let event = data
+            .get(..event_len)
+            .ok_or(Error::InvalidLength((data.len(), event_len)))?;
+
+        match ResponseStatus::from(event[0]) {
+            ResponseStatus::CashboxRemoved => Ok(CashboxRemovedEvent::try_from(event)?.into()),
+            ResponseStatus::CashboxReplaced => Ok(CashboxReplacedEvent::try_from(event)?.into()),
+            ResponseStatus::Disabled => Ok(DisabledEvent::try_from(event)?.into()),
+            ResponseStatus::FraudAttempt => Ok(FraudAttemptEvent::try_from(event)?.into()),
+            ResponseStatus::NoteClearedFromFront => {
+                Ok(NoteClearedFromFrontEvent::try_from(event)?.into())
+            }
+            ResponseStatus::NoteClearedIntoCashbox => {
+                Ok(NoteClearedIntoCashboxEvent::try_from(event)?.into())
+            }
+            ResponseStatus::NoteCredit => Ok(NoteCreditEvent::try_from(event)?.into()),
+            ResponseStatus::Read => Ok(ReadEvent::try_from(event)?.into()),
+            ResponseStatus::Rejected => Ok(RejectedEvent::try_from(event)?.into()),
+            ResponseStatus::Rejecting => Ok(RejectingEvent::try_from(event)?.into()),
+            ResponseStatus::DeviceReset => Ok(ResetEvent::try_from(event)?.into()),
+            ResponseStatus::Stacked => Ok(StackedEvent::try_from(event)?.into()),
+            ResponseStatus::StackerFull => Ok(StackerFullEvent::try_from(event)?.into()),
+            ResponseStatus::Stacking => Ok(StackingEvent::try_from(event)?.into()),
+            ResponseStatus::UnsafeJam => Ok(UnsafeJamEvent::try_from(event)?.into()),
+            ResponseStatus::Dispensing => Ok(DispensingEvent::try_from(event)?.into()),
+            ResponseStatus::Dispensed => Ok(DispensedEvent::try_from(event)?.into()),
+            ResponseStatus::Jammed => Ok(JammedEvent::try_from(event)?.into()),
+            ResponseStatus::Halted => Ok(HaltedEvent::try_from(event)?.into()),
+            ResponseStatus::Floating => Ok(FloatingEvent::try_from(event)?.into()),
+            ResponseStatus::Floated => Ok(FloatedEvent::try_from(event)?.into()),
+            ResponseStatus::Timeout => Ok(TimeoutEvent::try_from(event)?.into()),
+            ResponseStatus::IncompletePayout => Ok(IncompletePayoutEvent::try_from(event)?.into()),
+            ResponseStatus::IncompleteFloat => Ok(IncompleteFloatEvent::try_from(event)?.into()),
+            ResponseStatus::Emptying => Ok(EmptyingEvent::try_from(event)?.into()),
+            ResponseStatus::Emptied => Ok(EmptiedEvent::try_from(event)?.into()),
+            ResponseStatus::SmartEmptying => Ok(SmartEmptyingEvent::try_from(event)?.into()),
+            ResponseStatus::SmartEmptied => Ok(SmartEmptiedEvent::try_from(event)?.into()),
+            ResponseStatus::NoteStoredInPayout => {
+                Ok(NoteStoredInPayoutEvent::try_from(event)?.into())
+            }
+            ResponseStatus::NoteTransferredToStacker => {
+                Ok(NoteTransferredToStackerEvent::try_from(event)?.into())
+            }
+            ResponseStatus::NoteHeldInBezel => Ok(NoteHeldInBezelEvent::try_from(event)?.into()),
+            ResponseStatus::DeviceFull => Ok(DeviceFullEvent::try_from(event)?.into()),
+            ResponseStatus::PayoutOutOfService => {
+                Ok(PayoutOutOfServiceEvent::try_from(event)?.into())
+            }
+            ResponseStatus::NotePathOpen => Ok(NotePathOpenEvent::try_from(event)?.into()),
+            ResponseStatus::ChannelDisable => Ok(ChannelDisableEvent::try_from(event)?.into()),
+            status => Err(Error::Event(format!("unknown poll event: {status}"))),
         }
     }
 
@@ -116,6 +310,7 @@ impl EventPayload {
             Self::DispenseEvent(evt) => json!(evt),
             Self::EnableEvent(evt) => json!(evt),
             Self::RejectEvent(evt) => json!(evt),
+            Self::SetInhibitsEvent(evt) => json!(evt),
             Self::StackEvent(evt) => json!(evt),
             Self::StatusEvent(evt) => json!(evt),
             Self::CashboxRemovedEvent(evt) => json!(evt),
@@ -133,6 +328,26 @@ impl EventPayload {
             Self::StackerFullEvent(evt) => json!(evt),
             Self::StackingEvent(evt) => json!(evt),
             Self::UnsafeJamEvent(evt) => json!(evt),
+            Self::DispensingEvent(evt) => json!(evt),
+            Self::DispensedEvent(evt) => json!(evt),
+            Self::JammedEvent(evt) => json!(evt),
+            Self::HaltedEvent(evt) => json!(evt),
+            Self::FloatingEvent(evt) => json!(evt),
+            Self::FloatedEvent(evt) => json!(evt),
+            Self::TimeoutEvent(evt) => json!(evt),
+            Self::IncompletePayoutEvent(evt) => json!(evt),
+            Self::IncompleteFloatEvent(evt) => json!(evt),
+            Self::EmptyingEvent(evt) => json!(evt),
+            Self::EmptiedEvent(evt) => json!(evt),
+            Self::SmartEmptyingEvent(evt) => json!(evt),
+            Self::SmartEmptiedEvent(evt) => json!(evt),
+            Self::NoteStoredInPayoutEvent(evt) => json!(evt),
+            Self::NoteTransferredToStackerEvent(evt) => json!(evt),
+            Self::NoteHeldInBezelEvent(evt) => json!(evt),
+            Self::DeviceFullEvent(evt) => json!(evt),
+            Self::PayoutOutOfServiceEvent(evt) => json!(evt),
+            Self::NotePathOpenEvent(evt) => json!(evt),
+            Self::ChannelDisableEvent(evt) => json!(evt),
         }
     }
 }
@@ -145,6 +360,7 @@ impl fmt::Display for EventPayload {
             Self::DispenseEvent(evt) => write!(f, "{evt}"),
             Self::EnableEvent(evt) => write!(f, "{evt}"),
             Self::RejectEvent(evt) => write!(f, "{evt}"),
+            Self::SetInhibitsEvent(evt) => write!(f, "{evt}"),
             Self::StackEvent(evt) => write!(f, "{evt}"),
             Self::StatusEvent(evt) => write!(f, "{evt}"),
             Self::CashboxRemovedEvent(evt) => write!(f, "{evt}"),
@@ -162,6 +378,26 @@ impl fmt::Display for EventPayload {
             Self::StackerFullEvent(evt) => write!(f, "{evt}"),
             Self::StackingEvent(evt) => write!(f, "{evt}"),
             Self::UnsafeJamEvent(evt) => write!(f, "{evt}"),
+            Self::DispensingEvent(evt) => write!(f, "{evt}"),
+            Self::DispensedEvent(evt) => write!(f, "{evt}"),
+            Self::JammedEvent(evt) => write!(f, "{evt}"),
+            Self::HaltedEvent(evt) => write!(f, "{evt}"),
+            Self::FloatingEvent(evt) => write!(f, "{evt}"),
+            Self::FloatedEvent(evt) => write!(f, "{evt}"),
+            Self::TimeoutEvent(evt) => write!(f, "{evt}"),
+            Self::IncompletePayoutEvent(evt) => write!(f, "{evt}"),
+            Self::IncompleteFloatEvent(evt) => write!(f, "{evt}"),
+            Self::EmptyingEvent(evt) => write!(f, "{evt}"),
+            Self::EmptiedEvent(evt) => write!(f, "{evt}"),
+            Self::SmartEmptyingEvent(evt) => write!(f, "{evt}"),
+            Self::SmartEmptiedEvent(evt) => write!(f, "{evt}"),
+            Self::NoteStoredInPayoutEvent(evt) => write!(f, "{evt}"),
+            Self::NoteTransferredToStackerEvent(evt) => write!(f, "{evt}"),
+            Self::NoteHeldInBezelEvent(evt) => write!(f, "{evt}"),
+            Self::DeviceFullEvent(evt) => write!(f, "{evt}"),
+            Self::PayoutOutOfServiceEvent(evt) => write!(f, "{evt}"),
+            Self::NotePathOpenEvent(evt) => write!(f, "{evt}"),
+            Self::ChannelDisableEvent(evt) => write!(f, "{evt}"),
         }
     }
 }
@@ -171,6 +407,7 @@ inner_enum!(EventPayload, DisableEvent);
 inner_enum!(EventPayload, DispenseEvent);
 inner_enum!(EventPayload, EnableEvent);
 inner_enum!(EventPayload, RejectEvent);
+inner_enum!(EventPayload, SetInhibitsEvent);
 inner_enum!(EventPayload, StackEvent);
 inner_enum!(EventPayload, StatusEvent);
 inner_enum!(EventPayload, CashboxRemovedEvent);
@@ -188,6 +425,26 @@ inner_enum!(EventPayload, StackedEvent);
 inner_enum!(EventPayload, StackerFullEvent);
 inner_enum!(EventPayload, StackingEvent);
 inner_enum!(EventPayload, UnsafeJamEvent);
+inner_enum!(EventPayload, DispensingEvent);
+inner_enum!(EventPayload, DispensedEvent);
+inner_enum!(EventPayload, JammedEvent);
+inner_enum!(EventPayload, HaltedEvent);
+inner_enum!(EventPayload, FloatingEvent);
+inner_enum!(EventPayload, FloatedEvent);
+inner_enum!(EventPayload, TimeoutEvent);
+inner_enum!(EventPayload, IncompletePayoutEvent);
+inner_enum!(EventPayload, IncompleteFloatEvent);
+inner_enum!(EventPayload, EmptyingEvent);
+inner_enum!(EventPayload, EmptiedEvent);
+inner_enum!(EventPayload, SmartEmptyingEvent);
+inner_enum!(EventPayload, SmartEmptiedEvent);
+inner_enum!(EventPayload, NoteStoredInPayoutEvent);
+inner_enum!(EventPayload, NoteTransferredToStackerEvent);
+inner_enum!(EventPayload, NoteHeldInBezelEvent);
+inner_enum!(EventPayload, DeviceFullEvent);
+inner_enum!(EventPayload, PayoutOutOfServiceEvent);
+inner_enum!(EventPayload, NotePathOpenEvent);
+inner_enum!(EventPayload, ChannelDisableEvent);
 
 macro_rules! from_event_for_payload {
     ($event:ident) => {
@@ -231,6 +488,7 @@ macro_rules! from_event_for_payload {
 from_event_for_payload!(DisableEvent);
 from_event_for_payload!(EnableEvent);
 from_event_for_payload!(RejectEvent);
+from_event_for_payload!(SetInhibitsEvent);
 from_event_for_payload!(StackEvent);
 from_event_for_payload!(StatusEvent);
 from_event_for_payload!(DispenseEvent);
@@ -250,6 +508,28 @@ from_event_for_payload!(StackedEvent);
 from_event_for_payload!(StackerFullEvent);
 from_event_for_payload!(StackingEvent);
 from_event_for_payload!(UnsafeJamEvent);
+// Payout response events
+from_event_for_payload!(DispensingEvent);
+from_event_for_payload!(DispensedEvent);
+from_event_for_payload!(JammedEvent);
+from_event_for_payload!(HaltedEvent);
+from_event_for_payload!(FloatingEvent);
+from_event_for_payload!(FloatedEvent);
+from_event_for_payload!(TimeoutEvent);
+from_event_for_payload!(IncompletePayoutEvent);
+from_event_for_payload!(IncompleteFloatEvent);
+// Storage response events
+from_event_for_payload!(EmptyingEvent);
+from_event_for_payload!(EmptiedEvent);
+from_event_for_payload!(SmartEmptyingEvent);
+from_event_for_payload!(SmartEmptiedEvent);
+from_event_for_payload!(NoteStoredInPayoutEvent);
+from_event_for_payload!(NoteTransferredToStackerEvent);
+from_event_for_payload!(NoteHeldInBezelEvent);
+from_event_for_payload!(DeviceFullEvent);
+from_event_for_payload!(PayoutOutOfServiceEvent);
+from_event_for_payload!(NotePathOpenEvent);
+from_event_for_payload!(ChannelDisableEvent);
 
 /// Represents a generic event from a polling response.
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -300,6 +580,7 @@ impl From<Method> for Event {
             Method::Dispense => EventPayload::DispenseEvent(DispenseEvent::default()),
             Method::Enable | Method::Accept => EventPayload::EnableEvent(EnableEvent::default()),
             Method::Reject => EventPayload::RejectEvent(RejectEvent::new()),
+            Method::SetInhibits => EventPayload::SetInhibitsEvent(SetInhibitsEvent::new()),
             Method::Stack => EventPayload::StackEvent(StackEvent::default()),
             Method::Status => EventPayload::StatusEvent(StatusEvent::default()),
             Method::CashboxRemoved => EventPayload::CashboxRemovedEvent(CashboxRemovedEvent::new()),
@@ -323,6 +604,40 @@ impl From<Method> for Event {
             Method::StackerFull => EventPayload::StackerFullEvent(StackerFullEvent::new()),
             Method::Stacking => EventPayload::StackingEvent(StackingEvent::new()),
             Method::UnsafeJam => EventPayload::UnsafeJamEvent(UnsafeJamEvent::new()),
+            Method::Dispensing => EventPayload::DispensingEvent(DispensingEvent::default()),
+            Method::Dispensed => EventPayload::DispensedEvent(DispensedEvent::default()),
+            Method::Jammed => EventPayload::JammedEvent(JammedEvent::default()),
+            Method::Halted => EventPayload::HaltedEvent(HaltedEvent::default()),
+            Method::Floating => EventPayload::FloatingEvent(FloatingEvent::default()),
+            Method::Floated => EventPayload::FloatedEvent(FloatedEvent::default()),
+            Method::Timeout => EventPayload::TimeoutEvent(TimeoutEvent::default()),
+            Method::IncompletePayout => {
+                EventPayload::IncompletePayoutEvent(IncompletePayoutEvent::default())
+            }
+            Method::IncompleteFloat => {
+                EventPayload::IncompleteFloatEvent(IncompleteFloatEvent::default())
+            }
+            Method::Emptying => EventPayload::EmptyingEvent(EmptyingEvent::new()),
+            Method::Emptied => EventPayload::EmptiedEvent(EmptiedEvent::new()),
+            Method::SmartEmptying => {
+                EventPayload::SmartEmptyingEvent(SmartEmptyingEvent::default())
+            }
+            Method::SmartEmptied => EventPayload::SmartEmptiedEvent(SmartEmptiedEvent::default()),
+            Method::NoteStoredInPayout => {
+                EventPayload::NoteStoredInPayoutEvent(NoteStoredInPayoutEvent::new())
+            }
+            Method::NoteTransferredToStacker => {
+                EventPayload::NoteTransferredToStackerEvent(NoteTransferredToStackerEvent::default())
+            }
+            Method::NoteHeldInBezel => {
+                EventPayload::NoteHeldInBezelEvent(NoteHeldInBezelEvent::default())
+            }
+            Method::DeviceFull => EventPayload::DeviceFullEvent(DeviceFullEvent::new()),
+            Method::PayoutOutOfService => {
+                EventPayload::PayoutOutOfServiceEvent(PayoutOutOfServiceEvent::new())
+            }
+            Method::NotePathOpen => EventPayload::NotePathOpenEvent(NotePathOpenEvent::new()),
+            Method::ChannelDisable => EventPayload::ChannelDisableEvent(ChannelDisableEvent::new()),
             Method::Reserved(m) => EventPayload::Error(Error::Generic(-(m as i64))),
         };
 
@@ -375,6 +690,7 @@ macro_rules! from_event_for_event {
 from_event_for_event!(DisableEvent);
 from_event_for_event!(EnableEvent);
 from_event_for_event!(RejectEvent);
+from_event_for_event!(SetInhibitsEvent);
 from_event_for_event!(StackEvent);
 from_event_for_event!(StatusEvent);
 from_event_for_event!(DispenseEvent);
@@ -394,3 +710,25 @@ from_event_for_event!(StackedEvent);
 from_event_for_event!(StackerFullEvent);
 from_event_for_event!(StackingEvent);
 from_event_for_event!(UnsafeJamEvent);
+// Payout response events
+from_event_for_event!(DispensingEvent);
+from_event_for_event!(DispensedEvent);
+from_event_for_event!(JammedEvent);
+from_event_for_event!(HaltedEvent);
+from_event_for_event!(FloatingEvent);
+from_event_for_event!(FloatedEvent);
+from_event_for_event!(TimeoutEvent);
+from_event_for_event!(IncompletePayoutEvent);
+from_event_for_event!(IncompleteFloatEvent);
+// Storage response events
+from_event_for_event!(EmptyingEvent);
+from_event_for_event!(EmptiedEvent);
+from_event_for_event!(SmartEmptyingEvent);
+from_event_for_event!(SmartEmptiedEvent);
+from_event_for_event!(NoteStoredInPayoutEvent);
+from_event_for_event!(NoteTransferredToStackerEvent);
+from_event_for_event!(NoteHeldInBezelEvent);
+from_event_for_event!(DeviceFullEvent);
+from_event_for_event!(PayoutOutOfServiceEvent);
+from_event_for_event!(NotePathOpenEvent);
+from_event_for_event!(ChannelDisableEvent);
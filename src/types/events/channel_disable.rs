@@ -0,0 +1,82 @@
+use crate::{impl_default, std::fmt, Error, ResponseStatus, Result};
+
+use super::Method;
+
+/// Represents a [ChannelDisable](crate::ResponseStatus::ChannelDisable) event.
+///
+/// All note channels have been inhibited, and the device is inactive.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ChannelDisableEvent;
+
+impl ChannelDisableEvent {
+    /// Creates a new [ChannelDisableEvent].
+    pub const fn new() -> Self {
+        Self {}
+    }
+
+    /// Gets the [Method] for the [ChannelDisableEvent].
+    pub const fn method() -> Method {
+        Method::ChannelDisable
+    }
+
+    /// Converts the [ChannelDisableEvent] to a string.
+    pub const fn to_str(&self) -> &'static str {
+        Self::method().to_str()
+    }
+
+    /// Gets the length of the event in a [PollResponse](crate::PollResponse).
+    pub const fn len() -> usize {
+        1
+    }
+}
+
+impl TryFrom<&[u8]> for ChannelDisableEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        if val.is_empty() {
+            Err(Error::InvalidLength((val.len(), 1)))
+        } else {
+            match ResponseStatus::from(val[0]) {
+                ResponseStatus::ChannelDisable => Ok(Self::new()),
+                event => Err(Error::InvalidEvent((event, ResponseStatus::ChannelDisable))),
+            }
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<[u8; N]> for ChannelDisableEvent {
+    type Error = Error;
+
+    fn try_from(val: [u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8; N]> for ChannelDisableEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl From<&ChannelDisableEvent> for &'static str {
+    fn from(val: &ChannelDisableEvent) -> Self {
+        val.to_str()
+    }
+}
+
+impl From<ChannelDisableEvent> for &'static str {
+    fn from(val: ChannelDisableEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for ChannelDisableEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#"{{"{}"}}"#, self.to_str())
+    }
+}
+
+impl_default!(ChannelDisableEvent);
@@ -0,0 +1,82 @@
+use crate::{impl_default, std::fmt, Error, ResponseStatus, Result};
+
+use super::Method;
+
+/// Represents a [DeviceFull](crate::ResponseStatus::DeviceFull) event.
+///
+/// The device is full of banknotes, and no more can be added.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DeviceFullEvent;
+
+impl DeviceFullEvent {
+    /// Creates a new [DeviceFullEvent].
+    pub const fn new() -> Self {
+        Self {}
+    }
+
+    /// Gets the [Method] for the [DeviceFullEvent].
+    pub const fn method() -> Method {
+        Method::DeviceFull
+    }
+
+    /// Converts the [DeviceFullEvent] to a string.
+    pub const fn to_str(&self) -> &'static str {
+        Self::method().to_str()
+    }
+
+    /// Gets the length of the event in a [PollResponse](crate::PollResponse).
+    pub const fn len() -> usize {
+        1
+    }
+}
+
+impl TryFrom<&[u8]> for DeviceFullEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        if val.is_empty() {
+            Err(Error::InvalidLength((val.len(), 1)))
+        } else {
+            match ResponseStatus::from(val[0]) {
+                ResponseStatus::DeviceFull => Ok(Self::new()),
+                event => Err(Error::InvalidEvent((event, ResponseStatus::DeviceFull))),
+            }
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<[u8; N]> for DeviceFullEvent {
+    type Error = Error;
+
+    fn try_from(val: [u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8; N]> for DeviceFullEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl From<&DeviceFullEvent> for &'static str {
+    fn from(val: &DeviceFullEvent) -> Self {
+        val.to_str()
+    }
+}
+
+impl From<DeviceFullEvent> for &'static str {
+    fn from(val: DeviceFullEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for DeviceFullEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#"{{"{}"}}"#, self.to_str())
+    }
+}
+
+impl_default!(DeviceFullEvent);
@@ -0,0 +1,83 @@
+use crate::{impl_default, std::fmt, Error, ResponseStatus, Result};
+
+use super::Method;
+
+/// Represents an [Emptied](crate::ResponseStatus::Emptied) event.
+///
+/// The device has finished emptying its stored notes to the cashbox, following an
+/// [EmptyCommand](crate::EmptyCommand).
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EmptiedEvent;
+
+impl EmptiedEvent {
+    /// Creates a new [EmptiedEvent].
+    pub const fn new() -> Self {
+        Self {}
+    }
+
+    /// Gets the [Method] for the [EmptiedEvent].
+    pub const fn method() -> Method {
+        Method::Emptied
+    }
+
+    /// Converts the [EmptiedEvent] to a string.
+    pub const fn to_str(&self) -> &'static str {
+        Self::method().to_str()
+    }
+
+    /// Gets the length of the event in a [PollResponse](crate::PollResponse).
+    pub const fn len() -> usize {
+        1
+    }
+}
+
+impl TryFrom<&[u8]> for EmptiedEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        if val.is_empty() {
+            Err(Error::InvalidLength((val.len(), 1)))
+        } else {
+            match ResponseStatus::from(val[0]) {
+                ResponseStatus::Emptied => Ok(Self::new()),
+                event => Err(Error::InvalidEvent((event, ResponseStatus::Emptied))),
+            }
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<[u8; N]> for EmptiedEvent {
+    type Error = Error;
+
+    fn try_from(val: [u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8; N]> for EmptiedEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl From<&EmptiedEvent> for &'static str {
+    fn from(val: &EmptiedEvent) -> Self {
+        val.to_str()
+    }
+}
+
+impl From<EmptiedEvent> for &'static str {
+    fn from(val: EmptiedEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for EmptiedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#"{{"{}"}}"#, self.to_str())
+    }
+}
+
+impl_default!(EmptiedEvent);
@@ -0,0 +1,83 @@
+use crate::{impl_default, std::fmt, Error, ResponseStatus, Result};
+
+use super::Method;
+
+/// Represents an [Emptying](crate::ResponseStatus::Emptying) event.
+///
+/// The device is emptying its stored notes to the cashbox, following an
+/// [EmptyCommand](crate::EmptyCommand).
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EmptyingEvent;
+
+impl EmptyingEvent {
+    /// Creates a new [EmptyingEvent].
+    pub const fn new() -> Self {
+        Self {}
+    }
+
+    /// Gets the [Method] for the [EmptyingEvent].
+    pub const fn method() -> Method {
+        Method::Emptying
+    }
+
+    /// Converts the [EmptyingEvent] to a string.
+    pub const fn to_str(&self) -> &'static str {
+        Self::method().to_str()
+    }
+
+    /// Gets the length of the event in a [PollResponse](crate::PollResponse).
+    pub const fn len() -> usize {
+        1
+    }
+}
+
+impl TryFrom<&[u8]> for EmptyingEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        if val.is_empty() {
+            Err(Error::InvalidLength((val.len(), 1)))
+        } else {
+            match ResponseStatus::from(val[0]) {
+                ResponseStatus::Emptying => Ok(Self::new()),
+                event => Err(Error::InvalidEvent((event, ResponseStatus::Emptying))),
+            }
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<[u8; N]> for EmptyingEvent {
+    type Error = Error;
+
+    fn try_from(val: [u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8; N]> for EmptyingEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl From<&EmptyingEvent> for &'static str {
+    fn from(val: &EmptyingEvent) -> Self {
+        val.to_str()
+    }
+}
+
+impl From<EmptyingEvent> for &'static str {
+    fn from(val: EmptyingEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for EmptyingEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#"{{"{}"}}"#, self.to_str())
+    }
+}
+
+impl_default!(EmptyingEvent);
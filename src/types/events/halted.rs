@@ -0,0 +1,112 @@
+use crate::{std::fmt, CurrencyValueList, Error, ProtocolVersion, ResponseStatus, Result};
+
+use super::Method;
+
+/// Represents a [Halted](crate::ResponseStatus::Halted) event.
+///
+/// The payout was halted, and reports the value paid up to the halt point.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HaltedEvent {
+    values: CurrencyValueList,
+}
+
+impl HaltedEvent {
+    /// Creates a new [HaltedEvent] from the [CurrencyValueList].
+    pub const fn new(values: CurrencyValueList) -> Self {
+        Self { values }
+    }
+
+    /// Gets the [Method] for the [HaltedEvent].
+    pub const fn method() -> Method {
+        Method::Halted
+    }
+
+    /// Converts the [HaltedEvent] to a string.
+    pub const fn to_str(&self) -> &'static str {
+        Self::method().to_str()
+    }
+
+    /// Gets a reference to the [CurrencyValueList].
+    pub fn values(&self) -> &CurrencyValueList {
+        &self.values
+    }
+
+    /// Sets the [CurrencyValueList].
+    pub fn set_values(&mut self, values: CurrencyValueList) {
+        self.values = values;
+    }
+
+    /// Gets the length of the event at the start of `data` in a [PollResponse](crate::PollResponse).
+    ///
+    /// The length depends on the [ProtocolVersion], and the number of countries in the event data.
+    pub fn len(data: &[u8], protocol: ProtocolVersion) -> Result<usize> {
+        let event_data = data.get(1..).unwrap_or_default();
+
+        Ok(1 + CurrencyValueList::event_data_len(event_data, protocol)?)
+    }
+}
+
+impl TryFrom<&[u8]> for HaltedEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        match val.len() {
+            0 => Err(Error::InvalidLength((0, 1))),
+            _ => match ResponseStatus::from(val[0]) {
+                ResponseStatus::Halted => {
+                    Ok(Self::new(CurrencyValueList::from_event_data(&val[1..])?))
+                }
+                event => Err(Error::InvalidEvent((event, ResponseStatus::Halted))),
+            },
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<[u8; N]> for HaltedEvent {
+    type Error = Error;
+
+    fn try_from(val: [u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8; N]> for HaltedEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl From<CurrencyValueList> for HaltedEvent {
+    fn from(val: CurrencyValueList) -> Self {
+        Self::new(val)
+    }
+}
+
+impl From<&HaltedEvent> for &'static str {
+    fn from(val: &HaltedEvent) -> Self {
+        val.to_str()
+    }
+}
+
+impl From<HaltedEvent> for &'static str {
+    fn from(val: HaltedEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for HaltedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let method = self.to_str();
+        let values = self.values();
+
+        write!(f, r#"{{"{method}": {values}}}"#)
+    }
+}
+
+impl Default for HaltedEvent {
+    fn default() -> Self {
+        Self::new(CurrencyValueList::new())
+    }
+}
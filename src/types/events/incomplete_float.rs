@@ -0,0 +1,116 @@
+use crate::{std::fmt, Error, IncompleteValueList, ProtocolVersion, ResponseStatus, Result};
+
+use super::Method;
+
+/// Represents a [IncompleteFloat](crate::ResponseStatus::IncompleteFloat) event.
+///
+/// The device detected on power-up that the last float was interrupted, and reports the value
+/// floated and the value requested.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct IncompleteFloatEvent {
+    values: IncompleteValueList,
+}
+
+impl IncompleteFloatEvent {
+    /// Creates a new [IncompleteFloatEvent] from the [IncompleteValueList].
+    pub const fn new(values: IncompleteValueList) -> Self {
+        Self { values }
+    }
+
+    /// Gets the [Method] for the [IncompleteFloatEvent].
+    pub const fn method() -> Method {
+        Method::IncompleteFloat
+    }
+
+    /// Converts the [IncompleteFloatEvent] to a string.
+    pub const fn to_str(&self) -> &'static str {
+        Self::method().to_str()
+    }
+
+    /// Gets a reference to the [IncompleteValueList].
+    pub fn values(&self) -> &IncompleteValueList {
+        &self.values
+    }
+
+    /// Sets the [IncompleteValueList].
+    pub fn set_values(&mut self, values: IncompleteValueList) {
+        self.values = values;
+    }
+
+    /// Gets the length of the event at the start of `data` in a [PollResponse](crate::PollResponse).
+    ///
+    /// The length depends on the [ProtocolVersion], and the number of countries in the event data.
+    pub fn len(data: &[u8], protocol: ProtocolVersion) -> Result<usize> {
+        let event_data = data.get(1..).unwrap_or_default();
+
+        Ok(1 + IncompleteValueList::event_data_len(event_data, protocol)?)
+    }
+}
+
+impl TryFrom<&[u8]> for IncompleteFloatEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        match val.len() {
+            0 => Err(Error::InvalidLength((0, 1))),
+            _ => match ResponseStatus::from(val[0]) {
+                ResponseStatus::IncompleteFloat => {
+                    Ok(Self::new(IncompleteValueList::from_event_data(&val[1..])?))
+                }
+                event => Err(Error::InvalidEvent((
+                    event,
+                    ResponseStatus::IncompleteFloat,
+                ))),
+            },
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<[u8; N]> for IncompleteFloatEvent {
+    type Error = Error;
+
+    fn try_from(val: [u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8; N]> for IncompleteFloatEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl From<IncompleteValueList> for IncompleteFloatEvent {
+    fn from(val: IncompleteValueList) -> Self {
+        Self::new(val)
+    }
+}
+
+impl From<&IncompleteFloatEvent> for &'static str {
+    fn from(val: &IncompleteFloatEvent) -> Self {
+        val.to_str()
+    }
+}
+
+impl From<IncompleteFloatEvent> for &'static str {
+    fn from(val: IncompleteFloatEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for IncompleteFloatEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let method = self.to_str();
+        let values = self.values();
+
+        write!(f, r#"{{"{method}": {values}}}"#)
+    }
+}
+
+impl Default for IncompleteFloatEvent {
+    fn default() -> Self {
+        Self::new(IncompleteValueList::new())
+    }
+}
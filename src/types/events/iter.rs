@@ -0,0 +1,276 @@
+use crate::{Error, ProtocolVersion, Result};
+
+use super::EventPayload;
+
+/// Iterator over the events in the data field of a polling response.
+///
+/// Walks the data using the length of each event, so data bytes carried by an event (e.g. the
+/// channel of a [NoteCreditEvent](super::NoteCreditEvent)) are not misparsed as statuses.
+///
+/// If an event is unknown, or truncated, the iterator yields an error, and stops. Without knowing
+/// the length of the event, the position of the following event is unknown.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PollEventIter<'a> {
+    data: &'a [u8],
+    protocol: ProtocolVersion,
+}
+
+impl<'a> PollEventIter<'a> {
+    /// Creates a new [PollEventIter] over the poll event `data`.
+    ///
+    /// The `data` should not include the leading [ResponseStatus](crate::ResponseStatus) of the
+    /// response.
+    pub const fn new(data: &'a [u8], protocol: ProtocolVersion) -> Self {
+        Self { data, protocol }
+    }
+
+    /// Gets the [ProtocolVersion] used to determine event lengths.
+    pub const fn protocol(&self) -> ProtocolVersion {
+        self.protocol
+    }
+
+    /// Gets the remaining event data.
+    pub const fn remaining(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+impl Iterator for PollEventIter<'_> {
+    type Item = Result<EventPayload>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let res = EventPayload::poll_event_len(self.data, self.protocol).and_then(|event_len| {
+            if event_len > self.data.len() {
+                Err(Error::InvalidLength((self.data.len(), event_len)))
+            } else {
+                let (event, rest) = self.data.split_at(event_len);
+                self.data = rest;
+
+                EventPayload::from_poll_event(event, self.protocol)
+            }
+        });
+
+        if res.is_err() {
+            self.data = &[];
+        }
+
+        Some(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ChannelDisableEvent, ChannelValue, CountryCode, CurrencyValue, CurrencyValueList,
+        EmptiedEvent, HaltedEvent, IncompletePayoutEvent, IncompleteValue, IncompleteValueList,
+        JammedEvent, NoteCreditEvent, NoteHeldInBezelEvent, NotePathOpenEvent, ReadEvent,
+        ResponseStatus, SmartEmptyingEvent, StackedEvent,
+    };
+
+    #[test]
+    fn test_poll_event_iter() -> Result<()> {
+        let data = [
+            ResponseStatus::Read.to_u8(),
+            0x00,
+            ResponseStatus::Stacked.to_u8(),
+            ResponseStatus::NoteCredit.to_u8(),
+            0x00,
+        ];
+
+        let events = PollEventIter::new(data.as_ref(), ProtocolVersion::Six)
+            .collect::<Result<alloc::vec::Vec<EventPayload>>>()?;
+
+        assert_eq!(
+            events,
+            [
+                EventPayload::ReadEvent(ReadEvent::default()),
+                EventPayload::StackedEvent(StackedEvent::new()),
+                EventPayload::NoteCreditEvent(NoteCreditEvent::default()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_poll_event_iter_truncated() {
+        let data = [
+            ResponseStatus::Stacked.to_u8(),
+            ResponseStatus::NoteCredit.to_u8(),
+        ];
+
+        let mut iter = PollEventIter::new(data.as_ref(), ProtocolVersion::Six);
+
+        assert!(matches!(
+            iter.next(),
+            Some(Ok(EventPayload::StackedEvent(_)))
+        ));
+        assert!(matches!(iter.next(), Some(Err(Error::InvalidLength(_)))));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_poll_event_iter_unknown() {
+        let data = [0x01, ResponseStatus::Stacked.to_u8()];
+
+        let mut iter = PollEventIter::new(data.as_ref(), ProtocolVersion::Six);
+
+        assert!(matches!(iter.next(), Some(Err(Error::Event(_)))));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_poll_event_iter_payout() -> Result<()> {
+        // EUR 15.30 paid up to a halt, and an interrupted payout of EUR 23.00 out of EUR 50.00
+        let data = [
+            ResponseStatus::Halted.to_u8(),
+            0x01,
+            0xfa,
+            0x05,
+            0x00,
+            0x00,
+            0x45,
+            0x55,
+            0x52,
+            ResponseStatus::IncompletePayout.to_u8(),
+            0x01,
+            0xfc,
+            0x08,
+            0x00,
+            0x00,
+            0x88,
+            0x13,
+            0x00,
+            0x00,
+            0x45,
+            0x55,
+            0x52,
+            ResponseStatus::Stacked.to_u8(),
+        ];
+
+        let events = PollEventIter::new(data.as_ref(), ProtocolVersion::Six)
+            .collect::<Result<alloc::vec::Vec<EventPayload>>>()?;
+
+        assert_eq!(
+            events,
+            [
+                EventPayload::HaltedEvent(HaltedEvent::new(CurrencyValueList::from([
+                    CurrencyValue::create(ChannelValue::from(1530), CountryCode::EUR)
+                ]))),
+                EventPayload::IncompletePayoutEvent(IncompletePayoutEvent::new(
+                    IncompleteValueList::from([IncompleteValue::create(
+                        ChannelValue::from(2300),
+                        ChannelValue::from(5000),
+                        CountryCode::EUR
+                    )])
+                )),
+                EventPayload::StackedEvent(StackedEvent::new()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_poll_event_iter_payout_single_value() -> Result<()> {
+        // 2.30 paid up to a jam with protocol version 5, from the SSP Protocol Manual
+        let data = [
+            ResponseStatus::Jammed.to_u8(),
+            0xe6,
+            0x00,
+            0x00,
+            0x00,
+            ResponseStatus::Stacked.to_u8(),
+        ];
+
+        let events = PollEventIter::new(data.as_ref(), ProtocolVersion::Five)
+            .collect::<Result<alloc::vec::Vec<EventPayload>>>()?;
+
+        assert_eq!(
+            events,
+            [
+                EventPayload::JammedEvent(JammedEvent::new(CurrencyValueList::from([
+                    CurrencyValue::new().with_value(ChannelValue::from(230))
+                ]))),
+                EventPayload::StackedEvent(StackedEvent::new()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_poll_event_iter_storage() -> Result<()> {
+        // EUR 22.60 emptied up to the poll, and EUR 10.00 held in the bezel
+        let data = [
+            ResponseStatus::SmartEmptying.to_u8(),
+            0x01,
+            0xd4,
+            0x08,
+            0x00,
+            0x00,
+            0x45,
+            0x55,
+            0x52,
+            ResponseStatus::NoteHeldInBezel.to_u8(),
+            0xe8,
+            0x03,
+            0x00,
+            0x00,
+            0x45,
+            0x55,
+            0x52,
+            ResponseStatus::NotePathOpen.to_u8(),
+            ResponseStatus::Emptied.to_u8(),
+        ];
+
+        let events = PollEventIter::new(data.as_ref(), ProtocolVersion::Six)
+            .collect::<Result<alloc::vec::Vec<EventPayload>>>()?;
+
+        assert_eq!(
+            events,
+            [
+                EventPayload::SmartEmptyingEvent(SmartEmptyingEvent::new(CurrencyValueList::from(
+                    [CurrencyValue::create(
+                        ChannelValue::from(2260),
+                        CountryCode::EUR
+                    )]
+                ))),
+                EventPayload::NoteHeldInBezelEvent(NoteHeldInBezelEvent::new(
+                    CurrencyValue::create(ChannelValue::from(1000), CountryCode::EUR)
+                )),
+                EventPayload::NotePathOpenEvent(NotePathOpenEvent::new()),
+                EventPayload::EmptiedEvent(EmptiedEvent::new()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_poll_event_iter_channel_disable() -> Result<()> {
+        let data = [
+            ResponseStatus::ChannelDisable.to_u8(),
+            ResponseStatus::NoteCredit.to_u8(),
+            0x00,
+        ];
+
+        let events = PollEventIter::new(data.as_ref(), ProtocolVersion::Six)
+            .collect::<Result<alloc::vec::Vec<EventPayload>>>()?;
+
+        assert_eq!(
+            events,
+            [
+                EventPayload::ChannelDisableEvent(ChannelDisableEvent::new()),
+                EventPayload::NoteCreditEvent(NoteCreditEvent::default()),
+            ]
+        );
+
+        Ok(())
+    }
+}
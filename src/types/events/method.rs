@@ -27,6 +27,8 @@ pub enum Method {
     Shutdown,
     /// Dispense notes from the device.
     Dispense,
+    /// Set the channels enabled for note acceptance.
+    SetInhibits,
     /// Cashbox removed from device.
     CashboxRemoved = ResponseStatus::CashboxRemoved.to_u8(),
     /// Cashbox replaced into device.
@@ -57,6 +59,46 @@ pub enum Method {
     Stacking = ResponseStatus::Stacking.to_u8(),
     /// Unsafe jam detected.
     UnsafeJam = ResponseStatus::UnsafeJam.to_u8(),
+    /// Device is paying out a requested value.
+    Dispensing = ResponseStatus::Dispensing.to_u8(),
+    /// Device finished paying out a requested value.
+    Dispensed = ResponseStatus::Dispensed.to_u8(),
+    /// Payout unit jammed during a payout, float, or empty operation.
+    Jammed = ResponseStatus::Jammed.to_u8(),
+    /// Payout was halted.
+    Halted = ResponseStatus::Halted.to_u8(),
+    /// Device is floating to the cashbox.
+    Floating = ResponseStatus::Floating.to_u8(),
+    /// Device finished floating to the cashbox.
+    Floated = ResponseStatus::Floated.to_u8(),
+    /// Device timed out completing a payout request.
+    Timeout = ResponseStatus::Timeout.to_u8(),
+    /// Last payout was interrupted before completion.
+    IncompletePayout = ResponseStatus::IncompletePayout.to_u8(),
+    /// Last float was interrupted before completion.
+    IncompleteFloat = ResponseStatus::IncompleteFloat.to_u8(),
+    /// Device is emptying stored notes to the cashbox.
+    Emptying = ResponseStatus::Emptying.to_u8(),
+    /// Device finished emptying stored notes to the cashbox.
+    Emptied = ResponseStatus::Emptied.to_u8(),
+    /// Device is emptying stored notes, and counting the value emptied.
+    SmartEmptying = ResponseStatus::SmartEmptying.to_u8(),
+    /// Device finished emptying stored notes, and counting the value emptied.
+    SmartEmptied = ResponseStatus::SmartEmptied.to_u8(),
+    /// Note stored in the payout unit.
+    NoteStoredInPayout = ResponseStatus::NoteStoredInPayout.to_u8(),
+    /// Note moved from the payout store into the cashbox.
+    NoteTransferredToStacker = ResponseStatus::NoteTransferredToStacker.to_u8(),
+    /// Dispensed note is held in the bezel.
+    NoteHeldInBezel = ResponseStatus::NoteHeldInBezel.to_u8(),
+    /// Device storage is full.
+    DeviceFull = ResponseStatus::DeviceFull.to_u8(),
+    /// Payout unit went out of service.
+    PayoutOutOfService = ResponseStatus::PayoutOutOfService.to_u8(),
+    /// Device note path is open.
+    NotePathOpen = ResponseStatus::NotePathOpen.to_u8(),
+    /// All channels are inhibited, and the device is disabled.
+    ChannelDisable = ResponseStatus::ChannelDisable.to_u8(),
     /// Device failure.
     Fail = ResponseStatus::Fail.to_u8(),
     /// Currently reserved/unsupported method.
@@ -81,6 +123,7 @@ impl Method {
             Self::Stack => "stack",
             Self::Shutdown => "shutdown",
             Self::Dispense => "dispense",
+            Self::SetInhibits => "set_inhibits",
             Self::CashboxRemoved => "cashbox_removed",
             Self::CashboxReplaced => "cashbox_replaced",
             Self::Disabled => "disabled",
@@ -96,6 +139,26 @@ impl Method {
             Self::StackerFull => "stacker_full",
             Self::Stacking => "stacking",
             Self::UnsafeJam => "unsafe_jam",
+            Self::Dispensing => "dispensing",
+            Self::Dispensed => "dispensed",
+            Self::Jammed => "jammed",
+            Self::Halted => "halted",
+            Self::Floating => "floating",
+            Self::Floated => "floated",
+            Self::Timeout => "timeout",
+            Self::IncompletePayout => "incomplete_payout",
+            Self::IncompleteFloat => "incomplete_float",
+            Self::Emptying => "emptying",
+            Self::Emptied => "emptied",
+            Self::SmartEmptying => "smart_emptying",
+            Self::SmartEmptied => "smart_emptied",
+            Self::NoteStoredInPayout => "note_stored_in_payout",
+            Self::NoteTransferredToStacker => "note_transferred_to_stacker",
+            Self::NoteHeldInBezel => "note_held_in_bezel",
+            Self::DeviceFull => "device_full",
+            Self::PayoutOutOfService => "payout_out_of_service",
+            Self::NotePathOpen => "note_path_open",
+            Self::ChannelDisable => "channel_disable",
             Self::Fail => "fail",
             Self::Reserved(_) => "reserved",
         }
@@ -132,6 +195,26 @@ impl Method {
             ResponseStatus::StackerFull => Self::StackerFull,
             ResponseStatus::Stacking => Self::Stacking,
             ResponseStatus::UnsafeJam => Self::UnsafeJam,
+            ResponseStatus::Dispensing => Self::Dispensing,
+            ResponseStatus::Dispensed => Self::Dispensed,
+            ResponseStatus::Jammed => Self::Jammed,
+            ResponseStatus::Halted => Self::Halted,
+            ResponseStatus::Floating => Self::Floating,
+            ResponseStatus::Floated => Self::Floated,
+            ResponseStatus::Timeout => Self::Timeout,
+            ResponseStatus::IncompletePayout => Self::IncompletePayout,
+            ResponseStatus::IncompleteFloat => Self::IncompleteFloat,
+            ResponseStatus::Emptying => Self::Emptying,
+            ResponseStatus::Emptied => Self::Emptied,
+            ResponseStatus::SmartEmptying => Self::SmartEmptying,
+            ResponseStatus::SmartEmptied => Self::SmartEmptied,
+            ResponseStatus::NoteStoredInPayout => Self::NoteStoredInPayout,
+            ResponseStatus::NoteTransferredToStacker => Self::NoteTransferredToStacker,
+            ResponseStatus::NoteHeldInBezel => Self::NoteHeldInBezel,
+            ResponseStatus::DeviceFull => Self::DeviceFull,
+            ResponseStatus::PayoutOutOfService => Self::PayoutOutOfService,
+            ResponseStatus::NotePathOpen => Self::NotePathOpen,
+            ResponseStatus::ChannelDisable => Self::ChannelDisable,
             ResponseStatus::Fail => Self::Fail,
             status => Self::Reserved(status.to_u8()),
         }
@@ -155,6 +238,26 @@ impl Method {
             Self::StackerFull => ResponseStatus::StackerFull,
             Self::Stacking => ResponseStatus::Stacking,
             Self::UnsafeJam => ResponseStatus::UnsafeJam,
+            Self::Dispensing => ResponseStatus::Dispensing,
+            Self::Dispensed => ResponseStatus::Dispensed,
+            Self::Jammed => ResponseStatus::Jammed,
+            Self::Halted => ResponseStatus::Halted,
+            Self::Floating => ResponseStatus::Floating,
+            Self::Floated => ResponseStatus::Floated,
+            Self::Timeout => ResponseStatus::Timeout,
+            Self::IncompletePayout => ResponseStatus::IncompletePayout,
+            Self::IncompleteFloat => ResponseStatus::IncompleteFloat,
+            Self::Emptying => ResponseStatus::Emptying,
+            Self::Emptied => ResponseStatus::Emptied,
+            Self::SmartEmptying => ResponseStatus::SmartEmptying,
+            Self::SmartEmptied => ResponseStatus::SmartEmptied,
+            Self::NoteStoredInPayout => ResponseStatus::NoteStoredInPayout,
+            Self::NoteTransferredToStacker => ResponseStatus::NoteTransferredToStacker,
+            Self::NoteHeldInBezel => ResponseStatus::NoteHeldInBezel,
+            Self::DeviceFull => ResponseStatus::DeviceFull,
+            Self::PayoutOutOfService => ResponseStatus::PayoutOutOfService,
+            Self::NotePathOpen => ResponseStatus::NotePathOpen,
+            Self::ChannelDisable => ResponseStatus::ChannelDisable,
             Self::Fail => ResponseStatus::Fail,
             method => ResponseStatus::Reserved(method.to_u8()),
         }
@@ -175,6 +278,7 @@ impl FromStr for Method {
             "stack" => Self::Stack,
             "shutdown" => Self::Shutdown,
             "dispense" | "denomination_dispense" => Self::Dispense,
+            "set_inhibits" => Self::SetInhibits,
             "cashbox_removed" => Self::CashboxRemoved,
             "cashbox_replaced" => Self::CashboxReplaced,
             "disabled" => Self::Disabled,
@@ -190,6 +294,26 @@ impl FromStr for Method {
             "stacker_full" => Self::StackerFull,
             "stacking" => Self::Stacking,
             "unsafe_jam" => Self::UnsafeJam,
+            "dispensing" => Self::Dispensing,
+            "dispensed" => Self::Dispensed,
+            "jammed" => Self::Jammed,
+            "halted" => Self::Halted,
+            "floating" => Self::Floating,
+            "floated" => Self::Floated,
+            "timeout" => Self::Timeout,
+            "incomplete_payout" => Self::IncompletePayout,
+            "incomplete_float" => Self::IncompleteFloat,
+            "emptying" => Self::Emptying,
+            "emptied" => Self::Emptied,
+            "smart_emptying" => Self::SmartEmptying,
+            "smart_emptied" => Self::SmartEmptied,
+            "note_stored_in_payout" => Self::NoteStoredInPayout,
+            "note_transferred_to_stacker" => Self::NoteTransferredToStacker,
+            "note_held_in_bezel" => Self::NoteHeldInBezel,
+            "device_full" => Self::DeviceFull,
+            "payout_out_of_service" => Self::PayoutOutOfService,
+            "note_path_open" => Self::NotePathOpen,
+            "channel_disable" => Self::ChannelDisable,
             "fail" => Self::Fail,
             _ => Self::Reserved(0xff),
         };
@@ -285,6 +409,7 @@ impl serde::Serialize for Method {
             Self::Status => serializer.serialize_unit_variant("Method", 6, "status"),
             Self::Shutdown => serializer.serialize_unit_variant("Method", 7, "shutdown"),
             Self::Dispense => serializer.serialize_unit_variant("Method", 8, "dispense"),
+            Self::SetInhibits => serializer.serialize_unit_variant("Method", 44, "set_inhibits"),
             Self::CashboxRemoved => {
                 serializer.serialize_unit_variant("Method", 9, "cashbox_removed")
             }
@@ -309,6 +434,42 @@ impl serde::Serialize for Method {
             Self::Stacking => serializer.serialize_unit_variant("Method", 22, "stacking"),
             Self::UnsafeJam => serializer.serialize_unit_variant("Method", 23, "unsafe_jam"),
             Self::Fail => serializer.serialize_unit_variant("Method", 24, "fail"),
+            Self::Dispensing => serializer.serialize_unit_variant("Method", 25, "dispensing"),
+            Self::Dispensed => serializer.serialize_unit_variant("Method", 26, "dispensed"),
+            Self::Jammed => serializer.serialize_unit_variant("Method", 27, "jammed"),
+            Self::Halted => serializer.serialize_unit_variant("Method", 28, "halted"),
+            Self::Floating => serializer.serialize_unit_variant("Method", 29, "floating"),
+            Self::Floated => serializer.serialize_unit_variant("Method", 30, "floated"),
+            Self::Timeout => serializer.serialize_unit_variant("Method", 31, "timeout"),
+            Self::IncompletePayout => {
+                serializer.serialize_unit_variant("Method", 32, "incomplete_payout")
+            }
+            Self::IncompleteFloat => {
+                serializer.serialize_unit_variant("Method", 33, "incomplete_float")
+            }
+            Self::Emptying => serializer.serialize_unit_variant("Method", 34, "emptying"),
+            Self::Emptied => serializer.serialize_unit_variant("Method", 35, "emptied"),
+            Self::SmartEmptying => {
+                serializer.serialize_unit_variant("Method", 36, "smart_emptying")
+            }
+            Self::SmartEmptied => serializer.serialize_unit_variant("Method", 37, "smart_emptied"),
+            Self::NoteStoredInPayout => {
+                serializer.serialize_unit_variant("Method", 38, "note_stored_in_payout")
+            }
+            Self::NoteTransferredToStacker => {
+                serializer.serialize_unit_variant("Method", 39, "note_transferred_to_stacker")
+            }
+            Self::NoteHeldInBezel => {
+                serializer.serialize_unit_variant("Method", 40, "note_held_in_bezel")
+            }
+            Self::DeviceFull => serializer.serialize_unit_variant("Method", 41, "device_full"),
+            Self::PayoutOutOfService => {
+                serializer.serialize_unit_variant("Method", 42, "payout_out_of_service")
+            }
+            Self::NotePathOpen => serializer.serialize_unit_variant("Method", 43, "note_path_open"),
+            Self::ChannelDisable => {
+                serializer.serialize_unit_variant("Method", 44, "channel_disable")
+            }
             Self::Reserved(_) => serializer.serialize_unit_variant("Method", 0xff, "reserved"),
         }
     }
@@ -325,7 +486,7 @@ impl<'de> serde::Deserialize<'de> for Method {
             type Value = Method;
 
             fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                f.write_str("`disable` `stop` `enable` `accept` `reject` `stack` `status` `shutdown` `cashbox_removed` `cashbox_replaced` `disabled` `fraud_attempt` `note_cleared_from_front` `note_cleared_return` `note_cleared_into_cashbox` `note_cleared_stack` `note_credit` `read` `rejected` `rejecting` `reset` `stacked` `stacker_full` `stacking` `unsafe_jam` `fail` `reserved`")
+                f.write_str("`disable` `stop` `enable` `accept` `reject` `stack` `status` `shutdown` `dispense` `set_inhibits` `cashbox_removed` `cashbox_replaced` `disabled` `fraud_attempt` `note_cleared_from_front` `note_cleared_return` `note_cleared_into_cashbox` `note_cleared_stack` `note_credit` `read` `rejected` `rejecting` `reset` `stacked` `stacker_full` `stacking` `unsafe_jam` `dispensing` `dispensed` `jammed` `halted` `floating` `floated` `timeout` `incomplete_payout` `incomplete_float` `emptying` `emptied` `smart_emptying` `smart_emptied` `note_stored_in_payout` `note_transferred_to_stacker` `note_held_in_bezel` `device_full` `payout_out_of_service` `note_path_open` `channel_disable` `fail` `reserved`")
             }
 
             fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
@@ -376,6 +537,10 @@ mod tests {
             serde_json::to_string(&Method::Shutdown)?.as_str(),
             "\"shutdown\""
         );
+        assert_eq!(
+            serde_json::to_string(&Method::SetInhibits)?.as_str(),
+            "\"set_inhibits\""
+        );
         assert_eq!(
             serde_json::to_string(&Method::CashboxRemoved)?.as_str(),
             "\"cashbox_removed\""
@@ -433,6 +598,86 @@ mod tests {
             serde_json::to_string(&Method::UnsafeJam)?.as_str(),
             "\"unsafe_jam\""
         );
+        assert_eq!(
+            serde_json::to_string(&Method::Dispensing)?.as_str(),
+            "\"dispensing\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::Dispensed)?.as_str(),
+            "\"dispensed\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::Jammed)?.as_str(),
+            "\"jammed\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::Halted)?.as_str(),
+            "\"halted\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::Floating)?.as_str(),
+            "\"floating\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::Floated)?.as_str(),
+            "\"floated\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::Timeout)?.as_str(),
+            "\"timeout\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::IncompletePayout)?.as_str(),
+            "\"incomplete_payout\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::IncompleteFloat)?.as_str(),
+            "\"incomplete_float\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::Emptying)?.as_str(),
+            "\"emptying\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::Emptied)?.as_str(),
+            "\"emptied\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::SmartEmptying)?.as_str(),
+            "\"smart_emptying\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::SmartEmptied)?.as_str(),
+            "\"smart_emptied\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::NoteStoredInPayout)?.as_str(),
+            "\"note_stored_in_payout\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::NoteTransferredToStacker)?.as_str(),
+            "\"note_transferred_to_stacker\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::NoteHeldInBezel)?.as_str(),
+            "\"note_held_in_bezel\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::DeviceFull)?.as_str(),
+            "\"device_full\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::PayoutOutOfService)?.as_str(),
+            "\"payout_out_of_service\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::NotePathOpen)?.as_str(),
+            "\"note_path_open\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Method::ChannelDisable)?.as_str(),
+            "\"channel_disable\""
+        );
         assert_eq!(serde_json::to_string(&Method::Fail)?.as_str(), "\"fail\"");
 
         for i in 0..0xff {
@@ -474,6 +719,10 @@ mod tests {
             serde_json::from_str::<Method>("\"shutdown\"")?,
             Method::Shutdown
         );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"set_inhibits\"")?,
+            Method::SetInhibits
+        );
         assert_eq!(
             serde_json::from_str::<Method>("\"cashbox_removed\"")?,
             Method::CashboxRemoved
@@ -536,6 +785,86 @@ mod tests {
             serde_json::from_str::<Method>("\"unsafe_jam\"")?,
             Method::UnsafeJam
         );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"dispensing\"")?,
+            Method::Dispensing
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"dispensed\"")?,
+            Method::Dispensed
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"jammed\"")?,
+            Method::Jammed
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"halted\"")?,
+            Method::Halted
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"floating\"")?,
+            Method::Floating
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"floated\"")?,
+            Method::Floated
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"timeout\"")?,
+            Method::Timeout
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"incomplete_payout\"")?,
+            Method::IncompletePayout
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"incomplete_float\"")?,
+            Method::IncompleteFloat
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"emptying\"")?,
+            Method::Emptying
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"emptied\"")?,
+            Method::Emptied
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"smart_emptying\"")?,
+            Method::SmartEmptying
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"smart_emptied\"")?,
+            Method::SmartEmptied
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"note_stored_in_payout\"")?,
+            Method::NoteStoredInPayout
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"note_transferred_to_stacker\"")?,
+            Method::NoteTransferredToStacker
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"note_held_in_bezel\"")?,
+            Method::NoteHeldInBezel
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"device_full\"")?,
+            Method::DeviceFull
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"payout_out_of_service\"")?,
+            Method::PayoutOutOfService
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"note_path_open\"")?,
+            Method::NotePathOpen
+        );
+        assert_eq!(
+            serde_json::from_str::<Method>("\"channel_disable\"")?,
+            Method::ChannelDisable
+        );
         assert_eq!(serde_json::from_str::<Method>("\"fail\"")?, Method::Fail);
         assert_eq!(
             serde_json::from_str::<Method>("\"reserved\"")?,
@@ -0,0 +1,82 @@
+use crate::{impl_default, std::fmt, Error, ResponseStatus, Result};
+
+use super::Method;
+
+/// Represents a [NotePathOpen](crate::ResponseStatus::NotePathOpen) event.
+///
+/// The note path has been opened, and the device is disabled for bill entry until it is closed.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NotePathOpenEvent;
+
+impl NotePathOpenEvent {
+    /// Creates a new [NotePathOpenEvent].
+    pub const fn new() -> Self {
+        Self {}
+    }
+
+    /// Gets the [Method] for the [NotePathOpenEvent].
+    pub const fn method() -> Method {
+        Method::NotePathOpen
+    }
+
+    /// Converts the [NotePathOpenEvent] to a string.
+    pub const fn to_str(&self) -> &'static str {
+        Self::method().to_str()
+    }
+
+    /// Gets the length of the event in a [PollResponse](crate::PollResponse).
+    pub const fn len() -> usize {
+        1
+    }
+}
+
+impl TryFrom<&[u8]> for NotePathOpenEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        if val.is_empty() {
+            Err(Error::InvalidLength((val.len(), 1)))
+        } else {
+            match ResponseStatus::from(val[0]) {
+                ResponseStatus::NotePathOpen => Ok(Self::new()),
+                event => Err(Error::InvalidEvent((event, ResponseStatus::NotePathOpen))),
+            }
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<[u8; N]> for NotePathOpenEvent {
+    type Error = Error;
+
+    fn try_from(val: [u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8; N]> for NotePathOpenEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl From<&NotePathOpenEvent> for &'static str {
+    fn from(val: &NotePathOpenEvent) -> Self {
+        val.to_str()
+    }
+}
+
+impl From<NotePathOpenEvent> for &'static str {
+    fn from(val: NotePathOpenEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for NotePathOpenEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#"{{"{}"}}"#, self.to_str())
+    }
+}
+
+impl_default!(NotePathOpenEvent);
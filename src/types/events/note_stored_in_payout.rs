@@ -0,0 +1,88 @@
+use crate::{impl_default, std::fmt, Error, ResponseStatus, Result};
+
+use super::Method;
+
+/// Represents a [NoteStoredInPayout](crate::ResponseStatus::NoteStoredInPayout) event.
+///
+/// A note has been passed into the note store of the payout unit.
+///
+/// Uses the SMART Payout format, which has no event data. NV11 devices with the Report By Value
+/// option set add the note value, which is not supported.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NoteStoredInPayoutEvent;
+
+impl NoteStoredInPayoutEvent {
+    /// Creates a new [NoteStoredInPayoutEvent].
+    pub const fn new() -> Self {
+        Self {}
+    }
+
+    /// Gets the [Method] for the [NoteStoredInPayoutEvent].
+    pub const fn method() -> Method {
+        Method::NoteStoredInPayout
+    }
+
+    /// Converts the [NoteStoredInPayoutEvent] to a string.
+    pub const fn to_str(&self) -> &'static str {
+        Self::method().to_str()
+    }
+
+    /// Gets the length of the event in a [PollResponse](crate::PollResponse).
+    pub const fn len() -> usize {
+        1
+    }
+}
+
+impl TryFrom<&[u8]> for NoteStoredInPayoutEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        if val.is_empty() {
+            Err(Error::InvalidLength((val.len(), 1)))
+        } else {
+            match ResponseStatus::from(val[0]) {
+                ResponseStatus::NoteStoredInPayout => Ok(Self::new()),
+                event => Err(Error::InvalidEvent((
+                    event,
+                    ResponseStatus::NoteStoredInPayout,
+                ))),
+            }
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<[u8; N]> for NoteStoredInPayoutEvent {
+    type Error = Error;
+
+    fn try_from(val: [u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8; N]> for NoteStoredInPayoutEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl From<&NoteStoredInPayoutEvent> for &'static str {
+    fn from(val: &NoteStoredInPayoutEvent) -> Self {
+        val.to_str()
+    }
+}
+
+impl From<NoteStoredInPayoutEvent> for &'static str {
+    fn from(val: NoteStoredInPayoutEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for NoteStoredInPayoutEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#"{{"{}"}}"#, self.to_str())
+    }
+}
+
+impl_default!(NoteStoredInPayoutEvent);
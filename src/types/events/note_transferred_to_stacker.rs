@@ -0,0 +1,120 @@
+use crate::{len::CURRENCY_VALUE_BLOCK, std::fmt, CurrencyValue, Error, ResponseStatus, Result};
+
+use super::Method;
+
+/// Represents a [NoteTransferredToStacker](crate::ResponseStatus::NoteTransferredToStacker) event.
+///
+/// A note has been moved from the payout store into the stacker cashbox.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NoteTransferredToStackerEvent {
+    value: CurrencyValue,
+}
+
+impl NoteTransferredToStackerEvent {
+    /// Creates a new [NoteTransferredToStackerEvent] from the [CurrencyValue].
+    pub const fn new(value: CurrencyValue) -> Self {
+        Self { value }
+    }
+
+    /// Gets the [Method] for the [NoteTransferredToStackerEvent].
+    pub const fn method() -> Method {
+        Method::NoteTransferredToStacker
+    }
+
+    /// Converts the [NoteTransferredToStackerEvent] to a string.
+    pub const fn to_str(&self) -> &'static str {
+        Self::method().to_str()
+    }
+
+    /// Gets the [CurrencyValue].
+    pub const fn value(&self) -> CurrencyValue {
+        self.value
+    }
+
+    /// Sets the [CurrencyValue].
+    pub fn set_value(&mut self, value: CurrencyValue) {
+        self.value = value;
+    }
+
+    /// Gets the length of the event in a [PollResponse](crate::PollResponse).
+    pub const fn len() -> usize {
+        1 + CURRENCY_VALUE_BLOCK
+    }
+}
+
+impl TryFrom<&[u8]> for NoteTransferredToStackerEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        let len = Self::len();
+
+        if val.len() < len {
+            Err(Error::InvalidLength((val.len(), len)))
+        } else {
+            match ResponseStatus::from(val[0]) {
+                ResponseStatus::NoteTransferredToStacker => {
+                    Ok(Self::new(CurrencyValue::from_buffer(&val[1..len])?))
+                }
+                event => Err(Error::InvalidEvent((
+                    event,
+                    ResponseStatus::NoteTransferredToStacker,
+                ))),
+            }
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<[u8; N]> for NoteTransferredToStackerEvent {
+    type Error = Error;
+
+    fn try_from(val: [u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8; N]> for NoteTransferredToStackerEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl From<CurrencyValue> for NoteTransferredToStackerEvent {
+    fn from(val: CurrencyValue) -> Self {
+        Self::new(val)
+    }
+}
+
+impl From<&CurrencyValue> for NoteTransferredToStackerEvent {
+    fn from(val: &CurrencyValue) -> Self {
+        (*val).into()
+    }
+}
+
+impl From<&NoteTransferredToStackerEvent> for &'static str {
+    fn from(val: &NoteTransferredToStackerEvent) -> Self {
+        val.to_str()
+    }
+}
+
+impl From<NoteTransferredToStackerEvent> for &'static str {
+    fn from(val: NoteTransferredToStackerEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for NoteTransferredToStackerEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let method = self.to_str();
+        let value = self.value();
+
+        write!(f, r#"{{"{method}": {value}}}"#)
+    }
+}
+
+impl Default for NoteTransferredToStackerEvent {
+    fn default() -> Self {
+        Self::new(CurrencyValue::new())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{impl_default, std::fmt, Error, ResponseStatus, Result};
+
+use super::Method;
+
+/// Represents a [PayoutOutOfService](crate::ResponseStatus::PayoutOutOfService) event.
+///
+/// The payout unit has gone out of service during operation.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PayoutOutOfServiceEvent;
+
+impl PayoutOutOfServiceEvent {
+    /// Creates a new [PayoutOutOfServiceEvent].
+    pub const fn new() -> Self {
+        Self {}
+    }
+
+    /// Gets the [Method] for the [PayoutOutOfServiceEvent].
+    pub const fn method() -> Method {
+        Method::PayoutOutOfService
+    }
+
+    /// Converts the [PayoutOutOfServiceEvent] to a string.
+    pub const fn to_str(&self) -> &'static str {
+        Self::method().to_str()
+    }
+
+    /// Gets the length of the event in a [PollResponse](crate::PollResponse).
+    pub const fn len() -> usize {
+        1
+    }
+}
+
+impl TryFrom<&[u8]> for PayoutOutOfServiceEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        if val.is_empty() {
+            Err(Error::InvalidLength((val.len(), 1)))
+        } else {
+            match ResponseStatus::from(val[0]) {
+                ResponseStatus::PayoutOutOfService => Ok(Self::new()),
+                event => Err(Error::InvalidEvent((
+                    event,
+                    ResponseStatus::PayoutOutOfService,
+                ))),
+            }
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<[u8; N]> for PayoutOutOfServiceEvent {
+    type Error = Error;
+
+    fn try_from(val: [u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8; N]> for PayoutOutOfServiceEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl From<&PayoutOutOfServiceEvent> for &'static str {
+    fn from(val: &PayoutOutOfServiceEvent) -> Self {
+        val.to_str()
+    }
+}
+
+impl From<PayoutOutOfServiceEvent> for &'static str {
+    fn from(val: PayoutOutOfServiceEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for PayoutOutOfServiceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#"{{"{}"}}"#, self.to_str())
+    }
+}
+
+impl_default!(PayoutOutOfServiceEvent);
@@ -0,0 +1,145 @@
+use crate::{
+    arrays::{deserialize_vec, serialize_vec},
+    std::fmt,
+    EnableBitfield, EnableBitfieldList, EnableChannel, Error, InhibitChannels, Result,
+};
+
+use super::Method;
+
+/// Maximum number of channels configurable with a
+/// [SetInhibitsCommand](crate::SetInhibitsCommand).
+pub const MAX_INHIBIT_CHANNELS: usize = 64;
+
+/// Minimum number of inhibit bitfields sent to the device.
+///
+/// From the SSP Protocol Manual, the NV200 accepts two to four bitfields, and other banknote
+/// validators accept one or two. Two bitfields (16 channels) are accepted by all of them.
+const MIN_INHIBIT_BITFIELDS: usize = 2;
+
+/// Convenience alias for a list of channel numbers.
+pub type InhibitChannelVec = heapless::Vec<u8, MAX_INHIBIT_CHANNELS>;
+
+/// Represents a [SetInhibits](crate::Method::SetInhibits) event.
+///
+/// Contains the channel numbers to enable, starting from one. All other channels are inhibited.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SetInhibitsEvent {
+    #[serde(serialize_with = "serialize_vec", deserialize_with = "deserialize_vec")]
+    channels: InhibitChannelVec,
+}
+
+impl SetInhibitsEvent {
+    /// Creates a new [SetInhibitsEvent].
+    pub const fn new() -> Self {
+        Self {
+            channels: InhibitChannelVec::new(),
+        }
+    }
+
+    /// Creates a new [SetInhibitsEvent] with the provided channels enabled.
+    pub const fn create(channels: InhibitChannelVec) -> Self {
+        Self { channels }
+    }
+
+    /// Gets the [Method] for the [SetInhibitsEvent].
+    pub const fn method() -> Method {
+        Method::SetInhibits
+    }
+
+    /// Converts the [SetInhibitsEvent] to a string.
+    pub const fn to_str(&self) -> &'static str {
+        Self::method().to_str()
+    }
+
+    /// Gets the length of the event in a [PollResponse](crate::PollResponse).
+    pub const fn len() -> usize {
+        1
+    }
+
+    /// Gets the list of enabled channels.
+    pub fn channels(&self) -> &[u8] {
+        self.channels.as_ref()
+    }
+
+    /// Converts the enabled channels into an [EnableBitfieldList] for a
+    /// [SetInhibitsCommand](crate::SetInhibitsCommand).
+    ///
+    /// Returns an error if a channel is outside the range `1..=64`.
+    pub fn inhibits(&self) -> Result<EnableBitfieldList> {
+        let max_channel = self.channels.iter().max().copied().unwrap_or(0) as usize;
+
+        if self.channels.contains(&0) || max_channel > MAX_INHIBIT_CHANNELS {
+            return Err(Error::InvalidInhibitChannels);
+        }
+
+        let num_bitfields = max_channel.div_ceil(8).max(MIN_INHIBIT_BITFIELDS);
+        let num_bitfields = usize::from(InhibitChannels::from(num_bitfields));
+
+        let mut bitfields = [EnableBitfield::from(0); MAX_INHIBIT_CHANNELS / 8];
+
+        for channel in self.channels.iter().map(|&c| c as usize - 1) {
+            bitfields[channel / 8].set_channel(channel, EnableChannel::Set);
+        }
+
+        Ok(bitfields[..num_bitfields]
+            .iter()
+            .copied()
+            .collect::<crate::Vec<EnableBitfield>>()
+            .into())
+    }
+}
+
+impl From<&SetInhibitsEvent> for &'static str {
+    fn from(val: &SetInhibitsEvent) -> Self {
+        val.to_str()
+    }
+}
+
+impl From<SetInhibitsEvent> for &'static str {
+    fn from(val: SetInhibitsEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for SetInhibitsEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let method = self.to_str();
+
+        write!(f, r#"{{"{method}": {{"channels": ["#)?;
+
+        for (i, channel) in self.channels.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{channel}")?;
+        }
+
+        write!(f, "]}}}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_inhibits_event_bitfields() -> Result<()> {
+        let event = SetInhibitsEvent::create(InhibitChannelVec::from_slice(&[1, 3, 10]).unwrap());
+        let inhibits = event.inhibits()?;
+
+        assert_eq!(inhibits.len(), 2);
+        assert_eq!(u8::from(inhibits.as_inner()[0]), 0b0000_0101);
+        assert_eq!(u8::from(inhibits.as_inner()[1]), 0b0000_0010);
+
+        let event = SetInhibitsEvent::create(InhibitChannelVec::from_slice(&[24]).unwrap());
+        assert_eq!(event.inhibits()?.len(), 3);
+
+        let event = SetInhibitsEvent::create(InhibitChannelVec::from_slice(&[0]).unwrap());
+        assert_eq!(event.inhibits(), Err(Error::InvalidInhibitChannels));
+
+        let event = SetInhibitsEvent::create(InhibitChannelVec::from_slice(&[65]).unwrap());
+        assert_eq!(event.inhibits(), Err(Error::InvalidInhibitChannels));
+
+        Ok(())
+    }
+}
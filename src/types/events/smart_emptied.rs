@@ -0,0 +1,113 @@
+use crate::{std::fmt, CurrencyValueList, Error, ProtocolVersion, ResponseStatus, Result};
+
+use super::Method;
+
+/// Represents a [SmartEmptied](crate::ResponseStatus::SmartEmptied) event.
+///
+/// The device has finished emptying its stored notes to the cashbox, following a
+/// [SmartEmptyCommand](crate::SmartEmptyCommand), and reports the total value emptied.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SmartEmptiedEvent {
+    values: CurrencyValueList,
+}
+
+impl SmartEmptiedEvent {
+    /// Creates a new [SmartEmptiedEvent] from the [CurrencyValueList].
+    pub const fn new(values: CurrencyValueList) -> Self {
+        Self { values }
+    }
+
+    /// Gets the [Method] for the [SmartEmptiedEvent].
+    pub const fn method() -> Method {
+        Method::SmartEmptied
+    }
+
+    /// Converts the [SmartEmptiedEvent] to a string.
+    pub const fn to_str(&self) -> &'static str {
+        Self::method().to_str()
+    }
+
+    /// Gets a reference to the [CurrencyValueList].
+    pub fn values(&self) -> &CurrencyValueList {
+        &self.values
+    }
+
+    /// Sets the [CurrencyValueList].
+    pub fn set_values(&mut self, values: CurrencyValueList) {
+        self.values = values;
+    }
+
+    /// Gets the length of the event at the start of `data` in a [PollResponse](crate::PollResponse).
+    ///
+    /// The length depends on the [ProtocolVersion], and the number of countries in the event data.
+    pub fn len(data: &[u8], protocol: ProtocolVersion) -> Result<usize> {
+        let event_data = data.get(1..).unwrap_or_default();
+
+        Ok(1 + CurrencyValueList::event_data_len(event_data, protocol)?)
+    }
+}
+
+impl TryFrom<&[u8]> for SmartEmptiedEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        match val.len() {
+            0 => Err(Error::InvalidLength((0, 1))),
+            _ => match ResponseStatus::from(val[0]) {
+                ResponseStatus::SmartEmptied => {
+                    Ok(Self::new(CurrencyValueList::from_event_data(&val[1..])?))
+                }
+                event => Err(Error::InvalidEvent((event, ResponseStatus::SmartEmptied))),
+            },
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<[u8; N]> for SmartEmptiedEvent {
+    type Error = Error;
+
+    fn try_from(val: [u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8; N]> for SmartEmptiedEvent {
+    type Error = Error;
+
+    fn try_from(val: &[u8; N]) -> Result<Self> {
+        val.as_ref().try_into()
+    }
+}
+
+impl From<CurrencyValueList> for SmartEmptiedEvent {
+    fn from(val: CurrencyValueList) -> Self {
+        Self::new(val)
+    }
+}
+
+impl From<&SmartEmptiedEvent> for &'static str {
+    fn from(val: &SmartEmptiedEvent) -> Self {
+        val.to_str()
+    }
+}
+
+impl From<SmartEmptiedEvent> for &'static str {
+    fn from(val: SmartEmptiedEvent) -> Self {
+        (&val).into()
+    }
+}
+
+impl fmt::Display for SmartEmptiedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let method = self.to_str();
+        let values = self.values();
+
+        write!(f, r#"{{"{method}": {values}}}"#)
+    }
+}
+
+impl Default for SmartEmptiedEvent {
+    fn default() -> Self {
+        Self::new(CurrencyValueList::new())
+    }
+}
@@ -0,0 +1,177 @@
+use super::{ChannelValue, CountryCode};
+use crate::{len::INCOMPLETE_VALUE_BLOCK, std::fmt, Error, Result};
+
+mod list;
+pub use list::*;
+
+/// Convenience alias for a serialized [IncompleteValue] array.
+pub type IncompleteValueBlock = [u8; INCOMPLETE_VALUE_BLOCK];
+
+/// Represents the value dispensed, and the value originally requested, in a specific currency.
+///
+/// Reported by the [IncompletePayoutEvent](crate::IncompletePayoutEvent) and
+/// [IncompleteFloatEvent](crate::IncompleteFloatEvent) when an operation was interrupted, e.g. by a
+/// power failure.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct IncompleteValue {
+    dispensed: ChannelValue,
+    requested: ChannelValue,
+    currency: CountryCode,
+}
+
+impl IncompleteValue {
+    /// Creates a new [IncompleteValue].
+    pub const fn new() -> Self {
+        Self {
+            dispensed: ChannelValue::from_inner(0),
+            requested: ChannelValue::from_inner(0),
+            currency: CountryCode::new(),
+        }
+    }
+
+    /// Creates a new [IncompleteValue] with the provided parameters.
+    pub const fn create(
+        dispensed: ChannelValue,
+        requested: ChannelValue,
+        currency: CountryCode,
+    ) -> Self {
+        Self {
+            dispensed,
+            requested,
+            currency,
+        }
+    }
+
+    /// Gets the dispensed [ChannelValue].
+    pub const fn dispensed(&self) -> ChannelValue {
+        self.dispensed
+    }
+
+    /// Sets the dispensed [ChannelValue].
+    pub fn set_dispensed(&mut self, val: ChannelValue) {
+        self.dispensed = val;
+    }
+
+    /// Builder function that sets the dispensed [ChannelValue].
+    pub fn with_dispensed(mut self, val: ChannelValue) -> Self {
+        self.set_dispensed(val);
+        self
+    }
+
+    /// Gets the requested [ChannelValue].
+    pub const fn requested(&self) -> ChannelValue {
+        self.requested
+    }
+
+    /// Sets the requested [ChannelValue].
+    pub fn set_requested(&mut self, val: ChannelValue) {
+        self.requested = val;
+    }
+
+    /// Builder function that sets the requested [ChannelValue].
+    pub fn with_requested(mut self, val: ChannelValue) -> Self {
+        self.set_requested(val);
+        self
+    }
+
+    /// Gets the [CountryCode].
+    pub const fn currency(&self) -> CountryCode {
+        self.currency
+    }
+
+    /// Sets the [CountryCode].
+    pub fn set_currency(&mut self, val: CountryCode) {
+        self.currency = val;
+    }
+
+    /// Builder function that sets the [CountryCode].
+    pub fn with_currency(mut self, val: CountryCode) -> Self {
+        self.set_currency(val);
+        self
+    }
+
+    /// Serializes the [IncompleteValue] to a mutable buffer.
+    ///
+    /// Parameters:
+    ///
+    /// - `buf`: mutable reference to a byte buffer
+    ///
+    /// Returns:
+    ///
+    /// - `Ok(())`
+    /// - [`Error::InvalidLength`] if `buf` length is less than [`INCOMPLETE_VALUE_BLOCK`]
+    pub fn to_buffer(&self, buf: &mut [u8]) -> Result<()> {
+        let buf_len = buf.len();
+        if buf_len < INCOMPLETE_VALUE_BLOCK {
+            Err(Error::InvalidLength((buf_len, INCOMPLETE_VALUE_BLOCK)))
+        } else {
+            buf[..4].copy_from_slice(self.dispensed().as_inner().to_le_bytes().as_ref());
+            buf[4..8].copy_from_slice(self.requested().as_inner().to_le_bytes().as_ref());
+            buf[8..INCOMPLETE_VALUE_BLOCK]
+                .copy_from_slice(<&str>::from(self.currency()).as_bytes());
+
+            Ok(())
+        }
+    }
+
+    /// Deserializes the [IncompleteValue] from a buffer.
+    ///
+    /// Parameters:
+    ///
+    /// - `buf`: reference to a byte buffer
+    ///
+    /// Returns:
+    ///
+    /// - Ok([`IncompleteValue`])
+    /// - [`Error::InvalidLength`] if `buf` length is less than [`INCOMPLETE_VALUE_BLOCK`]
+    pub fn from_buffer(buf: &[u8]) -> Result<Self> {
+        let len = buf.len();
+
+        if len < INCOMPLETE_VALUE_BLOCK {
+            Err(Error::InvalidLength((len, INCOMPLETE_VALUE_BLOCK)))
+        } else {
+            let dispensed = ChannelValue::from(&buf[..4]);
+            let requested = ChannelValue::from(&buf[4..8]);
+            let currency = CountryCode::from(&buf[8..INCOMPLETE_VALUE_BLOCK]);
+
+            Ok(Self::create(dispensed, requested, currency))
+        }
+    }
+}
+
+impl From<IncompleteValue> for IncompleteValueBlock {
+    fn from(val: IncompleteValue) -> Self {
+        let mut res = [0u8; INCOMPLETE_VALUE_BLOCK];
+
+        // buffer is a valid length, so `to_buffer` will never fail.
+        val.to_buffer(res.as_mut()).ok();
+
+        res
+    }
+}
+
+impl From<&IncompleteValueBlock> for IncompleteValue {
+    fn from(val: &IncompleteValueBlock) -> Self {
+        // the unwrap branch should never happen, since the buffer length is valid.
+        Self::from_buffer(val.as_ref()).unwrap_or(Self::new())
+    }
+}
+
+impl TryFrom<&[u8]> for IncompleteValue {
+    type Error = Error;
+
+    fn try_from(val: &[u8]) -> Result<Self> {
+        Self::from_buffer(val)
+    }
+}
+
+impl fmt::Display for IncompleteValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""dispensed":{},"#, self.dispensed())?;
+        write!(f, r#""requested":{},"#, self.requested())?;
+        write!(f, r#""currency":{}"#, self.currency())?;
+        write!(f, "}}")
+    }
+}
@@ -0,0 +1,205 @@
+use super::IncompleteValue;
+use crate::{
+    arrays::{deserialize_vec, serialize_vec},
+    len::{INCOMPLETE_VALUE_BLOCK, MAX_DATA},
+    std::{cmp, fmt, slice},
+    types::currency_value::is_single_value,
+    ChannelValue, Error, ProtocolVersion, Result,
+};
+
+/// Length of the dispensed and requested values event data used before [ProtocolVersion::Six].
+const SINGLE_VALUE_LEN: usize = 8;
+
+/// Maximum number of [IncompleteValue]s in a single poll event.
+///
+/// Limited by the response data length, minus the status and number of countries bytes.
+pub const MAX_INCOMPLETE_VALUES: usize = (MAX_DATA - 2) / INCOMPLETE_VALUE_BLOCK;
+
+/// Convenience alias for an [IncompleteValue] vector.
+pub type IncompleteValueVec = heapless::Vec<IncompleteValue, MAX_INCOMPLETE_VALUES>;
+
+/// Container for a list of [IncompleteValue]s.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct IncompleteValueList {
+    #[serde(serialize_with = "serialize_vec", deserialize_with = "deserialize_vec")]
+    values: IncompleteValueVec,
+}
+
+impl IncompleteValueList {
+    /// Creates a new empty list.
+    pub const fn new() -> Self {
+        Self {
+            values: IncompleteValueVec::new(),
+        }
+    }
+
+    /// Creates a new [IncompleteValueList] from the provided [IncompleteValue]s.
+    pub const fn create(values: IncompleteValueVec) -> Self {
+        Self { values }
+    }
+
+    /// Gets an iterator over the list.
+    pub fn iter(&self) -> slice::Iter<'_, IncompleteValue> {
+        self.values.iter()
+    }
+
+    /// Gets a mutable iterator over the list.
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, IncompleteValue> {
+        self.values.iter_mut()
+    }
+
+    /// Gets the list length.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Gets the list capacity.
+    pub fn capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
+    /// Gets whether the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Get the list as a reference to its inner container type.
+    pub fn as_inner(&self) -> &IncompleteValueVec {
+        &self.values
+    }
+
+    /// Get the list as a mutable reference to its inner container type.
+    pub fn as_inner_mut(&mut self) -> &mut IncompleteValueVec {
+        &mut self.values
+    }
+
+    /// Converts the list into its inner container type.
+    pub fn into_inner(self) -> IncompleteValueVec {
+        self.values
+    }
+
+    /// Gets the length of the event data at the start of `data`, not including the event status
+    /// byte.
+    ///
+    /// Before [ProtocolVersion::Six], event data is the 4 byte dispensed value, followed by the 4
+    /// byte requested value. Later versions send the number of countries, followed by an
+    /// [IncompleteValue] block for each country.
+    pub fn event_data_len(data: &[u8], protocol: ProtocolVersion) -> Result<usize> {
+        if is_single_value(protocol) {
+            Ok(SINGLE_VALUE_LEN)
+        } else {
+            let num = data.first().ok_or(Error::InvalidLength((0, 1)))?;
+            Ok(1 + (*num as usize * INCOMPLETE_VALUE_BLOCK))
+        }
+    }
+
+    /// Parses the list from event data, not including the event status byte.
+    ///
+    /// Single value event data (before [ProtocolVersion::Six]) does not include a country code,
+    /// so the values are returned with the default [CountryCode](crate::CountryCode).
+    pub fn from_event_data(data: &[u8]) -> Result<Self> {
+        let data_len = data.len();
+
+        if data_len == SINGLE_VALUE_LEN {
+            let mut values = IncompleteValueVec::new();
+            // list is empty, so `push` will not fail
+            values
+                .push(
+                    IncompleteValue::new()
+                        .with_dispensed(ChannelValue::from(&data[..4]))
+                        .with_requested(ChannelValue::from(&data[4..])),
+                )
+                .ok();
+
+            return Ok(values.into());
+        }
+
+        let num = data
+            .first()
+            .map(|&n| n as usize)
+            .ok_or(Error::InvalidLength((0, 1)))?;
+        let end = 1 + (num * INCOMPLETE_VALUE_BLOCK);
+
+        if num > MAX_INCOMPLETE_VALUES {
+            Err(Error::InvalidLength((num, MAX_INCOMPLETE_VALUES)))
+        } else if data_len != end {
+            Err(Error::InvalidLength((data_len, end)))
+        } else {
+            let mut values = IncompleteValueVec::new();
+
+            for block in data[1..end].chunks_exact(INCOMPLETE_VALUE_BLOCK) {
+                // `push` only fails if `heapless::Vec` is full
+                // since we checked valid range above, `push` will not fail
+                values.push(IncompleteValue::try_from(block)?).ok();
+            }
+
+            Ok(values.into())
+        }
+    }
+}
+
+impl AsRef<[IncompleteValue]> for IncompleteValueList {
+    fn as_ref(&self) -> &[IncompleteValue] {
+        self.values.as_slice()
+    }
+}
+
+impl AsMut<[IncompleteValue]> for IncompleteValueList {
+    fn as_mut(&mut self) -> &mut [IncompleteValue] {
+        self.values.as_mut()
+    }
+}
+
+impl From<IncompleteValueVec> for IncompleteValueList {
+    fn from(val: IncompleteValueVec) -> Self {
+        Self { values: val }
+    }
+}
+
+impl From<&[IncompleteValue]> for IncompleteValueList {
+    fn from(val: &[IncompleteValue]) -> Self {
+        let len = cmp::min(val.len(), MAX_INCOMPLETE_VALUES);
+        let mut values = IncompleteValueVec::new();
+
+        // the above call to `min` ensures the length is in the valid range
+        values.extend_from_slice(&val[..len]).ok();
+
+        Self { values }
+    }
+}
+
+impl<const N: usize> From<[IncompleteValue; N]> for IncompleteValueList {
+    fn from(val: [IncompleteValue; N]) -> Self {
+        val.as_ref().into()
+    }
+}
+
+impl<const N: usize> From<&[IncompleteValue; N]> for IncompleteValueList {
+    fn from(val: &[IncompleteValue; N]) -> Self {
+        val.as_ref().into()
+    }
+}
+
+impl fmt::Display for IncompleteValueList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, r#""values":["#)?;
+
+        for (i, value) in self.iter().enumerate() {
+            write!(f, "{value}")?;
+
+            if i < self.len() - 1 {
+                write!(f, ",")?;
+            }
+        }
+
+        write!(f, "]}}")
+    }
+}
+
+impl Default for IncompleteValueList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
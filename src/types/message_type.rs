@@ -66,6 +66,8 @@ pub enum MessageType {
     FirmwareVersion = 0x20,
     /// Gets the dataset version of the device.
     DatasetVersion = 0x21,
+    /// Gets the number of notes stored for all denominations in the payout device.
+    GetAllLevels = 0x22,
     /// Set the configuration status of any barcode readers present on the device.
     SetBarcodeReaderConfiguration = 0x23,
     /// Get the configuration status of any barcode readers present on the device.
@@ -78,8 +80,26 @@ pub enum MessageType {
     GetBarcodeData = 0x27,
     /// Gets the manufacturer's extension of the device.
     ManufacturerExtension = 0x30,
+    /// Payout a monetary value to the customer.
+    PayoutAmount = 0x33,
+    /// Increments the stored level of a denomination in the payout device.
+    SetDenominationLevel = 0x34,
+    /// Gets the stored level of a denomination in the payout device.
+    GetDenominationLevel = 0x35,
+    /// Stops the execution of the current payout.
+    HaltPayout = 0x38,
+    /// Routes a denomination to be stored for payout, or sent to the cashbox.
+    SetDenominationRoute = 0x3b,
+    /// Gets the route of a denomination: stored for payout, or sent to the cashbox.
+    GetDenominationRoute = 0x3c,
+    /// Float the payout device to leave a monetary value, sending the rest to the cashbox.
+    FloatAmount = 0x3d,
+    /// Gets the minimum payout value the device can provide.
+    GetMinimumPayout = 0x3e,
     /// Causes the SMART Payout to empty all its stored notes to the cashbox.
     Empty = 0x3f,
+    /// Float the payout device to the requested levels of individual denominations.
+    FloatByDenomination = 0x44,
     /// Payout notes to the customer by denomination.
     PayoutByDenomination = 0x46,
     /// Sets the eSSP generator prime (64-bits).
@@ -141,13 +161,23 @@ impl From<u8> for MessageType {
             0x18 => Self::Hold,
             0x20 => Self::FirmwareVersion,
             0x21 => Self::DatasetVersion,
+            0x22 => Self::GetAllLevels,
             0x23 => Self::SetBarcodeReaderConfiguration,
             0x24 => Self::GetBarcodeReaderConfiguration,
             0x25 => Self::GetBarcodeInhibit,
             0x26 => Self::SetBarcodeInhibit,
             0x27 => Self::GetBarcodeData,
             0x30 => Self::ManufacturerExtension,
+            0x33 => Self::PayoutAmount,
+            0x34 => Self::SetDenominationLevel,
+            0x35 => Self::GetDenominationLevel,
+            0x38 => Self::HaltPayout,
+            0x3b => Self::SetDenominationRoute,
+            0x3c => Self::GetDenominationRoute,
+            0x3d => Self::FloatAmount,
+            0x3e => Self::GetMinimumPayout,
             0x3f => Self::Empty,
+            0x44 => Self::FloatByDenomination,
             0x46 => Self::PayoutByDenomination,
             0x4a => Self::SetGenerator,
             0x4b => Self::SetModulus,
@@ -200,13 +230,23 @@ impl From<MessageType> for &'static str {
             MessageType::Hold => "Hold",
             MessageType::FirmwareVersion => "FirmwareVersion",
             MessageType::DatasetVersion => "DatasetVersion",
+            MessageType::GetAllLevels => "GetAllLevels",
             MessageType::SetBarcodeReaderConfiguration => "SetBarcodeReaderConfiguration",
             MessageType::GetBarcodeReaderConfiguration => "GetBarcodeReaderConfiguration",
             MessageType::GetBarcodeInhibit => "GetBarcodeInhibit",
             MessageType::SetBarcodeInhibit => "SetBarcodeInhibit",
             MessageType::GetBarcodeData => "GetBarcodeData",
             MessageType::ManufacturerExtension => "ManufacturerExtension",
+            MessageType::PayoutAmount => "PayoutAmount",
+            MessageType::SetDenominationLevel => "SetDenominationLevel",
+            MessageType::GetDenominationLevel => "GetDenominationLevel",
+            MessageType::HaltPayout => "HaltPayout",
+            MessageType::SetDenominationRoute => "SetDenominationRoute",
+            MessageType::GetDenominationRoute => "GetDenominationRoute",
+            MessageType::FloatAmount => "FloatAmount",
+            MessageType::GetMinimumPayout => "GetMinimumPayout",
             MessageType::Empty => "Empty",
+            MessageType::FloatByDenomination => "FloatByDenomination",
             MessageType::PayoutByDenomination => "PayoutByDenomination",
             MessageType::SetGenerator => "SetGenerator",
             MessageType::SetModulus => "SetModulus",
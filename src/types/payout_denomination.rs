@@ -7,8 +7,10 @@ pub use list::*;
 /// Convenience alias for a serialized [PayoutDenomination] array.
 pub type PayoutBlock = [u8; PAYOUT_BLOCK];
 
-/// Represents an option byte at the end of the
-/// [PayoutByDenominationCommand](crate::PayoutByDenominationCommand) array.
+/// Represents an option byte at the end of the payout and float command arrays, e.g.
+/// [PayoutByDenominationCommand](crate::PayoutByDenominationCommand),
+/// [PayoutAmountCommand](crate::PayoutAmountCommand), and
+/// [FloatAmountCommand](crate::FloatAmountCommand).
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub enum PayoutOption {
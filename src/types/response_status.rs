@@ -79,6 +79,82 @@ pub enum ResponseStatus {
     /// the escrow position. The host can also issue a reject command at this point to reject the banknote
     /// back to the user.
     Read = 0xef,
+    /// Payout has completed.
+    ///
+    /// Shows the total value the device has dispensed in response to a payout command.
+    Dispensed = 0xd2,
+    /// The payout unit has jammed.
+    ///
+    /// Gives the value paid/floated up to the jam point. Used on the SMART Payout when a jam occurs
+    /// during a payout, float, or empty operation.
+    Jammed = 0xd5,
+    /// Payout has been interrupted.
+    ///
+    /// Given when the host requested a halt to the device, or (Protocol Version 6 and earlier) the
+    /// payout was automatically cancelled. The value paid up to the halt point is given in the
+    /// event data.
+    Halted = 0xd6,
+    /// The device is in the process of floating.
+    ///
+    /// Shows the value floated to the cashbox up to the poll point.
+    Floating = 0xd7,
+    /// Floating has completed.
+    ///
+    /// Shows the value actually floated at the end of the floating process.
+    Floated = 0xd8,
+    /// The device has been unable to complete a payout request.
+    ///
+    /// The value dispensed or floated up until the timeout point is given in the event data.
+    Timeout = 0xd9,
+    /// The device is in the process of paying out a requested value.
+    ///
+    /// The value paid at the poll point is given in the event data.
+    Dispensing = 0xda,
+    /// The last payout request was interrupted.
+    ///
+    /// Detected by the device on power-up, possibly due to a power failure. The value paid and the
+    /// value requested are given in the event data.
+    IncompletePayout = 0xdc,
+    /// The last float request was interrupted.
+    ///
+    /// Detected by the device on power-up, possibly due to a power failure. The value floated and
+    /// the value requested are given in the event data.
+    IncompleteFloat = 0xdd,
+    /// The device is emptying its stored notes to the cashbox.
+    ///
+    /// Given while the device performs an empty operation following an Empty command.
+    Emptying = 0xc2,
+    /// The device has completed its empty operation in response to an Empty command.
+    Emptied = 0xc3,
+    /// The device is carrying out a Smart Empty command.
+    ///
+    /// The value emptied up to the poll point is given in the event data.
+    SmartEmptying = 0xb3,
+    /// The device has completed a Smart Empty command.
+    ///
+    /// The total value emptied is given in the event data.
+    SmartEmptied = 0xb4,
+    /// The note has been passed into the note store of the payout unit.
+    NoteStoredInPayout = 0xdb,
+    /// A note has been moved from the payout store into the stacker cashbox.
+    ///
+    /// The value and country code of the note are given in the event data.
+    NoteTransferredToStacker = 0xc9,
+    /// A dispensing note is held in the bezel of the payout device.
+    ///
+    /// The value and country code of the note are given in the event data.
+    NoteHeldInBezel = 0xce,
+    /// The device has detected that it is full of banknotes, and no more can be added.
+    DeviceFull = 0xcf,
+    /// The payout unit has gone out of service during operation.
+    ///
+    /// The host can send an Enable Payout command to determine if the payout unit comes back into
+    /// service.
+    PayoutOutOfService = 0xc6,
+    /// The device has detected that its note path has been opened.
+    ///
+    /// The device will be disabled for bill entry until the note path is closed.
+    NotePathOpen = 0xe0,
     /// OK is the first byte returned in the response to a successful command. It does not indicate
     /// that the command has completed, just that it has been received and understood.
     Ok = 0xf0,
@@ -149,6 +225,25 @@ impl ResponseStatus {
             0xed => Self::Rejecting,
             0xee => Self::NoteCredit,
             0xef => Self::Read,
+            0xd2 => Self::Dispensed,
+            0xd5 => Self::Jammed,
+            0xd6 => Self::Halted,
+            0xd7 => Self::Floating,
+            0xd8 => Self::Floated,
+            0xd9 => Self::Timeout,
+            0xda => Self::Dispensing,
+            0xdc => Self::IncompletePayout,
+            0xdd => Self::IncompleteFloat,
+            0xc2 => Self::Emptying,
+            0xc3 => Self::Emptied,
+            0xb3 => Self::SmartEmptying,
+            0xb4 => Self::SmartEmptied,
+            0xdb => Self::NoteStoredInPayout,
+            0xc9 => Self::NoteTransferredToStacker,
+            0xce => Self::NoteHeldInBezel,
+            0xcf => Self::DeviceFull,
+            0xc6 => Self::PayoutOutOfService,
+            0xe0 => Self::NotePathOpen,
             0xf0 => Self::Ok,
             0xf1 => Self::DeviceReset,
             0xf2 => Self::CommandNotKnown,
@@ -179,6 +274,25 @@ impl ResponseStatus {
             Self::Rejecting => 0xed,
             Self::NoteCredit => 0xee,
             Self::Read => 0xef,
+            Self::Dispensed => 0xd2,
+            Self::Jammed => 0xd5,
+            Self::Halted => 0xd6,
+            Self::Floating => 0xd7,
+            Self::Floated => 0xd8,
+            Self::Timeout => 0xd9,
+            Self::Dispensing => 0xda,
+            Self::IncompletePayout => 0xdc,
+            Self::IncompleteFloat => 0xdd,
+            Self::Emptying => 0xc2,
+            Self::Emptied => 0xc3,
+            Self::SmartEmptying => 0xb3,
+            Self::SmartEmptied => 0xb4,
+            Self::NoteStoredInPayout => 0xdb,
+            Self::NoteTransferredToStacker => 0xc9,
+            Self::NoteHeldInBezel => 0xce,
+            Self::DeviceFull => 0xcf,
+            Self::PayoutOutOfService => 0xc6,
+            Self::NotePathOpen => 0xe0,
             Self::Ok => 0xf0,
             Self::DeviceReset => 0xf1,
             Self::CommandNotKnown => 0xf2,
@@ -228,6 +342,25 @@ impl From<ResponseStatus> for &'static str {
             ResponseStatus::Rejecting => "Rejecting",
             ResponseStatus::NoteCredit => "NoteCredit",
             ResponseStatus::Read => "Read",
+            ResponseStatus::Dispensed => "Dispensed",
+            ResponseStatus::Jammed => "Jammed",
+            ResponseStatus::Halted => "Halted",
+            ResponseStatus::Floating => "Floating",
+            ResponseStatus::Floated => "Floated",
+            ResponseStatus::Timeout => "Timeout",
+            ResponseStatus::Dispensing => "Dispensing",
+            ResponseStatus::IncompletePayout => "IncompletePayout",
+            ResponseStatus::IncompleteFloat => "IncompleteFloat",
+            ResponseStatus::Emptying => "Emptying",
+            ResponseStatus::Emptied => "Emptied",
+            ResponseStatus::SmartEmptying => "SmartEmptying",
+            ResponseStatus::SmartEmptied => "SmartEmptied",
+            ResponseStatus::NoteStoredInPayout => "NoteStoredInPayout",
+            ResponseStatus::NoteTransferredToStacker => "NoteTransferredToStacker",
+            ResponseStatus::NoteHeldInBezel => "NoteHeldInBezel",
+            ResponseStatus::DeviceFull => "DeviceFull",
+            ResponseStatus::PayoutOutOfService => "PayoutOutOfService",
+            ResponseStatus::NotePathOpen => "NotePathOpen",
             ResponseStatus::Ok => "OK",
             ResponseStatus::DeviceReset => "DeviceReset",
             ResponseStatus::CommandNotKnown => "CommandNotKnown",
@@ -0,0 +1,81 @@
+//! Connections to the device, either through `ssp-server`, or directly over a serial port.
+
+use std::{thread, time::Duration};
+
+use ssp::jsonrpc::{Client, Endpoint};
+use ssp::{Device, Error, Event, Result, SerialLink};
+
+/// Time between checks for new events, in milliseconds.
+///
+/// From the SSP Implementation Guide, the delay between polls should be no less than 200 ms.
+const WATCH_INTERVAL_MS: u64 = 200;
+
+/// Connection used to run commands against the device.
+pub enum Backend {
+    /// JSON-RPC connection to `ssp-server`.
+    Socket(Box<Client>),
+    /// Direct connection to the device on a serial port.
+    Serial(Box<Device<SerialLink>>),
+}
+
+impl Backend {
+    /// Connects to `ssp-server` at the [Endpoint].
+    pub fn socket(endpoint: Endpoint) -> Result<Self> {
+        Ok(Self::Socket(Box::new(Client::connect(endpoint)?)))
+    }
+
+    /// Opens the serial port at `path`, and runs the device setup sequence.
+    ///
+    /// The device is left disabled, if `enable` is set it is enabled to accept notes.
+    pub fn serial(path: &str, enable: bool) -> Result<Self> {
+        let mut device = Device::new(SerialLink::open(path)?);
+        let setup = device.initialize()?;
+
+        if enable {
+            device.enable()?;
+        }
+
+        log::info!("Connected to device: {setup}");
+
+        Ok(Self::Serial(Box::new(device)))
+    }
+
+    /// Sends a request [Event] to the device, and returns the response [Event].
+    pub fn request(&mut self, event: &Event) -> Result<Event> {
+        match self {
+            Self::Socket(client) => client.send_event(event),
+            Self::Serial(device) => device.handle_event(event),
+        }
+    }
+
+    /// Passes events reported by the device to `handler`, until the connection fails.
+    pub fn watch<F>(&mut self, mut handler: F) -> Result<()>
+    where
+        F: FnMut(&Event),
+    {
+        let interval = Duration::from_millis(WATCH_INTERVAL_MS);
+
+        loop {
+            match self {
+                Self::Socket(client) => match client.next_event(interval) {
+                    Ok(event) => handler(&event),
+                    Err(Error::Timeout(_)) => (),
+                    Err(err) => return Err(err),
+                },
+                Self::Serial(device) => {
+                    let res = device.poll()?;
+
+                    // an unknown or truncated event ends the parse of this poll, but not the watch
+                    for payload in res.events(device.protocol_version()) {
+                        match payload {
+                            Ok(payload) => handler(&Event::new(payload.method(), payload)),
+                            Err(err) => log::error!("Error parsing poll event: {err}"),
+                        }
+                    }
+
+                    thread::sleep(interval);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,272 @@
+//! Command-line client for operating SSP/eSSP devices.
+//!
+//! Commands are sent to a running `ssp-server` over its JSON-RPC socket (Unix, TCP, or WebSocket),
+//! or directly to a device on a serial port with `--serial`.
+//!
+//! Results and events are printed to stdout as JSON lines.
+
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use ssp::jsonrpc::{Endpoint, JSONRPC_ENV_SOCK, JSONRPC_SOCKET_PATH};
+use ssp::{
+    CountryCode, DispenseEvent, Event, InhibitChannelVec, Method, PayoutDenomination,
+    PayoutDenominationList, PayoutVec, SetInhibitsEvent,
+};
+
+mod backend;
+
+use backend::Backend;
+
+/// Command-line client for SSP/eSSP devices.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// `ssp-server` JSON-RPC endpoint: a Unix socket path, `tcp://host:port`, or
+    /// `ws://host:port[/path]`.
+    #[arg(long, env = JSONRPC_ENV_SOCK, default_value = JSONRPC_SOCKET_PATH, value_parser = parse_endpoint)]
+    socket: Endpoint,
+
+    /// Talk to the device on this serial port directly, instead of through `ssp-server`.
+    ///
+    /// Takes precedence over `--socket`.
+    #[arg(long)]
+    serial: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Enable the device to accept notes.
+    Enable,
+    /// Disable the device.
+    Disable,
+    /// Get the device status.
+    Status,
+    /// Reject the note held in escrow.
+    Reject,
+    /// Stack the note held in escrow.
+    Stack,
+    /// Pay out notes by denomination.
+    Dispense {
+        /// Currency of the dispensed notes.
+        #[arg(long, default_value = "EUR", value_parser = parse_currency)]
+        currency: CountryCode,
+        /// Notes to dispense, as `<NUMBER>x<VALUE>` (e.g. `2x500`).
+        #[arg(required = true, value_parser = parse_denomination)]
+        denominations: Vec<(u16, u32)>,
+    },
+    /// Enable note acceptance on the listed channels, and inhibit all others.
+    SetInhibits {
+        /// Channel numbers to enable, starting from one.
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=64))]
+        channels: Vec<u8>,
+    },
+    /// Stream device events as JSON lines until interrupted.
+    Watch,
+}
+
+impl Command {
+    /// Gets whether the device must accept notes before running the command.
+    ///
+    /// Only used when talking to the device directly, `ssp-server` manages the device state.
+    fn needs_enable(&self) -> bool {
+        matches!(self, Self::Dispense { .. } | Self::Watch)
+    }
+
+    fn to_event(&self) -> Result<Event, String> {
+        let event = match self {
+            Self::Enable => Method::Enable.into(),
+            Self::Disable => Method::Disable.into(),
+            Self::Status => Method::Status.into(),
+            Self::Reject => Method::Reject.into(),
+            Self::Stack => Method::Stack.into(),
+            Self::Dispense {
+                currency,
+                denominations,
+            } => {
+                let mut payouts = PayoutVec::new();
+
+                for &(number, value) in denominations.iter() {
+                    payouts
+                        .push(PayoutDenomination::create(number, value, *currency))
+                        .map_err(|_| "too many denominations".to_string())?;
+                }
+
+                DispenseEvent::create(PayoutDenominationList::create(payouts)).into()
+            }
+            Self::SetInhibits { channels } => {
+                let channels = InhibitChannelVec::from_slice(channels.as_ref())
+                    .map_err(|_| "too many channels".to_string())?;
+
+                SetInhibitsEvent::create(channels).into()
+            }
+            Self::Watch => return Err("watch is not a request".into()),
+        };
+
+        Ok(event)
+    }
+}
+
+fn parse_endpoint(val: &str) -> Result<Endpoint, String> {
+    val.parse().map_err(|err: ssp::Error| err.to_string())
+}
+
+fn parse_currency(val: &str) -> Result<CountryCode, String> {
+    let code = val.to_uppercase();
+
+    if code.len() == 3 && code.bytes().all(|b| b.is_ascii_uppercase()) {
+        Ok(CountryCode::from(code.as_bytes()))
+    } else {
+        Err(format!("invalid ISO 4217 currency code: {val}"))
+    }
+}
+
+fn parse_denomination(val: &str) -> Result<(u16, u32), String> {
+    let (number, value) = val
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("expected <NUMBER>x<VALUE>, found: {val}"))?;
+
+    let number = number
+        .parse::<u16>()
+        .map_err(|err| format!("invalid number of notes {number}: {err}"))?;
+    let value = value
+        .parse::<u32>()
+        .map_err(|err| format!("invalid note value {value}: {err}"))?;
+
+    Ok((number, value))
+}
+
+/// Prints an [Event] as a JSON line.
+fn print_event(event: &Event) {
+    let json = serde_json::json!({
+        "method": event.method(),
+        "params": event.payload().to_json(),
+    });
+
+    println!("{json}");
+}
+
+fn run(cli: Cli) -> ssp::Result<bool> {
+    let mut backend = match cli.serial.as_deref() {
+        Some(path) => Backend::serial(path, cli.command.needs_enable())?,
+        None => Backend::socket(cli.socket)?,
+    };
+
+    if let Command::Watch = cli.command {
+        return backend.watch(print_event).map(|_| true);
+    }
+
+    let event = cli.command.to_event().map_err(ssp::Error::Event)?;
+    let res = backend.request(&event)?;
+
+    print_event(&res);
+
+    Ok(res.method() != Method::Fail)
+}
+
+fn main() -> ExitCode {
+    env_logger::init();
+
+    match run(Cli::parse()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ssp::EventPayload;
+
+    #[test]
+    fn test_cli_socket_override() {
+        let cli = Cli::parse_from(["ssp-client", "--socket", "/tmp/test.sock", "status"]);
+        assert_eq!(cli.socket, Endpoint::Unix("/tmp/test.sock".into()));
+        assert!(cli.serial.is_none());
+
+        let cli = Cli::parse_from(["ssp-client", "--socket", "ws://10.0.0.2:7879", "watch"]);
+        assert_eq!(cli.socket, Endpoint::WebSocket("10.0.0.2:7879".into()));
+
+        assert!(Cli::try_parse_from(["ssp-client", "--socket", "tcp://", "status"]).is_err());
+
+        let cli = Cli::parse_from(["ssp-client", "--serial", "/dev/ttyUSB1", "watch"]);
+        assert_eq!(cli.serial.as_deref(), Some("/dev/ttyUSB1"));
+    }
+
+    #[test]
+    fn test_cli_needs_enable() {
+        let cli = Cli::parse_from(["ssp-client", "--serial", "/dev/ttyUSB1", "watch"]);
+        assert!(cli.command.needs_enable());
+
+        let cli = Cli::parse_from(["ssp-client", "dispense", "1x500"]);
+        assert!(cli.command.needs_enable());
+
+        for cmd in ["enable", "disable", "status", "reject", "stack"] {
+            let cli = Cli::parse_from(["ssp-client", "--serial", "/dev/ttyUSB1", cmd]);
+            assert!(
+                !cli.command.needs_enable(),
+                "{cmd} should not enable the device"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cli_dispense() -> Result<(), String> {
+        let cli = Cli::parse_from([
+            "ssp-client",
+            "dispense",
+            "--currency",
+            "gbp",
+            "2x500",
+            "1X1000",
+        ]);
+        let event = cli.command.to_event()?;
+
+        let exp_payouts = PayoutVec::from_slice(&[
+            PayoutDenomination::create(2, 500, CountryCode::GBP),
+            PayoutDenomination::create(1, 1000, CountryCode::GBP),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            event.payload(),
+            &EventPayload::DispenseEvent(DispenseEvent::create(PayoutDenominationList::create(
+                exp_payouts
+            )))
+        );
+
+        assert!(Cli::try_parse_from(["ssp-client", "dispense", "500"]).is_err());
+        assert!(
+            Cli::try_parse_from(["ssp-client", "dispense", "--currency", "EURO", "1x5"]).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_set_inhibits() -> Result<(), String> {
+        let cli = Cli::parse_from(["ssp-client", "set-inhibits", "1", "2", "16"]);
+        let event = cli.command.to_event()?;
+
+        assert_eq!(event.method(), Method::SetInhibits);
+        assert_eq!(
+            event.payload(),
+            &EventPayload::SetInhibitsEvent(SetInhibitsEvent::create(
+                InhibitChannelVec::from_slice(&[1, 2, 16]).unwrap()
+            ))
+        );
+
+        assert!(Cli::try_parse_from(["ssp-client", "set-inhibits", "0"]).is_err());
+        assert!(Cli::try_parse_from(["ssp-client", "set-inhibits", "65"]).is_err());
+
+        Ok(())
+    }
+}
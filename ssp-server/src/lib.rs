@@ -0,0 +1,26 @@
+//! JSON-RPC server for SSP/eSSP devices.
+//!
+//! The server owns the serial [Device](ssp::Device), and serves
+//! [jsonrpc::Client](ssp::jsonrpc::Client) connections over a Unix domain socket. It can also
+//! listen on TCP and WebSocket [Endpoint](ssp::jsonrpc::Endpoint)s, e.g. for a browser UI.
+//!
+//! Requests are decoded into [Event](ssp::Event)s, and run against the device. Events reported by
+//! the device in [PollResponse](ssp::PollResponse)s are pushed to every connected client as
+//! JSON-RPC notifications.
+
+mod server;
+
+pub use server::*;
+
+/// Environment variable for the serial port path.
+pub const SERIAL_ENV_PATH: &str = "SSP_SERIAL_PATH";
+/// Default serial port path.
+pub const SERIAL_PATH: &str = "/dev/ttyUSB0";
+
+/// Environment variable for the device polling interval, in milliseconds.
+pub const POLL_ENV_INTERVAL: &str = "SSP_POLL_INTERVAL_MS";
+/// Default time between device polls, in milliseconds.
+///
+/// From the SSP Implementation Guide, the delay between polls should be no less than 200 ms, and
+/// no greater than 1000 ms.
+pub const DEFAULT_POLL_INTERVAL_MS: u64 = 200;
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use ssp::jsonrpc::{
+    get_endpoint, Endpoint, JSONRPC_ENV_SOCK, JSONRPC_ENV_TCP, JSONRPC_ENV_WS, JSONRPC_SOCKET_PATH,
+};
+use ssp::{Device, Result, SerialLink};
+use ssp_server::{
+    Server, DEFAULT_POLL_INTERVAL_MS, POLL_ENV_INTERVAL, SERIAL_ENV_PATH, SERIAL_PATH,
+};
+
+fn main() -> Result<()> {
+    env_logger::init();
+
+    let serial_path = std::env::args()
+        .nth(1)
+        .or_else(|| std::env::var(SERIAL_ENV_PATH).ok())
+        .unwrap_or_else(|| SERIAL_PATH.into());
+    let endpoint = get_endpoint(JSONRPC_ENV_SOCK, JSONRPC_SOCKET_PATH)?;
+
+    let poll_interval = std::env::var(POLL_ENV_INTERVAL)
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_POLL_INTERVAL_MS);
+
+    log::info!("Opening serial port: {serial_path}");
+
+    let mut device = Device::new(SerialLink::open(serial_path.as_str())?);
+    let setup = device.connect()?;
+
+    log::info!("Connected to device: {setup}");
+
+    let mut server =
+        Server::bind(device, endpoint)?.with_poll_interval(Duration::from_millis(poll_interval));
+
+    if let Ok(addr) = std::env::var(JSONRPC_ENV_TCP) {
+        server.listen(Endpoint::Tcp(addr))?;
+    }
+
+    if let Ok(addr) = std::env::var(JSONRPC_ENV_WS) {
+        server.listen(Endpoint::WebSocket(addr))?;
+    }
+
+    server.run()?;
+
+    Ok(())
+}
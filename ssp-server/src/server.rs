@@ -0,0 +1,538 @@
+//! JSON-RPC server for clients on Unix domain sockets, TCP, and WebSockets.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use std::{cmp, fs, thread};
+
+use smol_jsonrpc::{Request, Response};
+use ssp::jsonrpc::{Connection, Endpoint, Listener, DEFAULT_WRITE_TIMEOUT_MS};
+use ssp::{Device, Error, Event, EventPayload, Method, Result, Transport};
+
+use super::DEFAULT_POLL_INTERVAL_MS;
+
+/// Identifier for a connected client.
+pub type ClientId = u64;
+
+// each writer has its own lock, so the client map is only locked to add, remove, or list clients
+type ClientWriter = Arc<Mutex<Box<dyn Connection>>>;
+type Clients = Arc<Mutex<BTreeMap<ClientId, ClientWriter>>>;
+
+// time to wait for a request before checking if the client is still connected
+const CLIENT_READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+// bounds for the delay before retrying a failed accept, e.g. when out of file descriptors
+const ACCEPT_RETRY_MIN: Duration = Duration::from_millis(10);
+const ACCEPT_RETRY_MAX: Duration = Duration::from_secs(1);
+
+/// JSON-RPC server that owns an SSP [Device].
+///
+/// The server can listen on several [Endpoint]s at once, e.g. a Unix domain socket for local
+/// tools, and a WebSocket for a browser UI. All clients share the same device, and receive the
+/// same notifications.
+///
+/// Each client connection is read on its own thread, and requests are forwarded to the thread
+/// running [Server::run]. That thread is the only one talking to the [Device], so the sequence
+/// flag state stays consistent between polls and client commands.
+pub struct Server<T: Transport> {
+    device: Device<T>,
+    listeners: Vec<Box<dyn Listener>>,
+    endpoints: Vec<Endpoint>,
+    clients: Clients,
+    poll_interval: Duration,
+    running: bool,
+}
+
+impl<T: Transport> Server<T> {
+    /// Creates a new [Server] listening on the [Endpoint].
+    ///
+    /// For Unix domain sockets, any stale socket file left at the path is removed before binding.
+    ///
+    /// The [Device] is expected to already be connected, e.g. with [Device::connect].
+    pub fn bind<E: Into<Endpoint>>(device: Device<T>, endpoint: E) -> Result<Self> {
+        let mut server = Self {
+            device,
+            listeners: Vec::new(),
+            endpoints: Vec::new(),
+            clients: Arc::new(Mutex::new(BTreeMap::new())),
+            poll_interval: Duration::from_millis(DEFAULT_POLL_INTERVAL_MS),
+            running: false,
+        };
+
+        server.listen(endpoint)?;
+
+        Ok(server)
+    }
+
+    /// Listens for clients on an additional [Endpoint].
+    ///
+    /// TCP and WebSocket endpoints may use port zero, and get the assigned port from
+    /// [Server::endpoints].
+    pub fn listen<E: Into<Endpoint>>(&mut self, endpoint: E) -> Result<()> {
+        let listener = endpoint.into().bind()?;
+
+        self.endpoints.push(listener.local_endpoint()?);
+        self.listeners.push(listener);
+
+        Ok(())
+    }
+
+    /// Builder function that sets the time between device polls.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Gets the [Endpoint]s the server listens on.
+    pub fn endpoints(&self) -> &[Endpoint] {
+        self.endpoints.as_ref()
+    }
+
+    /// Gets a reference to the [Device].
+    pub fn device(&self) -> &Device<T> {
+        &self.device
+    }
+
+    /// Gets a mutable reference to the [Device].
+    pub fn device_mut(&mut self) -> &mut Device<T> {
+        &mut self.device
+    }
+
+    /// Runs the server until a client sends a [Method::Shutdown] request.
+    ///
+    /// Client requests are handled as they arrive. The device is polled every poll interval,
+    /// regardless of client traffic, and the reported events are pushed to all clients as
+    /// notifications.
+    pub fn run(mut self) -> Result<Device<T>> {
+        let (req_tx, req_rx) = mpsc::channel::<(ClientId, Request)>();
+
+        // client IDs are unique across all listeners
+        let next_id = Arc::new(AtomicU64::new(1));
+        let stop = Arc::new(AtomicBool::new(false));
+        let mut accept_threads = Vec::with_capacity(self.listeners.len());
+
+        for (listener, endpoint) in self.listeners.drain(..).zip(self.endpoints.iter()) {
+            let clients = Arc::clone(&self.clients);
+            let next_id = Arc::clone(&next_id);
+            let stop = Arc::clone(&stop);
+            let req_tx = req_tx.clone();
+
+            accept_threads.push(thread::spawn(move || {
+                accept_clients(listener, clients, next_id, stop, req_tx)
+            }));
+
+            log::info!("Listening on {endpoint}");
+        }
+
+        // only the listener threads send requests, so the channel closes if they all stop
+        drop(req_tx);
+
+        self.running = true;
+
+        // polls run on a fixed schedule, so a busy client can not starve the device watchdog
+        let mut next_poll = Instant::now() + self.poll_interval;
+        let mut res = Ok(());
+
+        while self.running {
+            let now = Instant::now();
+
+            if now >= next_poll {
+                if let Err(err) = self.poll() {
+                    log::warn!("Error polling device: {err}");
+                }
+
+                // skip missed polls, instead of polling back-to-back to catch up
+                next_poll = cmp::max(next_poll + self.poll_interval, Instant::now());
+                continue;
+            }
+
+            match req_rx.recv_timeout(next_poll - now) {
+                Ok((id, request)) => {
+                    let mut response = Response::from(self.handle_request(&request));
+                    if let Some(req_id) = request.id() {
+                        response.set_id(req_id);
+                    }
+
+                    self.send_to(id, &serde_json::to_string(&response)?);
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => (),
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    res = Err(Error::Io("client listener stopped".into()));
+                    break;
+                }
+            }
+        }
+
+        log::info!("Shutting down server");
+
+        self.stop_listeners(&stop, accept_threads);
+        self.shutdown();
+
+        res.map(|_| self.device)
+    }
+
+    /// Runs a client request against the device, and returns the response [Event].
+    pub fn handle_request(&mut self, request: &Request) -> Event {
+        let event = Event::from(request);
+
+        log::debug!("Handling request: {event}");
+
+        if event.method() == Method::Shutdown {
+            self.running = false;
+        }
+
+        self.device
+            .handle_event(&event)
+            .unwrap_or_else(|err| Event::new(Method::Fail, EventPayload::Error(err)))
+    }
+
+    /// Polls the device, and pushes any reported events to all clients.
+    pub fn poll(&mut self) -> Result<()> {
+        let res = self.device.poll()?;
+
+        for payload in res.events(self.device.protocol_version()) {
+            match payload {
+                Ok(payload) => self.notify(&Event::new(payload.method(), payload)),
+                Err(err) => log::warn!("Error parsing poll event: {err}"),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pushes an [Event] to all connected clients as a JSON-RPC notification.
+    pub fn notify(&self, event: &Event) {
+        log::debug!("Sending notification: {event}");
+
+        // notifications have no ID, since no response is expected
+        let notification = Request::new()
+            .with_method(event.method().to_str())
+            .with_params(event.payload().to_json());
+
+        match serde_json::to_string(&notification) {
+            Ok(json) => {
+                // write without holding the client map, so a stalled client does not block
+                // clients connecting or disconnecting
+                let writers: Vec<(ClientId, ClientWriter)> = lock(&self.clients)
+                    .iter()
+                    .map(|(id, writer)| (*id, Arc::clone(writer)))
+                    .collect();
+
+                for (id, writer) in writers {
+                    self.send_message(id, &writer, &json);
+                }
+            }
+            Err(err) => log::error!("Error encoding notification: {err}"),
+        }
+    }
+
+    fn send_to(&self, id: ClientId, json: &str) {
+        let writer = lock(&self.clients).get(&id).map(Arc::clone);
+
+        if let Some(writer) = writer {
+            self.send_message(id, &writer, json);
+        }
+    }
+
+    fn send_message(&self, id: ClientId, writer: &ClientWriter, json: &str) {
+        // a stalled client is dropped, instead of blocking the device thread
+        let timeout = Duration::from_millis(DEFAULT_WRITE_TIMEOUT_MS);
+
+        if let Err(err) = lock(writer).send_message(json, timeout) {
+            log::info!("Dropping client {id}: {err}");
+            lock(writer).shutdown().ok();
+            lock(&self.clients).remove(&id);
+        }
+    }
+
+    fn stop_listeners(&self, stop: &AtomicBool, accept_threads: Vec<JoinHandle<()>>) {
+        stop.store(true, Ordering::SeqCst);
+
+        for (endpoint, handle) in self.endpoints.iter().zip(accept_threads) {
+            // wake the listener thread blocked in `accept`, so it sees the stop flag
+            match endpoint.connect() {
+                Ok(conn) => {
+                    conn.shutdown().ok();
+                    handle.join().ok();
+                }
+                Err(err) => log::warn!("Error stopping listener on {endpoint}: {err}"),
+            }
+        }
+    }
+
+    fn shutdown(&mut self) {
+        let mut clients = lock(&self.clients);
+
+        for (_, writer) in clients.iter() {
+            lock(writer).shutdown().ok();
+        }
+
+        clients.clear();
+
+        for path in self.endpoints.iter().filter_map(|e| e.socket_path()) {
+            fs::remove_file(path).ok();
+        }
+    }
+}
+
+// a panicked thread can not leave the client state inconsistent, so poisoned locks are recovered
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+fn accept_clients(
+    listener: Box<dyn Listener>,
+    clients: Clients,
+    next_id: Arc<AtomicU64>,
+    stop: Arc<AtomicBool>,
+    requests: mpsc::Sender<(ClientId, Request)>,
+) {
+    let mut retry_delay = ACCEPT_RETRY_MIN;
+
+    loop {
+        let res = listener.accept();
+
+        // the server wakes the listener with a connection of its own when stopping
+        if stop.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let conn = match res {
+            Ok(c) => {
+                retry_delay = ACCEPT_RETRY_MIN;
+                c
+            }
+            Err(err) => {
+                log::warn!("Error accepting client, retrying in {retry_delay:?}: {err}");
+
+                // back off, so a persistent error does not turn into a busy loop
+                thread::sleep(retry_delay);
+                retry_delay = cmp::min(retry_delay * 2, ACCEPT_RETRY_MAX);
+                continue;
+            }
+        };
+
+        let id = next_id.fetch_add(1, Ordering::Relaxed);
+
+        match conn.try_clone() {
+            Ok(writer) => {
+                log::info!("Client {id} connected");
+                lock(&clients).insert(id, Arc::new(Mutex::new(writer)));
+            }
+            Err(err) => {
+                log::warn!("Error accepting client {id}: {err}");
+                continue;
+            }
+        }
+
+        let clients = Arc::clone(&clients);
+        let requests = requests.clone();
+
+        thread::spawn(move || {
+            read_requests(id, conn, &requests);
+
+            log::info!("Client {id} disconnected");
+            lock(&clients).remove(&id);
+        });
+    }
+}
+
+fn read_requests(
+    id: ClientId,
+    mut conn: Box<dyn Connection>,
+    requests: &mpsc::Sender<(ClientId, Request)>,
+) {
+    loop {
+        let line = match conn.recv_message(CLIENT_READ_TIMEOUT) {
+            Ok(Some(l)) => l,
+            Ok(None) => continue,
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return,
+            Err(err) => {
+                log::warn!("Error reading from client {id}: {err}");
+                return;
+            }
+        };
+
+        log::debug!("Client {id} request: {line}");
+
+        let request = match serde_json::from_str::<Request>(&line) {
+            Ok(r) => r,
+            Err(err) => {
+                log::warn!("Invalid request from client {id}: {err}");
+                // decoded as a `Method::Fail` event, so the client still gets an error response
+                Request::new()
+                    .with_method(Method::Fail.to_str())
+                    .with_params(Error::JsonRpc(format!("invalid request: {err}")))
+            }
+        };
+
+        if requests.send((id, request)).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::VecDeque;
+
+    use ssp::jsonrpc::Client;
+    use ssp::{MessageType, ResponseStatus};
+
+    const STX: u8 = 0x7f;
+    const SEQ_ID: usize = 1;
+    const COMMAND: usize = 3;
+
+    type PollEvents = Arc<Mutex<VecDeque<Vec<u8>>>>;
+
+    /// Fake device that accepts every command, and reports queued events in poll replies.
+    struct FakeTransport {
+        seq_id: u8,
+        command: u8,
+        events: PollEvents,
+    }
+
+    impl Transport for FakeTransport {
+        fn send_frame(&mut self, frame: &[u8]) -> Result<()> {
+            self.seq_id = frame[SEQ_ID];
+            self.command = frame[COMMAND];
+            Ok(())
+        }
+
+        fn receive_frame(&mut self, buf: &mut [u8]) -> Result<usize> {
+            let mut data = vec![ResponseStatus::Ok.to_u8()];
+
+            if self.command == u8::from(MessageType::Poll) {
+                if let Some(event) = self.events.lock().unwrap().pop_front() {
+                    data.extend_from_slice(event.as_ref());
+                }
+            }
+
+            let mut frame = vec![STX, self.seq_id, data.len() as u8];
+            frame.extend_from_slice(data.as_ref());
+            frame.extend_from_slice(
+                ssp::crc::crc16(frame[SEQ_ID..].as_ref())
+                    .to_le_bytes()
+                    .as_ref(),
+            );
+
+            buf[..frame.len()].copy_from_slice(frame.as_ref());
+
+            Ok(frame.len())
+        }
+
+        fn timeout(&self) -> Duration {
+            Duration::from_millis(100)
+        }
+
+        fn set_timeout(&mut self, _timeout: Duration) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_server_requests_and_notifications() -> Result<()> {
+        let events = PollEvents::default();
+        let transport = FakeTransport {
+            seq_id: 0,
+            command: 0,
+            events: Arc::clone(&events),
+        };
+
+        let socket_path =
+            std::env::temp_dir().join(format!("ssp-server-{}.sock", std::process::id()));
+
+        let mut server = Server::bind(Device::new(transport), socket_path.clone())?
+            .with_poll_interval(Duration::from_millis(10));
+        server.listen(Endpoint::Tcp("127.0.0.1:0".into()))?;
+        server.listen(Endpoint::WebSocket("127.0.0.1:0".into()))?;
+
+        let endpoints = server.endpoints().to_vec();
+        assert_eq!(endpoints[0], Endpoint::Unix(socket_path.clone()));
+
+        let handle = thread::spawn(move || server.run());
+
+        let socket_str = socket_path.to_string_lossy().into_owned();
+        let mut client = Client::new(socket_str.as_str())?;
+        let mut tcp_client = Client::connect(endpoints[1].clone())?;
+        let mut ws_client = Client::connect(endpoints[2].clone())?;
+
+        let res = client.send(Method::Disable)?;
+        assert_eq!(res.method(), Method::Disable);
+
+        assert_eq!(tcp_client.send(Method::Status)?.method(), Method::Status);
+        assert_eq!(ws_client.send(Method::Reject)?.method(), Method::Reject);
+
+        events
+            .lock()
+            .unwrap()
+            .push_back(vec![ResponseStatus::NoteCredit.to_u8(), 0x02]);
+
+        for client in [&mut client, &mut tcp_client, &mut ws_client] {
+            let event = client.next_event(Duration::from_secs(1))?;
+            assert_eq!(event.method(), Method::NoteCredit);
+            assert!(client.receive()?.is_empty());
+        }
+
+        let res = Client::new(socket_str.as_str())?.send(Method::Shutdown)?;
+        assert_eq!(res.method(), Method::Disable);
+
+        handle.join().unwrap()?;
+        assert!(!socket_path.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_server_polls_during_requests() -> Result<()> {
+        let events = PollEvents::default();
+        let transport = FakeTransport {
+            seq_id: 0,
+            command: 0,
+            events: Arc::clone(&events),
+        };
+
+        let server = Server::bind(Device::new(transport), Endpoint::Tcp("127.0.0.1:0".into()))?
+            .with_poll_interval(Duration::from_millis(50));
+        let endpoint = server.endpoints()[0].clone();
+
+        let handle = thread::spawn(move || server.run());
+
+        let mut client = Client::connect(endpoint.clone())?;
+
+        events
+            .lock()
+            .unwrap()
+            .push_back(vec![ResponseStatus::NoteCredit.to_u8(), 0x02]);
+
+        // requests arrive faster than the poll interval, polls must still go out
+        let start = std::time::Instant::now();
+        while !events.lock().unwrap().is_empty() {
+            assert!(
+                start.elapsed() < Duration::from_secs(1),
+                "device was not polled"
+            );
+
+            assert_eq!(client.send(Method::Status)?.method(), Method::Status);
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        assert_eq!(
+            client.next_event(Duration::from_secs(1))?.method(),
+            Method::NoteCredit
+        );
+
+        Client::connect(endpoint.clone())?.send(Method::Shutdown)?;
+        handle.join().unwrap()?;
+
+        // the listener threads are joined, so the port no longer accepts clients
+        assert!(Client::connect(endpoint).is_err());
+
+        Ok(())
+    }
+}